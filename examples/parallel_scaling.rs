@@ -0,0 +1,30 @@
+//! Time `make_assignments_parallel` on 1, 2, 4 and 8 threads.
+//!
+//! Usage: `cargo run --release --example parallel_scaling [n] [min_group_size]`
+//!
+//! Defaults to `(7, 2)`. The exhaustive search grows explosively with `n`:
+//! `(10, 2)` enumerates every ordering of every 1-factorization of `K10` and
+//! will not finish in any reasonable time, parallel or not.
+use std::time::Instant;
+
+use group_generator::{make_assignments_parallel, ConflictMatrix};
+
+fn main() {
+    let mut args = std::env::args().skip(1).map(|arg| arg.parse::<usize>().expect("Arguments must be integers"));
+    let n = args.next().unwrap_or(7);
+    let min_group_size = args.next().unwrap_or(2);
+    let conflicts = ConflictMatrix::empty(n);
+
+    let mut baseline = None;
+    for threads in [1, 2, 4, 8] {
+        let start = Instant::now();
+        let sols = make_assignments_parallel(&conflicts, min_group_size, threads);
+        let elapsed = start.elapsed().as_secs_f64();
+        let speedup = baseline.get_or_insert(elapsed).to_owned() / elapsed;
+        println!(
+            "({n}, {min_group_size}) threads={threads}: {} assignments of {} rounds in {elapsed:.3}s (speedup {speedup:.2}x)",
+            sols.len(),
+            sols.first().map_or(0, Vec::len),
+        );
+    }
+}
@@ -0,0 +1,560 @@
+//! # Assignment Analysis
+//!
+//! Helpers for inspecting assignments produced by the solver, and for ranking
+//! the tie set of maximal-round assignments by some secondary quality.
+use alloc::collections::BTreeMap;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use bitvec::prelude::*;
+
+use crate::{ConflictMatrix, RawGroup};
+
+/// Count the number of **distinct partners** each vertex meets over the course
+/// of an assignment.
+///
+/// Entry `v` of the result is the number of other vertices which shared at
+/// least one group with `v` in any round.
+pub fn distinct_partners(assignment: &[Vec<RawGroup>], n: usize) -> Vec<usize> {
+    let mut met = vec![bitvec![0; n]; n];
+    for round in assignment {
+        for g in round {
+            for i in g {
+                for j in g {
+                    if i != j {
+                        met[*i].set(*j, true);
+                    }
+                }
+            }
+        }
+    }
+    met.iter().map(|row| row.count_ones()).collect()
+}
+
+/// Sort assignments best-first by how widely partners are spread.
+///
+/// The ranking key is the *total* number of distinct partners summed over all
+/// vertices, with ties broken by the *minimum* number of distinct partners any
+/// single vertex has (so nobody is left meeting unusually few people). The sort
+/// is stable, so equally ranked assignments keep their search order.
+pub fn rank_by_partner_spread(sols: &mut [Vec<Vec<RawGroup>>], n: usize) {
+    sols.sort_by_cached_key(|assignment| {
+        let partners = distinct_partners(assignment, n);
+        let total: usize = partners.iter().sum();
+        let min = partners.iter().copied().min().unwrap_or(0);
+        core::cmp::Reverse((total, min))
+    });
+}
+
+/// Total affinity satisfied by an assignment.
+///
+/// `affinity[u][v]` is how much `u` would like to be grouped with `v`. Every
+/// time `u` and `v` share a group, both `affinity[u][v]` and `affinity[v][u]`
+/// are added, so mutual preferences count from both sides.
+pub fn affinity_score(assignment: &[Vec<RawGroup>], affinity: &[Vec<u32>]) -> u64 {
+    assignment.iter().flatten().map(|g| group_affinity(g, affinity)).sum()
+}
+
+/// [`affinity_score()`] of a single group.
+pub(crate) fn group_affinity(g: &[usize], affinity: &[Vec<u32>]) -> u64 {
+    g.iter()
+        .flat_map(|u| g.iter().filter(move |v| *v != u).map(move |v| u64::from(affinity[*u][*v])))
+        .sum()
+}
+
+/// Total penalty of the soft conflicts an assignment breaks.
+///
+/// `penalties[u][v]` is the cost of grouping `u` with `v`, counted from both
+/// sides each time they share a group, exactly as [`affinity_score()`]
+/// counts affinity.
+pub fn soft_conflict_penalty(assignment: &[Vec<RawGroup>], penalties: &[Vec<u32>]) -> u64 {
+    affinity_score(assignment, penalties)
+}
+
+/// Sort assignments best-first by [`affinity_score()`]. The sort is stable,
+/// so equally ranked assignments keep their search order.
+pub fn rank_by_affinity(sols: &mut [Vec<Vec<RawGroup>>], affinity: &[Vec<u32>]) {
+    sols.sort_by_cached_key(|assignment| core::cmp::Reverse(affinity_score(assignment, affinity)));
+}
+
+/// The set of pairs grouped together at least once in `assignment`, as a
+/// symmetric `n x n` matrix with a clear diagonal.
+pub(crate) fn met_pairs(assignment: &[Vec<RawGroup>], n: usize) -> Vec<BitVec> {
+    let mut met = vec![bitvec![0; n]; n];
+    for g in assignment.iter().flatten() {
+        for u in g {
+            for v in g {
+                if u != v {
+                    met[*u].set(*v, true);
+                }
+            }
+        }
+    }
+    met
+}
+
+/// Distance between two assignments of `n` vertices: the number of unordered
+/// pairs grouped together in exactly one of them.
+///
+/// This is the size of the symmetric difference of their met-pair sets, so it
+/// ignores *when* and in which group a pair met. A distance of 0 means both
+/// assignments bring together exactly the same pairs.
+pub fn pair_distance(a: &[Vec<RawGroup>], b: &[Vec<RawGroup>], n: usize) -> usize {
+    let (a, b) = (met_pairs(a, n), met_pairs(b, n));
+    a.iter().zip(&b).map(|(x, y)| (x.clone() ^ y).count_ones()).sum::<usize>() / 2
+}
+
+/// Desired progression of group sizes over the course of a schedule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoundTrajectory {
+    /// Large groups first (e.g. a big icebreaker), shrinking later.
+    Decreasing,
+    /// Small groups first, growing later.
+    Increasing,
+    /// The same size throughout.
+    Flat,
+}
+
+/// Score how closely the round sizes of `assignment` follow `trajectory`.
+///
+/// A round's size is its largest group. Each pair of consecutive rounds
+/// scores `+1` if the size moves in the desired direction and `-1` if it moves
+/// against it; for [`RoundTrajectory::Flat`] every change scores `-1`. Higher
+/// is better, and an assignment with uniform round sizes scores 0.
+pub fn trajectory_score(assignment: &[Vec<RawGroup>], trajectory: RoundTrajectory) -> isize {
+    let sizes: Vec<usize> = assignment
+        .iter()
+        .map(|round| round.iter().map(Vec::len).max().unwrap_or(0))
+        .collect();
+    sizes
+        .windows(2)
+        .map(|w| match (trajectory, w[0].cmp(&w[1])) {
+            (_, core::cmp::Ordering::Equal) => 0,
+            (RoundTrajectory::Flat, _) => -1,
+            (RoundTrajectory::Decreasing, core::cmp::Ordering::Greater) => 1,
+            (RoundTrajectory::Increasing, core::cmp::Ordering::Less) => 1,
+            _ => -1,
+        })
+        .sum()
+}
+
+/// Sort assignments best-first by [`trajectory_score()`].
+///
+/// Meant for tie sets whose round shapes vary, and applied by
+/// [`make_assignments_with_trajectory()`](crate::make_assignments_with_trajectory)
+/// to schedules mixing shapes in different orders. The sort is stable, so
+/// equally ranked assignments keep their search order.
+pub fn rank_by_trajectory(sols: &mut [Vec<Vec<RawGroup>>], trajectory: RoundTrajectory) {
+    sols.sort_by_cached_key(|assignment| core::cmp::Reverse(trajectory_score(assignment, trajectory)));
+}
+
+/// Record *when* each pair of vertices met.
+///
+/// Entry `[i][j]` is `Some(r)` if `i` and `j` shared a group in round `r`, or
+/// `None` if they were never grouped together. Under no-repeat each pair meets
+/// at most once; if an assignment does repeat pairs, the most recent meeting
+/// is recorded. The diagonal is always `None`.
+pub fn met_rounds_matrix(assignment: &[Vec<RawGroup>], n: usize) -> Vec<Vec<Option<usize>>> {
+    let mut met = vec![vec![None; n]; n];
+    for (r, round) in assignment.iter().enumerate() {
+        for g in round {
+            for i in g {
+                for j in g {
+                    if i != j {
+                        met[*i][*j] = Some(r);
+                    }
+                }
+            }
+        }
+    }
+    met
+}
+
+/// Count *how often* each pair of vertices met.
+///
+/// Entry `[i][j]` is the number of rounds in which `i` and `j` shared a group.
+/// Under no-repeat every entry is 0 or 1. The diagonal is always 0.
+pub fn meeting_counts(assignment: &[Vec<RawGroup>], n: usize) -> Vec<Vec<usize>> {
+    let mut counts = vec![vec![0; n]; n];
+    for g in assignment.iter().flatten() {
+        for i in g {
+            for j in g {
+                if i != j {
+                    counts[*i][*j] += 1;
+                }
+            }
+        }
+    }
+    counts
+}
+
+/// Check that `assignment` is a valid schedule for `n` vertices:
+///
+/// - Every round partitions all vertices `0..n` (each appears exactly once).
+/// - Every group has at least `min_group_size` members.
+/// - No pair of vertices is grouped together more than once.
+///
+/// Accepts raw rounds as `Vec`s or slices of groups.
+pub fn validate_assignment<R: AsRef<[RawGroup]>>(assignment: &[R], n: usize, min_group_size: usize) -> bool {
+    let mut met = vec![bitvec![0; n]; n];
+    for round in assignment {
+        let mut seen = bitvec![0; n];
+        for g in round.as_ref() {
+            if g.len() < min_group_size {
+                return false;
+            }
+            for (i, u) in g.iter().enumerate() {
+                if *u >= n || seen[*u] {
+                    return false;
+                }
+                seen.set(*u, true);
+                for v in &g[..i] {
+                    if met[*u][*v] {
+                        return false;
+                    }
+                    met[*u].set(*v, true);
+                    met[*v].set(*u, true);
+                }
+            }
+        }
+        if !seen.all() {
+            return false;
+        }
+    }
+    true
+}
+
+/// Round-validation hook for *continuity*: whether every vertex in `round`
+/// shares its group with at least one person it was grouped with in
+/// `previous`.
+///
+/// Vertices which had no partner in `previous` (a singleton group, or absent)
+/// have nobody to carry over and are exempt. For the first round of a
+/// schedule there is no previous round, so callers should skip the check.
+///
+/// Under strict no-repeat this can never hold after the first round, so it is
+/// only meaningful for models which allow pairs to meet again. It is enforced
+/// by [`make_assignments_with_repeats_continuing()`](crate::make_assignments_with_repeats_continuing)
+/// and [`make_assignments_with_cooldown_continuing()`](crate::make_assignments_with_cooldown_continuing).
+pub fn has_continuity(previous: &[RawGroup], round: &[RawGroup]) -> bool {
+    round.iter().all(|g| {
+        g.iter().all(|v| {
+            let Some(before) = previous.iter().find(|p| p.contains(v)) else {
+                return true;
+            };
+            before.len() < 2 || g.iter().any(|u| u != v && before.contains(u))
+        })
+    })
+}
+
+/// Whether every round after the first satisfies [`has_continuity()`] with
+/// respect to the round before it.
+pub fn satisfies_continuity(assignment: &[Vec<RawGroup>]) -> bool {
+    assignment.windows(2).all(|w| has_continuity(&w[0], &w[1]))
+}
+
+/// Identify fragile placements: for each vertex, the rounds in which removing
+/// that vertex would leave its group below `min_group_size`.
+///
+/// Returns one `(vertex, rounds)` entry per vertex placed in the assignment,
+/// in ascending vertex order. An empty list of rounds means the schedule
+/// survives that vertex dropping out.
+pub fn dropout_robustness(assignment: &[Vec<RawGroup>], min_group_size: usize) -> Vec<(usize, Vec<usize>)> {
+    let mut fragile: Vec<(usize, Vec<usize>)> = assignment
+        .iter()
+        .flatten()
+        .flatten()
+        .map(|v| (*v, vec![]))
+        .collect();
+    fragile.sort_unstable();
+    fragile.dedup();
+    for (r, round) in assignment.iter().enumerate() {
+        for g in round.iter().filter(|g| g.len() <= min_group_size) {
+            for v in g {
+                if let Ok(i) = fragile.binary_search_by_key(v, |(u, _)| *u) {
+                    fragile[i].1.push(r);
+                }
+            }
+        }
+    }
+    fragile
+}
+
+/// Canonical form of a round: members sorted within each group, and groups
+/// sorted (equivalently, by least member). Two rounds describe the same
+/// partition exactly when their canonical forms are equal.
+pub fn canonical_round(round: &[RawGroup]) -> Vec<RawGroup> {
+    let mut round: Vec<RawGroup> = round
+        .iter()
+        .map(|g| {
+            let mut g = g.clone();
+            g.sort_unstable();
+            g
+        })
+        .collect();
+    round.sort_unstable();
+    round
+}
+
+/// Canonical form of an assignment: every round in canonical form (see
+/// [`canonical_round()`]), and the rounds themselves sorted.
+///
+/// Two assignments describe the same schedule up to the order of groups
+/// within rounds and the order of rounds exactly when their canonical forms
+/// are equal.
+pub fn canonical_assignment(assignment: &[Vec<RawGroup>]) -> Vec<Vec<RawGroup>> {
+    let mut rounds: Vec<Vec<RawGroup>> = assignment.iter().map(|round| canonical_round(round)).collect();
+    rounds.sort_unstable();
+    rounds
+}
+
+/// Put every assignment in canonical form (see [`canonical_assignment()`])
+/// and sort them, giving an ordering which only depends on the set of
+/// schedules, not on the order the search found them in.
+///
+/// Groups end up with ascending members and ordered by least member, rounds
+/// are ordered lexicographically by their groups, and assignments
+/// lexicographically by their rounds.
+pub fn sort_canonical(sols: &mut [Vec<Vec<RawGroup>>]) {
+    for assignment in sols.iter_mut() {
+        *assignment = canonical_assignment(assignment);
+    }
+    sols.sort_unstable();
+}
+
+/// Collapse assignments describing the same schedule up to the order of
+/// groups within rounds and the order of rounds, leaving each distinct
+/// schedule once, in canonical form and sorted (as by [`sort_canonical()`]).
+pub fn dedup_canonical(sols: &mut Vec<Vec<Vec<RawGroup>>>) {
+    sort_canonical(sols);
+    sols.dedup();
+}
+
+/// Partition assignments into classes of schedules which are the same up to
+/// relabeling vertices by an automorphism of the conflict graph, returning
+/// the first assignment of each class with the number of assignments in it.
+///
+/// An automorphism is a permutation of the vertices mapping conflicting pairs
+/// to conflicting pairs, so relabeled schedules are equally valid and equally
+/// good. Assignments differing only in the order of groups or rounds are also
+/// counted in the same class, so the multiplicities sum to `sols.len()`.
+/// Classes are in order of their first assignment.
+///
+/// # Cost
+///
+/// Every automorphism is enumerated once per class. With few conflicts there
+/// can be up to `n!` of them, so this is only practical for the small
+/// problems the exhaustive solver can handle anyway.
+pub fn isomorphism_classes(sols: &[Vec<Vec<RawGroup>>], conflicts: &ConflictMatrix) -> Vec<(Vec<Vec<RawGroup>>, usize)> {
+    let automorphisms = automorphisms(conflicts);
+    let mut class_of: BTreeMap<Vec<Vec<RawGroup>>, usize> = BTreeMap::new();
+    let mut classes: Vec<(Vec<Vec<RawGroup>>, usize)> = vec![];
+    for assignment in sols {
+        let canonical = canonical_assignment(assignment);
+        if let Some(class) = class_of.get(&canonical) {
+            classes[*class].1 += 1;
+            continue;
+        }
+        for perm in &automorphisms {
+            let relabeled: Vec<Vec<RawGroup>> = assignment
+                .iter()
+                .map(|round| round.iter().map(|g| g.iter().map(|v| perm[*v]).collect()).collect())
+                .collect();
+            class_of.insert(canonical_assignment(&relabeled), classes.len());
+        }
+        classes.push((assignment.clone(), 1));
+    }
+    classes
+}
+
+/// Every permutation `perm` of the vertices with `perm[u]` and `perm[v]`
+/// conflicting exactly when `u` and `v` do, including the identity.
+fn automorphisms(conflicts: &ConflictMatrix) -> Vec<Vec<usize>> {
+    fn extend(conflicts: &ConflictMatrix, perm: &mut Vec<usize>, used: &mut BitVec, res: &mut Vec<Vec<usize>>) {
+        let u = perm.len();
+        if u == conflicts.len() {
+            res.push(perm.clone());
+            return;
+        }
+        for image in used.iter_zeros().collect::<Vec<_>>() {
+            let consistent = conflicts.degree(u) == conflicts.degree(image)
+                && (0..u).all(|v| conflicts.is_conflicted(u, v) == conflicts.is_conflicted(image, perm[v]));
+            if consistent {
+                used.set(image, true);
+                perm.push(image);
+                extend(conflicts, perm, used, res);
+                perm.pop();
+                used.set(image, false);
+            }
+        }
+    }
+    let mut res = vec![];
+    extend(conflicts, &mut vec![], &mut bitvec![0; conflicts.len()], &mut res);
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::complete_seven_two;
+
+    #[test]
+    fn canonical_sort_ignores_search_order() {
+        let mut sols = vec![
+            vec![vec![vec![3, 1], vec![2, 0]], vec![vec![1, 0], vec![2, 3]]],
+            vec![vec![vec![0, 1], vec![2, 3]], vec![vec![0, 2], vec![1, 3]]],
+            vec![vec![vec![0, 3], vec![1, 2]]],
+        ];
+        let mut reversed: Vec<_> = sols.iter().rev().cloned().collect();
+        sort_canonical(&mut sols);
+        sort_canonical(&mut reversed);
+        assert_eq!(sols, reversed);
+        assert_eq!(
+            sols,
+            vec![
+                vec![vec![vec![0, 1], vec![2, 3]], vec![vec![0, 2], vec![1, 3]]],
+                vec![vec![vec![0, 1], vec![2, 3]], vec![vec![0, 2], vec![1, 3]]],
+                vec![vec![vec![0, 3], vec![1, 2]]],
+            ]
+        );
+    }
+
+    #[test]
+    fn dedup_collapses_reorderings() {
+        let mut sols = vec![
+            vec![vec![vec![0, 1], vec![2, 3]], vec![vec![0, 2], vec![1, 3]]],
+            vec![vec![vec![3, 1], vec![2, 0]], vec![vec![1, 0], vec![2, 3]]],
+            vec![vec![vec![2, 3], vec![0, 1]], vec![vec![0, 3], vec![1, 2]]],
+        ];
+        dedup_canonical(&mut sols);
+        assert_eq!(
+            sols,
+            vec![
+                vec![vec![vec![0, 1], vec![2, 3]], vec![vec![0, 2], vec![1, 3]]],
+                vec![vec![vec![0, 1], vec![2, 3]], vec![vec![0, 3], vec![1, 2]]],
+            ]
+        );
+    }
+
+    #[test]
+    fn relabelings_share_a_class() {
+        let sols = crate::make_assignments(&mut crate::tests::diagonal(6), 3);
+        let classes = isomorphism_classes(&sols, &crate::tests::diagonal(6));
+        assert_eq!(classes, vec![(sols[0].clone(), sols.len())]);
+
+        // Only the ends of the path 0 - 1 - 2, or the free vertices 3 and 4,
+        // can be swapped, which leaves two essentially different schedules
+        let conflicts = ConflictMatrix::empty(5).with_pairs([(0, 1), (1, 2)]);
+        let sols = crate::make_assignments(&mut conflicts.clone(), 2);
+        let classes = isomorphism_classes(&sols, &conflicts);
+        assert_eq!(classes.len(), 2);
+        assert_eq!(classes.iter().map(|(_, count)| count).sum::<usize>(), sols.len());
+        assert_eq!(classes[0].0, sols[0]);
+    }
+
+    #[test]
+    fn automorphisms_preserve_conflicts() {
+        assert_eq!(automorphisms(&crate::tests::diagonal(4)).len(), 24);
+        // A path 0 - 1 - 2 can only be reversed
+        let path = ConflictMatrix::empty(3).with_pairs([(0, 1), (1, 2)]);
+        assert_eq!(automorphisms(&path), vec![vec![0, 1, 2], vec![2, 1, 0]]);
+    }
+
+    #[test]
+    fn continuity_requires_carry_over() {
+        let first = vec![vec![0, 1], vec![2, 3]];
+        let carried = vec![vec![0, 1, 2, 3]];
+        let shuffled = vec![vec![0, 2], vec![1, 3]];
+        assert!(has_continuity(&first, &carried));
+        assert!(!has_continuity(&first, &shuffled));
+        assert!(satisfies_continuity(std::slice::from_ref(&first)));
+        assert!(satisfies_continuity(&[first.clone(), carried.clone(), first.clone()]));
+        assert!(!satisfies_continuity(&[first, shuffled]));
+        // A vertex sitting alone has nobody to carry over
+        assert!(has_continuity(&[vec![0], vec![1, 2]], &[vec![0, 1, 2]]));
+        // No-repeat schedules never have continuity past the first round
+        assert!(!complete_seven_two().iter().any(|a| satisfies_continuity(a)));
+    }
+
+    #[test]
+    fn dropout_flags_minimal_groups() {
+        let assignment = vec![vec![vec![0, 1, 2], vec![3, 4]], vec![vec![0, 3, 4], vec![1, 2]]];
+        let expected = vec![(0, vec![]), (1, vec![1]), (2, vec![1]), (3, vec![0]), (4, vec![0])];
+        assert_eq!(dropout_robustness(&assignment, 2), expected);
+    }
+
+    #[test]
+    fn validates_assignments() {
+        assert!(complete_seven_two().iter().all(|a| validate_assignment(a, 7, 2)));
+        let repeated = vec![vec![vec![0, 1], vec![2, 3]], vec![vec![1, 0], vec![2, 3]]];
+        assert!(!validate_assignment(&repeated, 4, 2));
+        let missing = vec![vec![vec![0, 1], vec![2]]];
+        assert!(!validate_assignment(&missing, 4, 1));
+        let undersized = vec![vec![vec![0, 1, 2], vec![3]]];
+        assert!(!validate_assignment(&undersized, 4, 2));
+        let duplicated = vec![vec![vec![0, 1], vec![1, 2, 3]]];
+        assert!(!validate_assignment(&duplicated, 4, 2));
+    }
+
+    #[test]
+    fn partner_spread_ranking() {
+        let n = 7;
+        let mut ranked = complete_seven_two().clone();
+        rank_by_partner_spread(&mut ranked, n);
+        let keys: Vec<(usize, usize)> = ranked
+            .iter()
+            .map(|a| {
+                let partners = distinct_partners(a, n);
+                (partners.iter().sum(), *partners.iter().min().unwrap())
+            })
+            .collect();
+        assert!(keys.windows(2).all(|w| w[0] >= w[1]));
+        assert!(keys.first() > keys.last(), "Expected maximal solutions to differ in spread");
+    }
+
+    #[test]
+    fn affinity_counts_both_directions() {
+        let mut affinity = vec![vec![0; 4]; 4];
+        affinity[0][1] = 3;
+        affinity[1][0] = 1;
+        affinity[2][3] = 2;
+        let together = vec![vec![vec![0, 1], vec![2, 3]]];
+        let apart = vec![vec![vec![0, 2], vec![1, 3]]];
+        assert_eq!(affinity_score(&together, &affinity), 6);
+        assert_eq!(affinity_score(&apart, &affinity), 0);
+        let mut ranked = vec![apart.clone(), together.clone()];
+        rank_by_affinity(&mut ranked, &affinity);
+        assert_eq!(ranked, vec![together, apart]);
+    }
+
+    #[test]
+    fn trajectory_reorders_solver_output() {
+        let mut ranked = crate::make_assignments_with_trajectory(&mut crate::tests::diagonal(6), &[2, 3], 2, RoundTrajectory::Flat).unwrap();
+        assert_eq!(trajectory_score(&ranked[0], RoundTrajectory::Flat), 0);
+        rank_by_trajectory(&mut ranked, RoundTrajectory::Decreasing);
+        let scores: Vec<isize> = ranked.iter().map(|a| trajectory_score(a, RoundTrajectory::Decreasing)).collect();
+        assert!(scores.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!((scores[0], scores[scores.len() - 1]), (1, -1));
+        assert_eq!(ranked[0][0].len(), 2);
+        assert_eq!(trajectory_score(&ranked[0], RoundTrajectory::Increasing), -1);
+    }
+
+    #[test]
+    fn met_rounds_recorded() {
+        let n = 7;
+        let assignment = &complete_seven_two()[0];
+        let met = met_rounds_matrix(assignment, n);
+        for (r, round) in assignment.iter().enumerate() {
+            for g in round {
+                for i in g {
+                    for j in g {
+                        let expected = if i == j { None } else { Some(r) };
+                        assert_eq!(met[*i][*j], expected);
+                    }
+                }
+            }
+        }
+        let met_pairs = met.iter().flatten().filter(|m| m.is_some()).count();
+        assert_eq!(met_pairs, 2 * 3 * (3 + 1 + 1));
+    }
+}
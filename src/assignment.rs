@@ -0,0 +1,288 @@
+//! # Typed Groups, Rounds and Assignments
+//!
+//! Thin newtype wrappers over the raw `Vec<usize>`, `Vec<Vec<usize>>` and
+//! `Vec<Vec<Vec<usize>>>` representations used by the solver, giving them
+//! domain methods, indexing and readable printing. All convert freely to and
+//! from the raw forms.
+use alloc::vec::Vec;
+use core::fmt;
+use core::ops::Index;
+
+use crate::analysis::validate_assignment;
+use crate::{ConflictMatrix, RawGroup};
+
+/// A single group: the vertices seated together.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Group(pub Vec<usize>);
+
+/// A single round: a partition of the vertices into groups.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Round(pub Vec<Group>);
+
+/// One full schedule: a sequence of rounds.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Assignment(pub Vec<Round>);
+
+impl Group {
+    /// The vertices in this group.
+    pub fn members(&self) -> &[usize] {
+        &self.0
+    }
+
+    /// Whether vertex `v` is in this group.
+    pub fn contains(&self, v: usize) -> bool {
+        self.0.contains(&v)
+    }
+}
+
+impl Round {
+    /// The groups making up this round.
+    pub fn groups(&self) -> &[Group] {
+        &self.0
+    }
+
+    /// Every vertex placed in this round, in group order.
+    pub fn members(&self) -> impl Iterator<Item = usize> + '_ {
+        self.0.iter().flat_map(|g| g.members()).copied()
+    }
+
+    /// The group containing vertex `v`, if `v` was placed in this round.
+    pub fn group_of(&self, v: usize) -> Option<&Group> {
+        self.0.iter().find(|g| g.contains(v))
+    }
+}
+
+impl Assignment {
+    /// The rounds making up this assignment, in order.
+    pub fn rounds(&self) -> &[Round] {
+        &self.0
+    }
+
+    /// Number of rounds in this assignment.
+    pub fn num_rounds(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Every pair `(u, v)` with `u < v` which shares a group in some round,
+    /// in ascending order and without repeats.
+    pub fn pairs_met(&self) -> Vec<(usize, usize)> {
+        let mut pairs: Vec<(usize, usize)> = self
+            .0
+            .iter()
+            .flat_map(|round| round.groups())
+            .flat_map(|g| {
+                let members = g.members();
+                members
+                    .iter()
+                    .flat_map(move |u| members.iter().filter(move |v| u < v).map(move |v| (*u, *v)))
+            })
+            .collect();
+        pairs.sort_unstable();
+        pairs.dedup();
+        pairs
+    }
+
+    /// Number of distinct partners vertex `v` meets across all rounds.
+    pub fn partner_count(&self, v: usize) -> usize {
+        self.pairs_met().iter().filter(|(a, b)| *a == v || *b == v).count()
+    }
+
+    /// Whether this is a valid assignment of `n` vertices with the given
+    /// minimum group size (see [`validate_assignment()`]).
+    pub fn is_valid(&self, n: usize, min_group_size: usize) -> bool {
+        validate_assignment(&Vec::<Vec<RawGroup>>::from(self.clone()), n, min_group_size)
+    }
+}
+
+impl Index<usize> for Group {
+    type Output = usize;
+
+    fn index(&self, i: usize) -> &usize {
+        &self.0[i]
+    }
+}
+
+impl Index<usize> for Round {
+    type Output = Group;
+
+    fn index(&self, i: usize) -> &Group {
+        &self.0[i]
+    }
+}
+
+impl Index<usize> for Assignment {
+    type Output = Round;
+
+    fn index(&self, i: usize) -> &Round {
+        &self.0[i]
+    }
+}
+
+impl From<RawGroup> for Group {
+    fn from(members: RawGroup) -> Self {
+        Self(members)
+    }
+}
+
+impl From<Group> for RawGroup {
+    fn from(group: Group) -> Self {
+        group.0
+    }
+}
+
+impl From<Vec<RawGroup>> for Round {
+    fn from(groups: Vec<RawGroup>) -> Self {
+        Self(groups.into_iter().map(Group).collect())
+    }
+}
+
+impl From<Round> for Vec<RawGroup> {
+    fn from(round: Round) -> Self {
+        round.0.into_iter().map(RawGroup::from).collect()
+    }
+}
+
+impl From<Vec<Vec<RawGroup>>> for Assignment {
+    fn from(rounds: Vec<Vec<RawGroup>>) -> Self {
+        Self(rounds.into_iter().map(Round::from).collect())
+    }
+}
+
+impl From<Assignment> for Vec<Vec<RawGroup>> {
+    fn from(assignment: Assignment) -> Self {
+        assignment.0.into_iter().map(Vec::from).collect()
+    }
+}
+
+/// Prints the members in braces, e.g. `{0, 3, 5}`, or `{0,3,5}` with the
+/// alternate flag (`{:#}`).
+impl fmt::Display for Group {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sep = if f.alternate() { "," } else { ", " };
+        write!(f, "{{")?;
+        for (j, v) in self.0.iter().enumerate() {
+            if j > 0 {
+                write!(f, "{sep}")?;
+            }
+            write!(f, "{v}")?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// Prints each group in braces, e.g. `{0, 3, 5} {1, 2, 4}`, or
+/// `{0,3,5} {1,2,4}` with the alternate flag (`{:#}`).
+impl fmt::Display for Round {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, g) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            if f.alternate() {
+                write!(f, "{g:#}")?;
+            } else {
+                write!(f, "{g}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Prints one numbered line per round, e.g. `Round 1: {0, 1} {2, 3}`.
+///
+/// The alternate flag (`{:#}`) gives a compact single line instead, with
+/// rounds separated by `|`, e.g. `{0,1} {2,3} | {0,2} {1,3}`, which suits
+/// logs and test failure messages.
+impl fmt::Display for Assignment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, round) in self.0.iter().enumerate() {
+            if f.alternate() {
+                if i > 0 {
+                    write!(f, " | ")?;
+                }
+                write!(f, "{round:#}")?;
+            } else {
+                if i > 0 {
+                    writeln!(f)?;
+                }
+                write!(f, "Round {}: {round}", i + 1)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Generate all *maximum-round* assignments (as in
+/// [`make_assignments()`](crate::make_assignments)) as typed [`Assignment`]s.
+pub fn make_assignments_typed(conflicts: &mut ConflictMatrix, min_group_size: usize) -> Vec<Assignment> {
+    crate::make_assignments(conflicts, min_group_size)
+        .into_iter()
+        .map(Assignment::from)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::diagonal;
+
+    #[test]
+    fn round_accessors() {
+        let round = Round::from(vec![vec![0, 3], vec![1, 2, 4]]);
+        assert_eq!(round.members().collect::<Vec<_>>(), vec![0, 3, 1, 2, 4]);
+        assert_eq!(round.group_of(2), Some(&Group(vec![1, 2, 4])));
+        assert_eq!(round.group_of(5), None);
+        assert_eq!(round[1][2], 4);
+        assert_eq!(round[0].members(), &[0, 3]);
+        assert_eq!(round.to_string(), "{0, 3} {1, 2, 4}");
+    }
+
+    #[test]
+    fn pairs_met_and_indexing() {
+        let assignment = Assignment::from(vec![
+            vec![vec![0, 1], vec![2, 3]],
+            vec![vec![0, 2], vec![3, 1]],
+        ]);
+        assert_eq!(assignment.num_rounds(), 2);
+        assert_eq!(assignment.pairs_met(), vec![(0, 1), (0, 2), (1, 3), (2, 3)]);
+        assert_eq!(assignment[1][1], Group(vec![3, 1]));
+        assert_eq!(assignment.partner_count(3), 2);
+    }
+
+    #[test]
+    fn typed_assignments() {
+        let res = make_assignments_typed(&mut diagonal(4), 2);
+        assert_eq!(res.len(), crate::make_assignments(&mut diagonal(4), 2).len());
+        for assignment in &res {
+            assert_eq!(assignment.num_rounds(), 3);
+            assert!(assignment.is_valid(4, 2));
+            assert!((0..4).all(|v| assignment.partner_count(v) == 3));
+        }
+        let raw: Vec<Vec<RawGroup>> = res[0].clone().into();
+        assert_eq!(Assignment::from(raw), res[0]);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_matches_raw_form() {
+        let raw = vec![vec![vec![0, 1], vec![2, 3]], vec![vec![0, 2], vec![1, 3]]];
+        let assignment = Assignment::from(raw.clone());
+        let json = serde_json::to_string(&assignment).unwrap();
+        assert_eq!(json, serde_json::to_string(&raw).unwrap());
+        assert_eq!(serde_json::from_str::<Assignment>(&json).unwrap(), assignment);
+    }
+
+    #[test]
+    fn assignment_display() {
+        let assignment = Assignment::from(vec![
+            vec![vec![0, 1], vec![2, 3]],
+            vec![vec![0, 2], vec![1, 3]],
+        ]);
+        assert_eq!(assignment.to_string(), "Round 1: {0, 1} {2, 3}\nRound 2: {0, 2} {1, 3}");
+        assert_eq!(format!("{assignment:#}"), "{0,1} {2,3} | {0,2} {1,3}");
+        assert_eq!(format!("{:#}", assignment[1]), "{0,2} {1,3}");
+    }
+}
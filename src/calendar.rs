@@ -0,0 +1,151 @@
+//! # Calendar Slots
+//!
+//! Planning sessions onto a calendar: rounds are placed in time slots which
+//! not everyone can attend, choosing the slots together with the groups.
+use alloc::vec;
+use alloc::vec::Vec;
+
+use bitvec::prelude::*;
+
+use crate::{commit_round, group_sizes, rollback_round, rounds_excluding, validate_problem, ConflictMatrix, Frame, GroupError, RawGroup};
+
+/// A round along with the index of the time slot it takes place in.
+type SlottedRound = (usize, Vec<RawGroup>);
+
+/// Plan the longest schedules of rounds, each held in its own time slot, such
+/// that every round only groups the vertices available in its slot.
+///
+/// `available[v][s]` is whether vertex `v` can attend slot `s`, and slots
+/// past the end of a row count as unavailable. Rounds keep the order of the
+/// slots, and a slot may go unused, e.g. when too few vertices can attend it
+/// or using it would cost a later, better attended one. Each round partitions
+/// the vertices present into [`group_sizes()`](crate::group_sizes) of their
+/// number, as in
+/// [`make_assignments_with_availability()`](crate::make_assignments_with_availability),
+/// which fixes round `i` to slot `i` instead.
+///
+/// The result holds every schedule with the most rounds, listing each round
+/// with its slot.
+///
+/// ```
+/// # use group_generator::{plan_sessions, ConflictMatrix};
+/// let (t, f) = (true, false);
+/// // Slot 1 is only good for vertices 0 and 1
+/// let available = vec![vec![t, t, t], vec![t, t, t], vec![t, f, t], vec![t, f, t]];
+/// let plans = plan_sessions(&mut ConflictMatrix::empty(4), 2, &available).unwrap();
+/// assert!(plans.iter().all(|plan| plan[1] == (1, vec![vec![0, 1]])));
+/// ```
+///
+/// With a minimum group size of 1, the single round of singletons is held in
+/// the first slot anyone can attend.
+///
+/// # Errors
+///
+/// - [`GroupError::AttributeMismatch`] if `available` does not have a row
+///   for each vertex.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`](crate::try_make_assignments).
+pub fn plan_sessions(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    available: &[Vec<bool>],
+) -> Result<Vec<Vec<SlottedRound>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    let n = conflicts.len();
+    if available.len() != n {
+        return Err(GroupError::AttributeMismatch { len: available.len(), n });
+    }
+    let num_slots = available.iter().map(Vec::len).max().unwrap_or(0);
+    let absent = |slot: usize| -> BitVec { (0..n).map(|v| available[v].get(slot) != Some(&true)).collect() };
+    if min_group_size == 1 {
+        let first = (0..num_slots).find(|s| absent(*s).not_all());
+        let plan = first.map(|s| (s, absent(s).iter_zeros().map(|v| vec![v]).collect()));
+        return Ok(vec![plan.into_iter().collect()]);
+    }
+    // Every round which could be held in a slot from `first` on
+    let options = |conflicts: &mut ConflictMatrix, first: usize| -> Vec<SlottedRound> {
+        let mut options = vec![];
+        for slot in first..num_slots {
+            let skip = absent(slot);
+            let present = skip.count_zeros();
+            if present < min_group_size {
+                continue;
+            }
+            let rounds = rounds_excluding(conflicts, &group_sizes(present, min_group_size), skip, |_, _, _| true);
+            options.extend(rounds.into_iter().map(|round| (slot, round)));
+        }
+        options
+    };
+
+    let mut sols = vec![];
+    let mut best = 0;
+    let mut curr: Vec<SlottedRound> = vec![];
+    let mut stack = vec![Frame::new(options(conflicts, 0))];
+    if stack[0].options.is_empty() {
+        sols.push(vec![]);
+    }
+    while let Some(frame) = stack.last_mut() {
+        let Some((slot, round)) = frame.next_option() else {
+            stack.pop();
+            if let Some((_, round)) = curr.pop() {
+                rollback_round(conflicts, &round);
+            }
+            continue;
+        };
+        commit_round(conflicts, &round);
+        curr.push((slot, round));
+        let next = options(conflicts, slot + 1);
+        if !next.is_empty() {
+            stack.push(Frame::new(next));
+            continue;
+        }
+        if curr.len() >= best {
+            if curr.len() > best {
+                sols.clear();
+            }
+            sols.push(curr.clone());
+            best = curr.len();
+        }
+        if let Some((_, round)) = curr.pop() {
+            rollback_round(conflicts, &round);
+        }
+    }
+    Ok(sols)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::diagonal;
+
+    #[test]
+    fn rounds_follow_slot_availability() {
+        let (t, f) = (true, false);
+        let available = vec![vec![t, t, t], vec![t, t, t], vec![t, f, t], vec![t, f, t]];
+        let plans = plan_sessions(&mut diagonal(4), 2, &available).unwrap();
+        assert!(!plans.is_empty());
+        for plan in &plans {
+            assert!(plan.iter().map(|(slot, _)| *slot).eq([0, 1, 2]));
+            assert_eq!(plan[1].1, vec![vec![0, 1]]);
+        }
+    }
+
+    #[test]
+    fn slots_are_chosen_jointly() {
+        let (t, f) = (true, false);
+        // Vertices 0 and 1 can meet in either slot, but only once
+        let available = vec![vec![t, t], vec![t, t], vec![f], vec![]];
+        let plans = plan_sessions(&mut diagonal(4), 2, &available).unwrap();
+        assert_eq!(plans, vec![vec![(0, vec![vec![0, 1]])], vec![(1, vec![vec![0, 1]])]]);
+        let singles = plan_sessions(&mut diagonal(3), 1, &[vec![f, t], vec![f, t], vec![]]).unwrap();
+        assert_eq!(singles, vec![vec![(1, vec![vec![0], vec![1]])]]);
+    }
+
+    #[test]
+    fn unusable_calendars() {
+        let none = plan_sessions(&mut diagonal(3), 2, &[vec![true], vec![false], vec![]]).unwrap();
+        assert!(none.len() == 1 && none[0].is_empty());
+        let err = plan_sessions(&mut diagonal(3), 2, &[vec![true]]);
+        assert_eq!(err, Err(GroupError::AttributeMismatch { len: 1, n: 3 }));
+    }
+}
@@ -0,0 +1,311 @@
+//! # Checkpointing
+//!
+//! A version of the exhaustive search which runs in bounded slices and can
+//! save its full state to disk in between, so searches taking hours can
+//! survive a restart.
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::Path;
+
+use crate::jsonl::parse_assignment;
+use crate::{
+    can_form_any_round, check_problem, commit_round, group_sizes, rollback_round, single_assignment, singletons,
+    write_assignment, ConflictMatrix, Frame, RawGroup,
+};
+
+/// First line of every checkpoint file, identifying its format version.
+const HEADER: &str = "group_generator checkpoint 1";
+
+/// Exhaustive search for the *maximum-round* assignments (as in
+/// [`make_assignments()`](crate::make_assignments)) which can be paused,
+/// saved and resumed.
+///
+/// The search runs in slices of [`run()`](ResumableSearch::run), between
+/// which it may be written out with [`save()`](ResumableSearch::save) and
+/// picked up later, possibly in another process, with
+/// [`load()`](ResumableSearch::load). The results, and their order, are the
+/// same as those of [`make_assignments()`](crate::make_assignments) however
+/// the search is sliced.
+///
+/// ```
+/// # use group_generator::{make_assignments, ConflictMatrix, ResumableSearch};
+/// let mut search = ResumableSearch::new(ConflictMatrix::empty(5), 2);
+/// while !search.run(10) {
+///     let mut checkpoint = vec![];
+///     search.save(&mut checkpoint).unwrap();
+///     search = ResumableSearch::load(&checkpoint[..]).unwrap();
+/// }
+/// assert_eq!(search.solutions(), make_assignments(&mut ConflictMatrix::empty(5), 2));
+/// ```
+///
+/// # Saved State
+///
+/// A checkpoint holds the original conflicts, the rounds committed on the
+/// current branch (from which the current conflicts are rebuilt), the
+/// untried rounds at every depth of the backtracking stack, the best depth
+/// so far and the solutions collected at that depth. It is plain text, with
+/// rounds and assignments written as in
+/// [`write_assignment()`](crate::write_assignment), and grows with the
+/// solutions collected.
+#[derive(Debug, Clone)]
+pub struct ResumableSearch {
+    conflicts: ConflictMatrix,
+    min_group_size: usize,
+    group_sizes: Vec<usize>,
+    curr: Vec<Vec<RawGroup>>,
+    stack: Vec<Frame<Vec<RawGroup>>>,
+    best: usize,
+    sols: Vec<Vec<Vec<RawGroup>>>,
+}
+
+impl ResumableSearch {
+    /// Set up the search without exploring anything yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as
+    /// [`make_assignments()`](crate::make_assignments).
+    pub fn new(mut conflicts: ConflictMatrix, min_group_size: usize) -> Self {
+        check_problem(&conflicts, min_group_size);
+        let group_sizes = group_sizes(conflicts.len(), min_group_size);
+        let mut stack = vec![];
+        let mut sols = vec![];
+        if min_group_size == 1 {
+            sols.push(vec![singletons(conflicts.len())]);
+        } else if !can_form_any_round(&conflicts, min_group_size) {
+            sols.push(vec![]);
+        } else {
+            stack.push(Frame::new(single_assignment(&mut conflicts, &group_sizes)));
+        }
+        Self {
+            conflicts,
+            min_group_size,
+            group_sizes,
+            curr: vec![],
+            stack,
+            best: 0,
+            sols,
+        }
+    }
+
+    /// Continue the search for at most `steps` more rounds tried, returning
+    /// whether it has finished.
+    pub fn run(&mut self, steps: usize) -> bool {
+        for _ in 0..steps {
+            let Some(frame) = self.stack.last_mut() else {
+                break;
+            };
+            let Some(opt) = frame.next_option() else {
+                self.stack.pop();
+                if let Some(opt) = self.curr.pop() {
+                    rollback_round(&mut self.conflicts, &opt);
+                }
+                continue;
+            };
+            // As in `explore_rounds()`, a round without pairs ends the branch
+            let is_stuck = opt.iter().all(|g| g.len() < 2);
+            commit_round(&mut self.conflicts, &opt);
+            self.curr.push(opt);
+            let options = if is_stuck {
+                vec![]
+            } else {
+                single_assignment(&mut self.conflicts, &self.group_sizes)
+            };
+            if !options.is_empty() {
+                self.stack.push(Frame::new(options));
+                continue;
+            }
+            if self.curr.len() >= self.best {
+                if self.curr.len() > self.best {
+                    self.sols.clear();
+                }
+                self.sols.push(self.curr.clone());
+                self.best = self.curr.len();
+            }
+            if let Some(opt) = self.curr.pop() {
+                rollback_round(&mut self.conflicts, &opt);
+            }
+        }
+        self.is_finished()
+    }
+
+    /// Whether the whole search tree has been explored.
+    pub fn is_finished(&self) -> bool {
+        self.stack.is_empty()
+    }
+
+    /// The assignments reaching the deepest level found so far. Once the
+    /// search has finished, these are exactly the maximal assignments.
+    pub fn solutions(&self) -> &[Vec<Vec<RawGroup>>] {
+        &self.sols
+    }
+
+    /// Take the solutions collected so far (see
+    /// [`solutions()`](ResumableSearch::solutions)).
+    pub fn into_solutions(self) -> Vec<Vec<Vec<RawGroup>>> {
+        self.sols
+    }
+
+    /// Write the full search state to `writer`.
+    pub fn save(&self, writer: &mut impl Write) -> io::Result<()> {
+        let mut original = self.conflicts.clone();
+        for round in self.curr.iter().rev() {
+            rollback_round(&mut original, round);
+        }
+        let pairs: Vec<RawGroup> = original.pairs().map(|(i, j)| vec![i, j]).collect();
+        writeln!(writer, "{HEADER}")?;
+        writeln!(
+            writer,
+            "{} {} {} {} {}",
+            original.len(),
+            self.min_group_size,
+            self.best,
+            self.stack.len(),
+            self.sols.len()
+        )?;
+        write_assignment(writer, &[pairs])?;
+        write_assignment(writer, &self.curr)?;
+        for frame in &self.stack {
+            write_assignment(writer, frame.options.get(frame.next..).unwrap_or_default())?;
+        }
+        for assignment in &self.sols {
+            write_assignment(writer, assignment)?;
+        }
+        Ok(())
+    }
+
+    /// Read back a search state written by [`save()`](ResumableSearch::save).
+    ///
+    /// # Errors
+    ///
+    /// Returns any error from reading, or an [`io::ErrorKind::InvalidData`]
+    /// error if the input is not a checkpoint in this format.
+    pub fn load(reader: impl BufRead) -> io::Result<Self> {
+        let invalid = |msg: String| io::Error::new(io::ErrorKind::InvalidData, msg);
+        let mut lines = reader.lines();
+        let mut next_line = || lines.next().unwrap_or_else(|| Err(invalid("Checkpoint ended early.".to_string())));
+        if next_line()? != HEADER {
+            return Err(invalid("Not a checkpoint file.".to_string()));
+        }
+        let counts: Vec<usize> = next_line()?
+            .split_whitespace()
+            .map(|x| x.parse().map_err(|_| invalid(format!("Invalid count '{x}'."))))
+            .collect::<io::Result<_>>()?;
+        let &[n, min_group_size, best, nframes, nsols] = &counts[..] else {
+            return Err(invalid("Expected five counts.".to_string()));
+        };
+        let mut next_assignment = || parse_assignment(&next_line()?).map_err(invalid);
+
+        let pairs = next_assignment()?.into_iter().flatten().map(|g| match g[..] {
+            [i, j] => Ok((i, j)),
+            _ => Err(invalid("Conflicts must be pairs.".to_string())),
+        });
+        let pairs: Vec<(usize, usize)> = pairs.collect::<io::Result<_>>()?;
+        let mut conflicts = ConflictMatrix::from_edges(n, pairs).map_err(|err| invalid(err.to_string()))?;
+        // Every saved round is committed or compared against `n` vertices
+        // later, so reject any vertex out of range up front
+        let mut next_in_range = || {
+            let rounds = next_assignment()?;
+            if rounds.iter().flatten().flatten().any(|v| *v >= n) {
+                return Err(invalid("Saved round is out of range.".to_string()));
+            }
+            Ok(rounds)
+        };
+        let curr = next_in_range()?;
+        for round in &curr {
+            commit_round(&mut conflicts, round);
+        }
+        let stack = (0..nframes)
+            .map(|_| next_in_range().map(Frame::new))
+            .collect::<io::Result<_>>()?;
+        let sols = (0..nsols).map(|_| next_in_range()).collect::<io::Result<_>>()?;
+        if min_group_size == 0 || min_group_size > n {
+            return Err(invalid("Invalid minimum group size.".to_string()));
+        }
+        Ok(Self {
+            conflicts,
+            min_group_size,
+            group_sizes: group_sizes(n, min_group_size),
+            curr,
+            stack,
+            best,
+            sols,
+        })
+    }
+
+    /// Write the search state to the file at `path` (created or truncated).
+    ///
+    /// The state is written to a temporary file next to `path` first and
+    /// then renamed over it, so a crash mid-write leaves the previous
+    /// checkpoint intact.
+    pub fn save_to_file(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        let path = path.as_ref();
+        let tmp = path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&tmp)?);
+        self.save(&mut writer)?;
+        writer.flush()?;
+        drop(writer);
+        std::fs::rename(tmp, path)
+    }
+
+    /// Read a search state saved with
+    /// [`save_to_file()`](ResumableSearch::save_to_file).
+    pub fn load_from_file(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::load(BufReader::new(File::open(path)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::make_assignments;
+    use crate::tests::diagonal;
+
+    #[test]
+    fn sliced_search_matches_make_assignments() {
+        let mut conflicted = diagonal(6);
+        conflicted.add_conflict(0, 1);
+        for (conflicts, k) in [(diagonal(5), 2), (diagonal(6), 3), (conflicted, 3), (diagonal(3), 1)] {
+            let expected = make_assignments(&mut conflicts.clone(), k);
+            let mut search = ResumableSearch::new(conflicts.clone(), k);
+            while !search.run(3) {
+                let mut buf = vec![];
+                search.save(&mut buf).unwrap();
+                search = ResumableSearch::load(&buf[..]).unwrap();
+            }
+            assert_eq!(search.into_solutions(), expected);
+        }
+    }
+
+    #[test]
+    fn resumes_from_file() {
+        let path = std::env::temp_dir().join(format!("group_generator_checkpoint_{}.txt", std::process::id()));
+        let mut search = ResumableSearch::new(diagonal(4), 2);
+        search.run(2);
+        search.save_to_file(&path).unwrap();
+        let mut resumed = ResumableSearch::load_from_file(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(resumed.conflicts, search.conflicts);
+        assert!(resumed.run(usize::MAX));
+        assert_eq!(resumed.solutions(), make_assignments(&mut diagonal(4), 2));
+    }
+
+    #[test]
+    fn rejects_malformed_checkpoints() {
+        for text in ["", "not a checkpoint\n", "group_generator checkpoint 1\n4 2 0\n", "group_generator checkpoint 1\n4 2 0 0 0\n[[[0,1,2]]]\n[]\n"] {
+            let err = ResumableSearch::load(text.as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "Accepted {text:?}");
+        }
+    }
+
+    #[test]
+    fn rejects_out_of_range_rounds() {
+        // A corrupt stack frame and a corrupt solution, each naming vertex 9 of 4
+        for (counts, rounds) in [("4 2 0 1 0", "[[[0,9],[1,2]]]"), ("4 2 1 0 1", "[[[0,1],[2,9]]]")] {
+            let text = format!("{HEADER}\n{counts}\n[]\n[]\n{rounds}\n");
+            let err = ResumableSearch::load(text.as_bytes()).unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+            assert!(err.to_string().contains("out of range"), "{err}");
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! # Cohort Partitioning
+//!
+//! When a single exhaustive solve is intractable, the vertices can be split
+//! into smaller cohorts which are scheduled independently. This trades
+//! optimality for tractability: no mixing *between* cohorts is scheduled.
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{make_assignments, ConflictMatrix, RawGroup};
+
+/// One independently scheduled cohort.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cohort {
+    /// The original vertex indices belonging to this cohort, ascending.
+    pub vertices: Vec<usize>,
+    /// All maximal assignments for the cohort, using original vertex indices.
+    pub assignments: Vec<Vec<Vec<RawGroup>>>,
+}
+
+/// Split the vertices into cohorts of at most `max_cohort_size`, solve each
+/// with [`make_assignments()`], and return the per-cohort schedules.
+///
+/// # Partitioning Heuristic
+///
+/// The number of cohorts is the fewest that respects `max_cohort_size`, and
+/// their capacities are balanced to within one vertex. Vertices are then
+/// placed greedily in order of decreasing conflict degree, each into the
+/// cohort (with room left) where it has the fewest existing conflicts,
+/// preferring emptier cohorts on ties. Since conflicting vertices could never
+/// be grouped anyway, spreading them across cohorts leaves each cohort with
+/// as many schedulable pairs as possible.
+///
+/// Vertices in different cohorts are never grouped together; interleaving the
+/// cohort schedules is left to the caller.
+///
+/// # Panics
+///
+/// Panics if the balanced cohorts would be smaller than `min_group_size`, or
+/// under the same conditions as [`make_assignments()`].
+pub fn partition_and_solve(conflicts: &ConflictMatrix, min_group_size: usize, max_cohort_size: usize) -> Vec<Cohort> {
+    let n = conflicts.len();
+    assert!(max_cohort_size > 0, "Cohorts must hold at least one vertex.");
+    let ncohorts = n.div_ceil(max_cohort_size);
+    assert!(
+        n / ncohorts >= min_group_size,
+        "Cohorts of at most {max_cohort_size} vertices cannot hold groups of {min_group_size}."
+    );
+    let capacity = |c: usize| n / ncohorts + usize::from(c < n % ncohorts);
+
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|v| core::cmp::Reverse(conflicts.degree(*v)));
+
+    let mut members: Vec<Vec<usize>> = vec![vec![]; ncohorts];
+    for v in order {
+        let target = (0..ncohorts)
+            .filter(|c| members[*c].len() < capacity(*c))
+            .min_by_key(|c| {
+                let clashes = members[*c].iter().filter(|u| conflicts.is_conflicted(v, **u)).count();
+                (clashes, members[*c].len())
+            })
+            .expect("Cohort capacities sum to the number of vertices");
+        members[target].push(v);
+    }
+
+    members
+        .into_iter()
+        .map(|mut vertices| {
+            vertices.sort_unstable();
+            let local = |v: usize| vertices.binary_search(&v).ok();
+            let pairs = conflicts.pairs().filter_map(|(i, j)| Some((local(i)?, local(j)?)));
+            let mut sub = ConflictMatrix::empty(vertices.len()).with_pairs(pairs);
+            let assignments = make_assignments(&mut sub, min_group_size)
+                .into_iter()
+                .map(|assignment| {
+                    assignment
+                        .into_iter()
+                        .map(|round| {
+                            round
+                                .into_iter()
+                                .map(|g| g.into_iter().map(|v| vertices[v]).collect())
+                                .collect()
+                        })
+                        .collect()
+                })
+                .collect();
+            Cohort { vertices, assignments }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{conflicts_from_groups, validate_assignment};
+
+    #[test]
+    fn conflicting_pairs_split_across_cohorts() {
+        let conflicts = conflicts_from_groups(8, &[0, 0, 1, 1, 2, 2, 3, 3]);
+        let cohorts = partition_and_solve(&conflicts, 2, 4);
+        assert_eq!(cohorts.len(), 2);
+        assert_eq!(cohorts[0].vertices, vec![0, 2, 4, 6]);
+        assert_eq!(cohorts[1].vertices, vec![1, 3, 5, 7]);
+        for cohort in &cohorts {
+            for assignment in &cohort.assignments {
+                assert_eq!(assignment.len(), 3);
+                for round in assignment {
+                    for g in round {
+                        assert!(g.iter().all(|v| cohort.vertices.contains(v)));
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn balanced_cohort_sizes() {
+        let cohorts = partition_and_solve(&crate::tests::diagonal(7), 2, 3);
+        let sizes: Vec<usize> = cohorts.iter().map(|c| c.vertices.len()).collect();
+        assert_eq!(sizes, vec![3, 2, 2]);
+        for cohort in &cohorts {
+            let local: Vec<Vec<RawGroup>> = cohort.assignments[0]
+                .iter()
+                .map(|round| {
+                    round
+                        .iter()
+                        .map(|g| g.iter().map(|v| cohort.vertices.iter().position(|u| u == v).unwrap()).collect())
+                        .collect()
+                })
+                .collect();
+            assert!(validate_assignment(&local, cohort.vertices.len(), 2));
+        }
+    }
+}
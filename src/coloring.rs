@@ -0,0 +1,125 @@
+//! # Conflict Graph Coloring
+//!
+//! Bounds on the chromatic number of the conflict graph, whose edges are the
+//! off-diagonal conflicts.
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::ConflictMatrix;
+
+/// Upper bound on the chromatic number of the conflict graph: the number of
+/// colors used by a greedy coloring in order of decreasing degree.
+///
+/// Together with [`conflict_clique_bound()`] this brackets the chromatic
+/// number, which is known exactly when the two agree.
+///
+/// # Interpretation
+///
+/// Every group must be conflict-free, so every round is a proper coloring of
+/// the conflict graph with one color per group. A round therefore needs at
+/// least as many groups as the chromatic number:
+///
+/// - If the number of groups planned per round (see
+///   [`group_sizes()`](crate::group_sizes)) is at least this bound, the
+///   conflicts alone never rule out a round: the greedy coloring is one.
+/// - If it is below [`conflict_clique_bound()`], no round can be formed at
+///   all.
+///
+/// An empty matrix has chromatic number 0.
+pub fn conflict_chromatic_bound(conflicts: &ConflictMatrix) -> usize {
+    greedy_colors(conflicts)
+}
+
+/// Lower bound on the chromatic number of the conflict graph: the size of a
+/// maximum clique, found exactly by branch-and-bound.
+///
+/// Mutually conflicting vertices (a clique) must be split across different
+/// groups in every round, so a round needs at least this many groups (see
+/// [`conflict_chromatic_bound()`]).
+pub fn conflict_clique_bound(conflicts: &ConflictMatrix) -> usize {
+    max_clique(conflicts)
+}
+
+fn adjacent(conflicts: &ConflictMatrix, u: usize, v: usize) -> bool {
+    u != v && conflicts.is_conflicted(u, v)
+}
+
+/// Size of a maximum clique, extending cliques with higher-indexed vertices
+/// only and abandoning any which cannot beat the best found.
+fn max_clique(conflicts: &ConflictMatrix) -> usize {
+    let n = conflicts.len();
+    let mut best = 0;
+    let mut curr: Vec<usize> = vec![];
+    // `next[i]` is the next vertex to try as member `i` of the clique
+    let mut next = vec![0];
+    while let Some(cursor) = next.last_mut() {
+        let v = *cursor;
+        *cursor += 1;
+        if v >= n || curr.len() + (n - v) <= best {
+            next.pop();
+            curr.pop();
+            continue;
+        }
+        if curr.iter().all(|u| adjacent(conflicts, *u, v)) {
+            curr.push(v);
+            best = best.max(curr.len());
+            next.push(v + 1);
+        }
+    }
+    best
+}
+
+/// Number of colors used by greedily coloring vertices in order of decreasing
+/// degree, each with the smallest color unused by its colored neighbors.
+fn greedy_colors(conflicts: &ConflictMatrix) -> usize {
+    let n = conflicts.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|v| core::cmp::Reverse(conflicts.degree(*v)));
+
+    let mut color: Vec<Option<usize>> = vec![None; n];
+    let mut ncolors = 0;
+    for v in order {
+        let used: Vec<usize> = (0..n)
+            .filter(|u| adjacent(conflicts, v, *u))
+            .filter_map(|u| color[u])
+            .collect();
+        let c = (0..).find(|c| !used.contains(c)).unwrap_or_default();
+        color[v] = Some(c);
+        ncolors = ncolors.max(c + 1);
+    }
+    ncolors
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::diagonal;
+    use crate::{conflicts_from_groups, make_assignments};
+
+    #[test]
+    fn disjoint_cliques_are_exact() {
+        let conflicts = conflicts_from_groups(6, &[0, 0, 0, 1, 1, 2]);
+        assert_eq!((conflict_clique_bound(&conflicts), conflict_chromatic_bound(&conflicts)), (3, 3));
+        assert_eq!((conflict_clique_bound(&diagonal(4)), conflict_chromatic_bound(&diagonal(4))), (1, 1));
+        assert_eq!((conflict_clique_bound(&diagonal(0)), conflict_chromatic_bound(&diagonal(0))), (0, 0));
+    }
+
+    #[test]
+    fn odd_cycle_brackets_chromatic_number() {
+        let n = 5;
+        let mut conflicts = diagonal(n);
+        for v in 0..n {
+            conflicts.add_conflict(v, (v + 1) % n);
+        }
+        assert_eq!(conflict_clique_bound(&conflicts), 2);
+        assert!(conflict_chromatic_bound(&conflicts) >= 3);
+    }
+
+    #[test]
+    fn clique_larger_than_round_blocks_scheduling() {
+        // Three mutually conflicting vertices cannot fit in two groups
+        let mut conflicts = conflicts_from_groups(4, &[0, 0, 0, 1]);
+        assert_eq!(conflict_clique_bound(&conflicts), 3);
+        assert_eq!(make_assignments(&mut conflicts, 2), vec![Vec::<Vec<Vec<usize>>>::new()]);
+    }
+}
@@ -0,0 +1,108 @@
+//! # Compact Search
+//!
+//! Running the search with narrower vertex indices (`u8`, `u16`, `u32`) than
+//! `usize`, so groups, candidate rounds and collected solutions all take less
+//! memory and more of them fit in cache.
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
+use bitvec::prelude::*;
+
+use crate::{
+    can_form_any_round, check_problem, commit_round, explore_rounds, group_sizes, rollback_round, rounds_excluding_where,
+    ConflictMatrix, RawGroup, VertexIndex,
+};
+
+/// Generate all *maximum-round* assignments (as in
+/// [`make_assignments()`](crate::make_assignments)), in the same order, with
+/// vertices stored as `V` instead of `usize`.
+///
+/// The search holds every vertex as `V` throughout: the candidate groups, the
+/// rounds built from them, the stack of options at each depth and the
+/// collected solutions all take `size_of::<V>()` bytes per vertex placement.
+/// With `u16` that is a quarter of the plain solver's footprint on 64-bit
+/// targets. Only the [`ConflictMatrix`] is unchanged, being a bit matrix
+/// already.
+///
+/// ```
+/// # use group_generator::{make_assignments, make_assignments_compact, ConflictMatrix};
+/// let compact = make_assignments_compact::<u8>(&mut ConflictMatrix::empty(4), 2);
+/// assert_eq!(compact.len(), make_assignments(&mut ConflictMatrix::empty(4), 2).len());
+/// assert_eq!(compact[0][0], vec![vec![0u8, 1], vec![2, 3]]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if some vertex index does not fit in `V`, or under the same
+/// conditions as [`make_assignments()`](crate::make_assignments).
+pub fn make_assignments_compact<V: VertexIndex>(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+) -> Vec<Vec<Vec<RawGroup<V>>>> {
+    check_problem(conflicts, min_group_size);
+    let n = conflicts.len();
+    assert!(n - 1 <= V::MAX_INDEX, "Vertex indices up to {} do not fit the index type.", n - 1);
+
+    if min_group_size == 1 {
+        return vec![vec![(0..n).map(|v| vec![V::from_index(v)]).collect()]];
+    }
+    if !can_form_any_round(conflicts, min_group_size) {
+        return vec![vec![]];
+    }
+    let group_sizes = group_sizes(n, min_group_size);
+    let mut best = 0;
+    let mut sols = vec![];
+    explore_rounds(
+        conflicts,
+        |conflicts, _| rounds_excluding_where(conflicts, &group_sizes, bitvec![0; n], |_, _: &[V], _| true, |_, _| true),
+        commit_round,
+        rollback_round,
+        |curr| {
+            if curr.len() >= best {
+                if curr.len() > best {
+                    sols.clear();
+                }
+                sols.push(curr.to_vec());
+                best = curr.len();
+            }
+            ControlFlow::Continue(())
+        },
+    );
+    sols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::make_assignments;
+    use crate::tests::diagonal;
+
+    fn widen<V: VertexIndex>(sols: &[Vec<Vec<RawGroup<V>>>]) -> Vec<Vec<Vec<RawGroup>>> {
+        sols.iter()
+            .map(|a| a.iter().map(|r| r.iter().map(|g| g.iter().map(|v| v.index()).collect()).collect()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn compact_matches_usize() {
+        let mut conflicted = diagonal(5);
+        conflicted.add_conflict(0, 3);
+        let mut hyper = diagonal(6);
+        hyper.add_hyperedge(&[0, 1, 2]);
+        for (conflicts, k) in [(diagonal(5), 2), (diagonal(6), 3), (conflicted, 2), (hyper, 3), (diagonal(3), 1), (diagonal(4), 3)] {
+            let wide = make_assignments(&mut conflicts.clone(), k);
+            let mut search_conflicts = conflicts.clone();
+            assert_eq!(widen(&make_assignments_compact::<u16>(&mut search_conflicts, k)), wide);
+            assert_eq!(search_conflicts, conflicts);
+            assert_eq!(widen(&make_assignments_compact::<u8>(&mut conflicts.clone(), k)), wide);
+            assert_eq!(widen(&make_assignments_compact::<u32>(&mut conflicts.clone(), k)), wide);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "do not fit")]
+    fn oversized_problem_panics() {
+        make_assignments_compact::<u8>(&mut diagonal(257), 2);
+    }
+}
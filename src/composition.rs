@@ -0,0 +1,545 @@
+//! # Group Composition
+//!
+//! Rules on *who* makes up each group, beyond pairwise conflicts, such as
+//! requiring a leader in every group or balancing departments. They are
+//! checked while groups are built, so partial groups which can no longer
+//! satisfy a rule are abandoned early rather than filtered out of the
+//! results.
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
+use bitvec::prelude::*;
+
+use crate::{check_vertices, group_sizes, validate_problem, visit_maximal_filtered, ConflictMatrix, GroupError, RawGroup, Slot};
+
+/// Requires every group to contain at least
+/// [`min_members`](RoleQuota::min_members) vertices tagged with
+/// [`role`](RoleQuota::role).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RoleQuota {
+    pub role: String,
+    pub min_members: usize,
+}
+
+/// Requires every group to contain exactly
+/// [`per_group`](SubsetQuota::per_group) of the vertices in
+/// [`members`](SubsetQuota::members).
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SubsetQuota {
+    pub members: Vec<usize>,
+    pub per_group: usize,
+}
+
+/// Generate the *maximum-round* assignments (as in
+/// [`make_assignments()`](crate::make_assignments)) in which every group
+/// meets every quota, given the role tags of each vertex.
+///
+/// Vertex `v` carries the tags in `tags[v]`, or none if `tags` is shorter. A
+/// partial group is abandoned as soon as its free places could no longer
+/// make up a quota.
+///
+/// ```
+/// # use group_generator::{make_assignments_with_roles, ConflictMatrix, RoleQuota};
+/// let tags = vec![vec!["leader".to_string()], vec![], vec![], vec!["leader".to_string()]];
+/// let quota = RoleQuota { role: "leader".to_string(), min_members: 1 };
+/// let sols = make_assignments_with_roles(&mut ConflictMatrix::empty(4), 2, &tags, &[quota]).unwrap();
+/// assert!(sols.iter().flatten().flatten().all(|g| g.contains(&0) != g.contains(&3)));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::VertexOutOfRange`] if `tags` has more entries than there
+///   are vertices.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`](crate::try_make_assignments).
+pub fn make_assignments_with_roles(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    tags: &[Vec<String>],
+    quotas: &[RoleQuota],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    check_vertices(conflicts.len(), tags.len().checked_sub(1))?;
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut sols = vec![];
+    let allows = |slot, partial: &[usize], candidate| quotas_allow(tags, quotas, slot, partial, candidate);
+    visit_maximal_filtered::<()>(conflicts, &[group_sizes], allows, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    });
+    Ok(sols)
+}
+
+/// Generate the *maximum-round* assignments (as in
+/// [`make_assignments()`](crate::make_assignments)) in which every group holds
+/// exactly the required number of members of each subset, e.g. exactly one
+/// native speaker per language-exchange group.
+///
+/// Since every vertex is placed in every round, a subset can only be shared
+/// out if it has exactly `per_group` members for each group, and the
+/// assignment is empty otherwise. Within a round, a partial group is
+/// abandoned as soon as too few members of a subset are left to fill its
+/// quota.
+///
+/// ```
+/// # use group_generator::{make_assignments_with_subset_quotas, ConflictMatrix, SubsetQuota};
+/// let speakers = SubsetQuota { members: vec![0, 5], per_group: 1 };
+/// let sols = make_assignments_with_subset_quotas(&mut ConflictMatrix::empty(6), 3, &[speakers]).unwrap();
+/// assert!(sols.iter().flatten().flatten().all(|g| g.contains(&0) != g.contains(&5)));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::VertexOutOfRange`] if a subset refers to a vertex which
+///   does not exist.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`](crate::try_make_assignments).
+pub fn make_assignments_with_subset_quotas(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    quotas: &[SubsetQuota],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    let quotas = SubsetQuotas::new(conflicts.len(), quotas)?;
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    if !quotas.fit(&group_sizes) {
+        return Ok(vec![vec![]]);
+    }
+    let mut sols = vec![];
+    let allows = |slot, partial: &[usize], candidate| quotas.allows(slot, partial, candidate);
+    visit_maximal_filtered::<()>(conflicts, &[group_sizes], allows, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    });
+    Ok(sols)
+}
+
+/// The [`SubsetQuota`]s every group must meet, prepared for checking
+/// partial groups (see [`make_assignments_with_subset_quotas()`]).
+#[derive(Debug, Clone)]
+pub(crate) struct SubsetQuotas {
+    /// For each quota, its number per group, which vertices belong to the
+    /// subset, and how many members come after each vertex, at its index
+    sets: Vec<(usize, BitVec, Vec<usize>)>,
+}
+
+impl SubsetQuotas {
+    pub(crate) fn new(n: usize, quotas: &[SubsetQuota]) -> Result<Self, GroupError> {
+        check_vertices(n, quotas.iter().flat_map(|quota| quota.members.iter().copied()))?;
+        let sets = quotas
+            .iter()
+            .map(|quota| {
+                let mut members = bitvec![0; n];
+                for v in &quota.members {
+                    members.set(*v, true);
+                }
+                let mut after = vec![0; n];
+                for v in (0..n.saturating_sub(1)).rev() {
+                    after[v] = after[v + 1] + usize::from(members[v + 1]);
+                }
+                (quota.per_group, members, after)
+            })
+            .collect();
+        Ok(Self { sets })
+    }
+
+    /// Whether every subset has exactly its quota for each of the groups.
+    pub(crate) fn fit(&self, group_sizes: &[usize]) -> bool {
+        let smallest = group_sizes.iter().copied().min().unwrap_or(0);
+        self.sets
+            .iter()
+            .all(|(per_group, members, _)| members.count_ones() == per_group * group_sizes.len() && *per_group <= smallest)
+    }
+
+    /// Whether a group of `slot.size` holding `partial` and `candidate` can
+    /// still meet every quota exactly. Members are added in ascending order,
+    /// so the rest come from the vertices after `candidate`.
+    pub(crate) fn allows(&self, slot: Slot, partial: &[usize], candidate: usize) -> bool {
+        let free = slot.size - partial.len() - 1;
+        self.sets.iter().all(|(per_group, members, after)| {
+            let count = partial.iter().filter(|v| members[**v]).count() + usize::from(members[candidate]);
+            count <= *per_group && count + free.min(after[candidate]) >= *per_group
+        })
+    }
+}
+
+/// Generate the *maximum-round* assignments (as in
+/// [`make_assignments()`](crate::make_assignments)) in which no category makes
+/// up more than `max_percent` percent of any group.
+///
+/// Vertex `v` belongs to category `categories[v]` (e.g. a department), or to
+/// none if `categories` is shorter; uncategorized vertices never count
+/// towards a limit. A group of `s` members holds at most
+/// `s * max_percent / 100` (rounded down) from any one category, so at 60% no
+/// two members of a group of three may share a category.
+///
+/// ```
+/// # use group_generator::{make_assignments_balanced, ConflictMatrix};
+/// let sols = make_assignments_balanced(&mut ConflictMatrix::empty(6), 3, &[0, 0, 0, 1, 1, 1], 70).unwrap();
+/// assert!(sols.iter().flatten().flatten().all(|g| g != &[0, 1, 2] && g != &[3, 4, 5]));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::VertexOutOfRange`] if `categories` has more entries than
+///   there are vertices.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`](crate::try_make_assignments).
+pub fn make_assignments_balanced(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    categories: &[usize],
+    max_percent: usize,
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    check_vertices(conflicts.len(), categories.len().checked_sub(1))?;
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut sols = vec![];
+    let allows = |slot, partial: &[usize], candidate| balance_allows(categories, max_percent, slot, partial, candidate);
+    visit_maximal_filtered::<()>(conflicts, &[group_sizes], allows, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    });
+    Ok(sols)
+}
+
+/// Generate the *maximum-round* assignments (as in
+/// [`make_assignments()`](crate::make_assignments)) in which no vertex is
+/// placed in a group larger than its personal maximum.
+///
+/// Vertex `v` only joins groups of at most `max_sizes[v]` members (e.g. 2
+/// for someone who only works in pairs), or of any size if `max_sizes` is
+/// shorter. Group sizes follow the usual round shape, so a vertex whose
+/// maximum is below the smallest group there can never be placed.
+///
+/// ```
+/// # use group_generator::{make_assignments_with_size_limits, ConflictMatrix};
+/// let sols = make_assignments_with_size_limits(&mut ConflictMatrix::empty(5), 2, &[2]).unwrap();
+/// assert!(sols.iter().flatten().flatten().all(|g| !g.contains(&0) || g.len() == 2));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::VertexOutOfRange`] if `max_sizes` has more entries than
+///   there are vertices.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`](crate::try_make_assignments).
+pub fn make_assignments_with_size_limits(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    max_sizes: &[usize],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    check_vertices(conflicts.len(), max_sizes.len().checked_sub(1))?;
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut sols = vec![];
+    let allows = |slot, _: &[usize], candidate| size_limit_allows(max_sizes, slot, candidate);
+    visit_maximal_filtered::<()>(conflicts, &[group_sizes], allows, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    });
+    Ok(sols)
+}
+
+/// Whether a group of `slot.size` is within `candidate`'s personal maximum.
+pub(crate) fn size_limit_allows(max_sizes: &[usize], slot: Slot, candidate: usize) -> bool {
+    max_sizes.get(candidate).is_none_or(|max| slot.size <= *max)
+}
+
+/// Whether `candidate` can join `partial` without its category exceeding
+/// `max_percent` of a group of `slot.size`.
+pub(crate) fn balance_allows(categories: &[usize], max_percent: usize, slot: Slot, partial: &[usize], candidate: usize) -> bool {
+    let Some(category) = categories.get(candidate) else {
+        return true;
+    };
+    let same = partial.iter().filter(|v| categories.get(**v) == Some(category)).count() + 1;
+    same * 100 <= slot.size * max_percent
+}
+
+/// Generate the *maximum-round* assignments (as in
+/// [`make_assignments()`](crate::make_assignments)) in which every group has
+/// members from at least `min_categories` distinct categories, as for
+/// cross-functional teams.
+///
+/// Categories are given as for [`make_assignments_balanced()`], and
+/// uncategorized vertices add no category. A partial group is abandoned as
+/// soon as its free places could no longer bring in enough new categories.
+///
+/// ```
+/// # use group_generator::{make_assignments_diverse, ConflictMatrix};
+/// let sols = make_assignments_diverse(&mut ConflictMatrix::empty(6), 3, &[0, 0, 1, 1, 2, 2], 3).unwrap();
+/// assert!(sols.iter().flatten().flatten().all(|g| g.iter().map(|v| v / 2).sum::<usize>() == 3));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::VertexOutOfRange`] if `categories` has more entries than
+///   there are vertices.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`](crate::try_make_assignments).
+pub fn make_assignments_diverse(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    categories: &[usize],
+    min_categories: usize,
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    check_vertices(conflicts.len(), categories.len().checked_sub(1))?;
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut sols = vec![];
+    let allows = |slot, partial: &[usize], candidate| diversity_allows(categories, min_categories, slot, partial, candidate);
+    visit_maximal_filtered::<()>(conflicts, &[group_sizes], allows, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    });
+    Ok(sols)
+}
+
+/// Whether a group of `slot.size` holding `partial` and `candidate` can still
+/// reach `min_categories` distinct categories once its free places are
+/// filled.
+pub(crate) fn diversity_allows(categories: &[usize], min_categories: usize, slot: Slot, partial: &[usize], candidate: usize) -> bool {
+    let mut seen: Vec<usize> = partial.iter().chain([&candidate]).filter_map(|v| categories.get(*v).copied()).collect();
+    seen.sort_unstable();
+    seen.dedup();
+    seen.len() + (slot.size - partial.len() - 1) >= min_categories
+}
+
+/// Generate the *maximum-round* assignments (as in
+/// [`make_assignments()`](crate::make_assignments)) in which the mean score of
+/// every group is within `tolerance` of the mean score of all vertices.
+///
+/// Vertex `v` has score `scores[v]` (e.g. a skill rating). Scores are whole
+/// numbers, so for a finer tolerance scale them up first (e.g. by 10 for a
+/// tolerance of 0.5 in the original units). A partial group is abandoned as
+/// soon as no choice of its remaining members could bring its mean into the
+/// band.
+///
+/// ```
+/// # use group_generator::{make_assignments_mean_balanced, ConflictMatrix};
+/// let scores = [1, 2, 3, 4, 5, 6];
+/// let sols = make_assignments_mean_balanced(&mut ConflictMatrix::empty(6), 3, &scores, 1).unwrap();
+/// for g in sols.iter().flatten().flatten() {
+///     let sum: i64 = g.iter().map(|v| scores[*v]).sum();
+///     assert!((8..=13).contains(&sum));
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::AttributeMismatch`] if there is not exactly one score per
+///   vertex.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`](crate::try_make_assignments).
+pub fn make_assignments_mean_balanced(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    scores: &[i64],
+    tolerance: u64,
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    let band = MeanBand::new(conflicts.len(), scores, tolerance)?;
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut sols = vec![];
+    let allows = |slot, partial: &[usize], candidate| band.allows(slot, partial, candidate);
+    visit_maximal_filtered::<()>(conflicts, &[group_sizes], allows, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    });
+    Ok(sols)
+}
+
+/// The band around the mean score which every group mean must fall in (see
+/// [`make_assignments_mean_balanced()`]).
+#[derive(Debug, Clone)]
+pub(crate) struct MeanBand<'a> {
+    scores: &'a [i64],
+    total: i128,
+    tolerance: i128,
+    // Least and greatest score among vertices `v..`, at index `v`
+    suffix_min: Vec<i64>,
+    suffix_max: Vec<i64>,
+}
+
+impl<'a> MeanBand<'a> {
+    pub(crate) fn new(n: usize, scores: &'a [i64], tolerance: u64) -> Result<Self, GroupError> {
+        if scores.len() != n {
+            return Err(GroupError::AttributeMismatch { len: scores.len(), n });
+        }
+        let mut suffix_min = vec![i64::MAX; n + 1];
+        let mut suffix_max = vec![i64::MIN; n + 1];
+        for v in (0..n).rev() {
+            suffix_min[v] = suffix_min[v + 1].min(scores[v]);
+            suffix_max[v] = suffix_max[v + 1].max(scores[v]);
+        }
+        Ok(Self {
+            scores,
+            total: scores.iter().map(|x| i128::from(*x)).sum(),
+            tolerance: i128::from(tolerance),
+            suffix_min,
+            suffix_max,
+        })
+    }
+
+    /// Whether a group of `slot.size` holding `partial` and `candidate` can
+    /// still end up with its mean in the band. Members are added in ascending
+    /// order, so the rest come from the vertices after `candidate`.
+    pub(crate) fn allows(&self, slot: Slot, partial: &[usize], candidate: usize) -> bool {
+        let n = self.scores.len() as i128;
+        let size = slot.size as i128;
+        let free = (slot.size - partial.len() - 1) as i128;
+        let sum: i128 = partial.iter().chain([&candidate]).map(|v| i128::from(self.scores[*v])).sum();
+        let (lowest, highest) = match free {
+            0 => (sum, sum),
+            _ if candidate + 1 >= self.scores.len() => return false,
+            _ => (
+                sum + free * i128::from(self.suffix_min[candidate + 1]),
+                sum + free * i128::from(self.suffix_max[candidate + 1]),
+            ),
+        };
+        // |sum / size - total / n| <= tolerance, scaled by `size * n`
+        let (target, slack) = (self.total * size, self.tolerance * size * n);
+        highest * n >= target - slack && lowest * n <= target + slack
+    }
+}
+
+/// Whether a group of `slot.size` holding `partial` and `candidate` can still
+/// meet every quota once its free places are filled.
+pub(crate) fn quotas_allow(tags: &[Vec<String>], quotas: &[RoleQuota], slot: Slot, partial: &[usize], candidate: usize) -> bool {
+    let free = slot.size - partial.len() - 1;
+    quotas.iter().all(|quota| {
+        let tagged = |v: &usize| tags.get(*v).is_some_and(|t| t.contains(&quota.role));
+        let members = partial.iter().filter(|v| tagged(v)).count() + usize::from(tagged(&candidate));
+        members + free >= quota.min_members
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::make_assignments;
+    use crate::tests::diagonal;
+    use alloc::string::ToString;
+
+    #[test]
+    fn every_group_has_a_leader() {
+        let tags = vec![vec!["leader".to_string()], vec![], vec!["x".to_string()], vec!["x".to_string(), "leader".to_string()]];
+        let quota = RoleQuota { role: "leader".to_string(), min_members: 1 };
+        let res = make_assignments_with_roles(&mut diagonal(6), 3, &tags, core::slice::from_ref(&quota)).unwrap();
+        let expected: Vec<_> = make_assignments(&mut diagonal(6), 3)
+            .into_iter()
+            .filter(|a| a.iter().flatten().all(|g| g.contains(&0) != g.contains(&3)))
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(res, expected);
+
+        let lone = RoleQuota { role: "x".to_string(), min_members: 2 };
+        let res = make_assignments_with_roles(&mut diagonal(6), 3, &tags, &[lone]).unwrap();
+        assert!(res.len() == 1 && res[0].is_empty());
+        let long = vec![vec![]; 7];
+        assert_eq!(
+            make_assignments_with_roles(&mut diagonal(6), 3, &long, &[quota]),
+            Err(GroupError::VertexOutOfRange { vertex: 6, n: 6 })
+        );
+    }
+
+    #[test]
+    fn group_means_stay_in_band() {
+        let scores = [1, 2, 3, 4, 5, 6];
+        let res = make_assignments_mean_balanced(&mut diagonal(6), 3, &scores, 1).unwrap();
+        let in_band = |g: &RawGroup| (8..=13).contains(&g.iter().map(|v| scores[*v]).sum::<i64>());
+        let expected: Vec<_> = make_assignments(&mut diagonal(6), 3)
+            .into_iter()
+            .filter(|a| a.iter().flatten().all(in_band))
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(res, expected);
+        // A mean of 3.5 is out of reach for trios of whole scores
+        let res = make_assignments_mean_balanced(&mut diagonal(6), 3, &scores, 0).unwrap();
+        assert!(res.len() == 1 && res[0].is_empty());
+        assert_eq!(
+            make_assignments_mean_balanced(&mut diagonal(6), 3, &scores[1..], 1),
+            Err(GroupError::AttributeMismatch { len: 5, n: 6 })
+        );
+    }
+
+    #[test]
+    fn personal_size_limits_hold() {
+        let res = make_assignments_with_size_limits(&mut diagonal(5), 2, &[2, 3, 3]).unwrap();
+        assert!(!res.is_empty());
+        for assignment in &res {
+            assert!(crate::validate_assignment(assignment, 5, 2));
+            assert!(assignment.iter().flatten().all(|g| !g.contains(&0) || g.len() == 2));
+        }
+        let loose = make_assignments_with_size_limits(&mut diagonal(5), 2, &[3; 5]).unwrap();
+        assert_eq!(loose, make_assignments(&mut diagonal(5), 2));
+        assert_eq!(
+            make_assignments_with_size_limits(&mut diagonal(5), 2, &[3; 6]),
+            Err(GroupError::VertexOutOfRange { vertex: 5, n: 5 })
+        );
+    }
+
+    #[test]
+    fn subsets_fill_each_group_exactly() {
+        let quota = SubsetQuota { members: vec![1, 2, 4, 5], per_group: 2 };
+        let res = make_assignments_with_subset_quotas(&mut diagonal(6), 3, core::slice::from_ref(&quota)).unwrap();
+        let exact = |g: &RawGroup| g.iter().filter(|v| quota.members.contains(v)).count() == 2;
+        let expected: Vec<_> = make_assignments(&mut diagonal(6), 3)
+            .into_iter()
+            .filter(|a| a.iter().flatten().all(exact))
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(res, expected);
+        let uneven = SubsetQuota { members: vec![0, 1, 2], per_group: 1 };
+        let res = make_assignments_with_subset_quotas(&mut diagonal(6), 3, &[uneven]).unwrap();
+        assert!(res.len() == 1 && res[0].is_empty());
+        let bad = SubsetQuota { members: vec![6], per_group: 1 };
+        assert_eq!(
+            make_assignments_with_subset_quotas(&mut diagonal(6), 3, &[bad]),
+            Err(GroupError::VertexOutOfRange { vertex: 6, n: 6 })
+        );
+    }
+
+    #[test]
+    fn groups_span_enough_categories() {
+        let categories = [0, 0, 1, 1, 2];
+        let res = make_assignments_diverse(&mut diagonal(6), 3, &categories, 2).unwrap();
+        let diverse = |g: &RawGroup| {
+            let mut seen: Vec<usize> = g.iter().filter_map(|v| categories.get(*v).copied()).collect();
+            seen.sort_unstable();
+            seen.dedup();
+            seen.len() >= 2
+        };
+        let expected: Vec<_> = make_assignments(&mut diagonal(6), 3)
+            .into_iter()
+            .filter(|a| a.iter().flatten().all(diverse))
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(res, expected);
+        let res = make_assignments_diverse(&mut diagonal(6), 3, &categories, 4).unwrap();
+        assert!(res.len() == 1 && res[0].is_empty());
+    }
+
+    #[test]
+    fn no_category_dominates_a_group() {
+        let categories = [0, 0, 0, 1, 1, 1];
+        let res = make_assignments_balanced(&mut diagonal(6), 3, &categories, 70).unwrap();
+        let expected: Vec<_> = make_assignments(&mut diagonal(6), 3)
+            .into_iter()
+            .filter(|a| a.iter().flatten().all(|g| g.iter().any(|v| categories[*v] != categories[g[0]])))
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(res, expected);
+        // At 60%, trios need three categories, and there are only two
+        let res = make_assignments_balanced(&mut diagonal(6), 3, &categories, 60).unwrap();
+        assert!(res.len() == 1 && res[0].is_empty());
+        // Uncategorized vertices do not count
+        let res = make_assignments_balanced(&mut diagonal(6), 3, &[0, 1, 2, 0], 60).unwrap();
+        assert!(res.iter().flatten().flatten().all(|g| !(g.contains(&0) && g.contains(&3))));
+        assert!(!res[0].is_empty());
+    }
+}
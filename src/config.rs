@@ -0,0 +1,663 @@
+//! # Solver Configuration
+//!
+//! A plain-data description of a scheduling problem, its constraints and
+//! solver options, which can be built programmatically or parsed from text
+//! (see [`parse_constraints()`](crate::parse_constraints)) and solved with
+//! [`solve_with_config()`].
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
+use crate::composition::{balance_allows, diversity_allows, quotas_allow, size_limit_allows, MeanBand, SubsetQuotas};
+use crate::{add_separation_sets, canonical_assignment, check_hosts, check_vertices, completes_forbidden, group_sizes_with_count, hosts_allow, group_sizes_with_policy, sort_canonical, validate_problem, validate_shape, visit_continuing, visit_maximal_together, ConflictMatrix, GroupError, LeftoverPolicy, RawGroup, RoleQuota, SeparationSet, SubsetQuota};
+
+/// Description of a scheduling problem, its constraints and solver options.
+///
+/// Options are added as fields rather than as extra solver arguments, so
+/// build it with struct update syntax to stay compatible as more are added:
+///
+/// ```
+/// # use group_generator::{solve_with_config, SolverConfig};
+/// let config = SolverConfig {
+///     vertices: 6,
+///     min_group_size: 3,
+///     max_assignments: Some(2),
+///     ..Default::default()
+/// };
+/// assert_eq!(solve_with_config(&config).unwrap().len(), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SolverConfig {
+    /// Number of vertices.
+    pub vertices: usize,
+    /// Pairs which may never share a group.
+    pub conflicts: Vec<(usize, usize)>,
+    /// Named sets whose members may never share a group (see
+    /// [`SeparationSet`]).
+    pub separation_sets: Vec<SeparationSet>,
+    /// Pairs which must share a group in every round (see
+    /// [`make_assignments_together()`](crate::make_assignments_together)).
+    pub together: Vec<(usize, usize)>,
+    /// Sets of vertices which may never all share a group, even if pairwise
+    /// conflict-free (see
+    /// [`make_assignments_forbidding()`](crate::make_assignments_forbidding)).
+    pub forbidden_groups: Vec<Vec<usize>>,
+    /// Role tags of each vertex, by index. Vertices past the end have none.
+    pub roles: Vec<Vec<String>>,
+    /// Composition rules every group must meet, in terms of
+    /// [`roles`](SolverConfig::roles) (see
+    /// [`make_assignments_with_roles()`](crate::make_assignments_with_roles)).
+    pub role_quotas: Vec<RoleQuota>,
+    /// Exact numbers of members every group must have from given subsets
+    /// (see
+    /// [`make_assignments_with_subset_quotas()`](crate::make_assignments_with_subset_quotas)).
+    pub subset_quotas: Vec<SubsetQuota>,
+    /// Category of each vertex (e.g. its department), by index. Vertices
+    /// past the end have none.
+    pub categories: Vec<usize>,
+    /// Largest share of any group, in percent, which may come from a single
+    /// [category](SolverConfig::categories), if limited (see
+    /// [`make_assignments_balanced()`](crate::make_assignments_balanced)).
+    pub max_category_percent: Option<usize>,
+    /// Least number of distinct [categories](SolverConfig::categories) in
+    /// every group, if required (see
+    /// [`make_assignments_diverse()`](crate::make_assignments_diverse)).
+    pub min_distinct_categories: Option<usize>,
+    /// Score of each vertex (e.g. a skill rating), by index.
+    pub scores: Vec<i64>,
+    /// Largest distance of any group's mean score from the mean of all
+    /// [`scores`](SolverConfig::scores), if limited (see
+    /// [`make_assignments_mean_balanced()`](crate::make_assignments_mean_balanced)).
+    /// There must then be exactly one score per vertex.
+    pub score_tolerance: Option<u64>,
+    /// Personal maximum group size of each vertex, by index. Vertices past
+    /// the end have none (see
+    /// [`make_assignments_with_size_limits()`](crate::make_assignments_with_size_limits)).
+    pub max_sizes: Vec<usize>,
+    /// Vertices anchored to a fixed group slot in every round (e.g. a table
+    /// host), in slot order (see
+    /// [`make_assignments_with_hosts()`](crate::make_assignments_with_hosts)).
+    pub anchors: Vec<usize>,
+    /// Minimum group size.
+    pub min_group_size: usize,
+    /// Maximum group size, if limited.
+    pub max_group_size: Option<usize>,
+    /// Exact number of groups per round, if fixed (see
+    /// [`group_sizes_with_count()`]). Otherwise it follows from the minimum
+    /// group size.
+    pub num_groups: Option<usize>,
+    /// Explicit group sizes for every round, e.g. `[4, 3, 3]`, if given.
+    /// This replaces the shape derived from the minimum group size or
+    /// [`num_groups`](SolverConfig::num_groups).
+    pub group_shape: Option<Vec<usize>>,
+    /// Where vertices left over from an even split by the minimum group size
+    /// are placed (see [`group_sizes_with_policy()`]).
+    pub leftover_policy: LeftoverPolicy,
+    /// A hand-picked first round, if given. Only the rounds after it are
+    /// searched (as in
+    /// [`make_assignments_continuing()`](crate::make_assignments_continuing)),
+    /// and every assignment starts with it.
+    pub first_round: Option<Vec<RawGroup>>,
+    /// Stop after this many maximal assignments, if limited.
+    pub max_assignments: Option<usize>,
+    /// Return assignments in canonical order (see [`sort_canonical()`])
+    /// instead of search order, so results can be diffed between runs.
+    pub canonical_order: bool,
+    /// Return each distinct schedule once, in canonical form (see
+    /// [`canonical_assignment()`]), collapsing assignments which only differ
+    /// in the order of groups within rounds or the order of rounds.
+    pub deduplicate: bool,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self {
+            vertices: 0,
+            conflicts: vec![],
+            separation_sets: vec![],
+            together: vec![],
+            forbidden_groups: vec![],
+            roles: vec![],
+            role_quotas: vec![],
+            subset_quotas: vec![],
+            categories: vec![],
+            max_category_percent: None,
+            min_distinct_categories: None,
+            scores: vec![],
+            score_tolerance: None,
+            max_sizes: vec![],
+            anchors: vec![],
+            min_group_size: 2,
+            max_group_size: None,
+            num_groups: None,
+            group_shape: None,
+            leftover_policy: LeftoverPolicy::default(),
+            first_round: None,
+            max_assignments: None,
+            canonical_order: false,
+            deduplicate: false,
+        }
+    }
+}
+
+impl SolverConfig {
+    /// Build the conflict matrix for this configuration: the usual diagonal
+    /// plus every pair in [`conflicts`](SolverConfig::conflicts) and within
+    /// each of the [`separation_sets`](SolverConfig::separation_sets),
+    /// symmetrically.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a conflict or set refers to a vertex out of range.
+    pub fn conflict_matrix(&self) -> ConflictMatrix {
+        let mut conflicts = ConflictMatrix::empty(self.vertices).with_pairs(self.conflicts.iter().copied());
+        if let Err(err) = add_separation_sets(&mut conflicts, &self.separation_sets) {
+            panic!("{err}");
+        }
+        conflicts
+    }
+
+    /// The group sizes of every round.
+    ///
+    /// An explicit [`group_shape`](SolverConfig::group_shape) is used as is.
+    /// Otherwise, with [`num_groups`](SolverConfig::num_groups) set, there are exactly
+    /// that many groups (see [`group_sizes_with_count()`]). Otherwise there
+    /// are as many as the minimum group size allows (see
+    /// [`group_sizes()`](crate::group_sizes)). Either way, vertices left over
+    /// from an even split are placed according to the
+    /// [`leftover_policy`](SolverConfig::leftover_policy).
+    ///
+    /// # Errors
+    ///
+    /// - [`GroupError::InvalidMinGroupSize`] if the minimum group size is 0 or
+    ///   exceeds the number of vertices.
+    /// - [`GroupError::InvalidGroupCount`] if the groups cannot all reach the
+    ///   minimum size.
+    /// - [`GroupError::InvalidGroupShape`] if the explicit shape is empty, has
+    ///   a group of 0 or does not sum to the number of vertices.
+    /// - [`GroupError::NoFeasibleShape`] if a group is larger than
+    ///   [`max_group_size`](SolverConfig::max_group_size).
+    ///   [`LeftoverPolicy::TrailingRemainder`] piles all leftovers into one
+    ///   group, so it can fail where the default succeeds.
+    pub fn round_shape(&self) -> Result<Vec<usize>, GroupError> {
+        let (n, min_group_size) = (self.vertices, self.min_group_size);
+        let sizes = match (&self.group_shape, self.num_groups) {
+            (Some(shape), _) => {
+                validate_shape(n, shape)?;
+                shape.clone()
+            }
+            (None, Some(num_groups)) => {
+                let mut sizes = group_sizes_with_count(n, min_group_size, num_groups)?;
+                if self.leftover_policy == LeftoverPolicy::TrailingRemainder {
+                    sizes.fill(n / num_groups);
+                    if let Some(last) = sizes.last_mut() {
+                        *last += n % num_groups;
+                    }
+                }
+                sizes
+            }
+            (None, None) => {
+                if min_group_size == 0 || min_group_size > n {
+                    return Err(GroupError::InvalidMinGroupSize { min_group_size, n });
+                }
+                group_sizes_with_policy(n, min_group_size, self.leftover_policy)
+            }
+        };
+        if let Some(max_group_size) = self.max_group_size {
+            if sizes.iter().any(|k| *k > max_group_size) {
+                return Err(GroupError::NoFeasibleShape { n, min_group_size, max_group_size });
+            }
+        }
+        Ok(sizes)
+    }
+}
+
+/// Generate the *maximum-round* assignments described by `config`, as
+/// [`make_assignments()`](crate::make_assignments) does for a bare conflict
+/// matrix.
+///
+/// Rounds are shaped as described by [`SolverConfig::round_shape()`], and the
+/// search stops once [`max_assignments`](SolverConfig::max_assignments) have
+/// been found.
+/// Assignments come in the same order as from
+/// [`make_assignments_with()`](crate::make_assignments_with), unless
+/// [`canonical_order`](SolverConfig::canonical_order) is set, in which case
+/// they are passed through [`sort_canonical()`]. With a limit, that sorts the
+/// assignments found before stopping, not the first ones overall.
+///
+/// With [`deduplicate`](SolverConfig::deduplicate), duplicates are dropped as
+/// the search goes, so a limit counts distinct schedules.
+///
+/// # Errors
+///
+/// - [`GroupError::VertexOutOfRange`] if a constraint refers to a vertex
+///   which does not exist.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`](crate::try_make_assignments).
+/// - Any error from [`SolverConfig::round_shape()`].
+/// - [`GroupError::AttributeMismatch`] if a
+///   [`score_tolerance`](SolverConfig::score_tolerance) is set without
+///   exactly one score per vertex.
+/// - [`GroupError::InvalidRound`] if the
+///   [`first_round`](SolverConfig::first_round) is not a conflict-free
+///   partition into groups of at least the minimum size, or splits a pair
+///   which must stay [`together`](SolverConfig::together).
+/// - [`GroupError::GroupIndexOutOfRange`] if there are more
+///   [`anchors`](SolverConfig::anchors) than groups per round.
+pub fn solve_with_config(config: &SolverConfig) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    let n = config.vertices;
+    let mut conflicts = ConflictMatrix::from_edges(n, config.conflicts.iter().copied())?;
+    add_separation_sets(&mut conflicts, &config.separation_sets)?;
+    validate_problem(&conflicts, config.min_group_size)?;
+    check_vertices(n, config.forbidden_groups.iter().flatten().copied())?;
+    check_vertices(n, config.roles.len().checked_sub(1))?;
+    check_vertices(n, config.categories.len().checked_sub(1))?;
+    check_vertices(n, config.max_sizes.len().checked_sub(1))?;
+
+    let limit = config.max_assignments.unwrap_or(usize::MAX);
+    let mut sols = vec![];
+    if limit == 0 {
+        return Ok(sols);
+    }
+    let group_sizes = config.round_shape()?;
+    check_hosts(n, group_sizes.len(), &config.anchors)?;
+    let mut seen = BTreeSet::new();
+    let visit = |assignment: &[Vec<RawGroup>]| {
+        if !config.deduplicate {
+            sols.push(assignment.to_vec());
+        } else {
+            let canonical = canonical_assignment(assignment);
+            if !seen.insert(canonical.clone()) {
+                return ControlFlow::Continue(());
+            }
+            sols.push(canonical);
+        }
+        if sols.len() == limit {
+            ControlFlow::Break(())
+        } else {
+            ControlFlow::Continue(())
+        }
+    };
+    let history = config.first_round.as_slice();
+    let subset_quotas = SubsetQuotas::new(n, &config.subset_quotas)?;
+    let band = config.score_tolerance.map(|tolerance| MeanBand::new(n, &config.scores, tolerance)).transpose()?;
+    let allows = |slot, partial: &[usize], candidate| {
+        !completes_forbidden(partial, candidate, &config.forbidden_groups)
+            && quotas_allow(&config.roles, &config.role_quotas, slot, partial, candidate)
+            && subset_quotas.allows(slot, partial, candidate)
+            && config
+                .max_category_percent
+                .is_none_or(|percent| balance_allows(&config.categories, percent, slot, partial, candidate))
+            && config
+                .min_distinct_categories
+                .is_none_or(|d| diversity_allows(&config.categories, d, slot, partial, candidate))
+            && band.as_ref().is_none_or(|band| band.allows(slot, partial, candidate))
+            && hosts_allow(&config.anchors, slot, partial, candidate)
+            && size_limit_allows(&config.max_sizes, slot, candidate)
+    };
+    if config.together.is_empty() {
+        visit_continuing::<()>(&mut conflicts, config.min_group_size, &group_sizes, history, allows, visit)?;
+    } else {
+        visit_maximal_together::<()>(&mut conflicts, config.min_group_size, &group_sizes, &config.together, history, allows, visit)?;
+    }
+    if config.canonical_order {
+        sort_canonical(&mut sols);
+    }
+    Ok(sols)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{dedup_canonical, make_assignments, make_assignments_continuing, make_assignments_diverse, make_assignments_forbidding, make_assignments_balanced, make_assignments_mean_balanced, make_assignments_together, make_assignments_with_hosts, make_assignments_with_roles, make_assignments_with_size_limits, make_assignments_with_subset_quotas};
+    use alloc::string::ToString;
+    use bitvec::prelude::*;
+
+    #[test]
+    fn conflict_matrix_is_symmetric() {
+        let config = SolverConfig {
+            vertices: 3,
+            conflicts: vec![(0, 2)],
+            ..Default::default()
+        };
+        let rows: Vec<BitVec> = config.conflict_matrix().into();
+        assert_eq!(rows, vec![bitvec![1, 0, 1], bitvec![0, 1, 0], bitvec![1, 0, 1]]);
+    }
+
+    #[test]
+    fn solve_matches_make_assignments() {
+        let config = SolverConfig {
+            vertices: 6,
+            conflicts: vec![(0, 1)],
+            min_group_size: 3,
+            ..Default::default()
+        };
+        let expected = make_assignments(&mut config.conflict_matrix(), 3);
+        assert_eq!(solve_with_config(&config).unwrap(), expected);
+
+        let limited = SolverConfig { max_assignments: Some(3), ..config.clone() };
+        assert_eq!(solve_with_config(&limited).unwrap(), expected[..3]);
+        let none = SolverConfig { max_assignments: Some(0), ..config };
+        assert!(solve_with_config(&none).unwrap().is_empty());
+    }
+
+    #[test]
+    fn solve_in_canonical_order() {
+        let config = SolverConfig {
+            vertices: 6,
+            min_group_size: 3,
+            canonical_order: true,
+            ..Default::default()
+        };
+        let res = solve_with_config(&config).unwrap();
+        assert!(res.windows(2).all(|w| w[0] <= w[1]));
+        assert!(res.iter().flatten().all(|round| round.is_sorted()));
+        let mut expected = make_assignments(&mut config.conflict_matrix(), 3);
+        sort_canonical(&mut expected);
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn solve_deduplicated() {
+        let config = SolverConfig {
+            vertices: 6,
+            min_group_size: 3,
+            deduplicate: true,
+            ..Default::default()
+        };
+        let mut expected = make_assignments(&mut config.conflict_matrix(), 3);
+        dedup_canonical(&mut expected);
+        let mut res = solve_with_config(&config).unwrap();
+        assert!(res.len() < make_assignments(&mut config.conflict_matrix(), 3).len());
+        res.sort_unstable();
+        assert_eq!(res, expected);
+
+        let limited = SolverConfig { max_assignments: Some(2), ..config };
+        let res = solve_with_config(&limited).unwrap();
+        assert_eq!(res.len(), 2);
+        assert_ne!(res[0], res[1]);
+    }
+
+    #[test]
+    fn solve_follows_leftover_policy() {
+        let config = SolverConfig {
+            vertices: 7,
+            min_group_size: 3,
+            leftover_policy: LeftoverPolicy::TrailingRemainder,
+            max_assignments: Some(1),
+            ..Default::default()
+        };
+        let res = solve_with_config(&config).unwrap();
+        assert!(res[0].iter().all(|round| round.iter().map(Vec::len).eq([3, 4])));
+    }
+
+    #[test]
+    fn solve_reports_invalid_config() {
+        let config = SolverConfig {
+            vertices: 3,
+            conflicts: vec![(0, 3)],
+            ..Default::default()
+        };
+        assert_eq!(solve_with_config(&config), Err(GroupError::VertexOutOfRange { vertex: 3, n: 3 }));
+        let config = SolverConfig {
+            vertices: 4,
+            anchors: vec![0, 1, 2],
+            ..Default::default()
+        };
+        assert_eq!(
+            solve_with_config(&config),
+            Err(GroupError::GroupIndexOutOfRange { group: 2, num_groups: 2 })
+        );
+    }
+
+    #[test]
+    fn solve_continues_first_round() {
+        let first_round = vec![vec![0, 1, 2], vec![3, 4]];
+        let config = SolverConfig {
+            vertices: 5,
+            first_round: Some(first_round.clone()),
+            ..Default::default()
+        };
+        let expected = make_assignments_continuing(&mut config.conflict_matrix(), 2, &[first_round]).unwrap();
+        assert_eq!(solve_with_config(&config).unwrap(), expected);
+
+        let linked = SolverConfig { together: vec![(0, 1)], ..config.clone() };
+        let res = solve_with_config(&linked).unwrap();
+        assert!(res.iter().all(|a| a[0] == config.first_round.clone().unwrap()));
+        assert!(res.iter().flatten().all(|round| round.iter().any(|g| g.contains(&0) && g.contains(&1))));
+        let split = SolverConfig { together: vec![(0, 3)], ..config.clone() };
+        assert_eq!(solve_with_config(&split), Err(GroupError::InvalidRound));
+        let repeated = SolverConfig { conflicts: vec![(3, 4)], ..config };
+        assert_eq!(solve_with_config(&repeated), Err(GroupError::InvalidRound));
+    }
+
+    #[test]
+    fn solve_respects_personal_size_limits() {
+        let config = SolverConfig {
+            vertices: 5,
+            min_group_size: 2,
+            max_sizes: vec![2, 2],
+            ..Default::default()
+        };
+        let expected = make_assignments_with_size_limits(&mut config.conflict_matrix(), 2, &config.max_sizes).unwrap();
+        assert!(!expected.is_empty());
+        assert_eq!(solve_with_config(&config).unwrap(), expected);
+    }
+
+    #[test]
+    fn solve_keeps_anchors_in_their_slots() {
+        let config = SolverConfig {
+            vertices: 6,
+            min_group_size: 3,
+            anchors: vec![5, 2],
+            ..Default::default()
+        };
+        let expected = make_assignments_with_hosts(&mut config.conflict_matrix(), 3, &config.anchors).unwrap();
+        assert!(!expected.is_empty());
+        assert_eq!(solve_with_config(&config).unwrap(), expected);
+    }
+
+    #[test]
+    fn solve_keeps_separation_sets_apart() {
+        let config = SolverConfig {
+            vertices: 6,
+            min_group_size: 2,
+            separation_sets: vec![SeparationSet { name: "rivals".into(), members: vec![0, 1, 2] }],
+            ..Default::default()
+        };
+        let expected = make_assignments(&mut ConflictMatrix::empty(6).with_pairs([(0, 1), (0, 2), (1, 2)]), 2);
+        assert_eq!(config.conflict_matrix().pairs().count(), 3);
+        assert_eq!(solve_with_config(&config).unwrap(), expected);
+        let bad = SolverConfig {
+            separation_sets: vec![SeparationSet { name: "rivals".into(), members: vec![0, 6] }],
+            ..config
+        };
+        assert_eq!(solve_with_config(&bad), Err(GroupError::VertexOutOfRange { vertex: 6, n: 6 }));
+    }
+
+    #[test]
+    fn solve_avoids_forbidden_groups() {
+        let config = SolverConfig {
+            vertices: 6,
+            min_group_size: 3,
+            forbidden_groups: vec![vec![0, 1, 2]],
+            ..Default::default()
+        };
+        let expected = make_assignments_forbidding(&mut config.conflict_matrix(), 3, &config.forbidden_groups).unwrap();
+        assert_eq!(solve_with_config(&config).unwrap(), expected);
+        let linked = SolverConfig { together: vec![(0, 1)], ..config };
+        let res = solve_with_config(&linked).unwrap();
+        assert!(!res.is_empty());
+        assert!(res.iter().flatten().flatten().all(|g| g.contains(&0) != g.contains(&2)));
+    }
+
+    #[test]
+    fn solve_meets_role_quotas() {
+        let config = SolverConfig {
+            vertices: 6,
+            min_group_size: 3,
+            roles: vec![vec!["leader".to_string()], vec![], vec!["leader".to_string()]],
+            role_quotas: vec![RoleQuota { role: "leader".to_string(), min_members: 1 }],
+            ..Default::default()
+        };
+        let expected = make_assignments_with_roles(&mut config.conflict_matrix(), 3, &config.roles, &config.role_quotas).unwrap();
+        assert!(!expected.is_empty());
+        assert_eq!(solve_with_config(&config).unwrap(), expected);
+    }
+
+    #[test]
+    fn solve_meets_subset_quotas() {
+        let config = SolverConfig {
+            vertices: 6,
+            min_group_size: 3,
+            subset_quotas: vec![SubsetQuota { members: vec![0, 3], per_group: 1 }],
+            ..Default::default()
+        };
+        let expected = make_assignments_with_subset_quotas(&mut config.conflict_matrix(), 3, &config.subset_quotas).unwrap();
+        assert!(!expected.is_empty());
+        assert_eq!(solve_with_config(&config).unwrap(), expected);
+    }
+
+    #[test]
+    fn solve_requires_diverse_groups() {
+        let config = SolverConfig {
+            vertices: 6,
+            min_group_size: 3,
+            categories: vec![0, 0, 0, 1, 1, 1],
+            min_distinct_categories: Some(2),
+            ..Default::default()
+        };
+        let expected = make_assignments_diverse(&mut config.conflict_matrix(), 3, &config.categories, 2).unwrap();
+        assert!(!expected.is_empty());
+        assert_eq!(solve_with_config(&config).unwrap(), expected);
+    }
+
+    #[test]
+    fn solve_balances_categories() {
+        let config = SolverConfig {
+            vertices: 6,
+            min_group_size: 3,
+            categories: vec![0, 0, 0, 1, 1, 1],
+            max_category_percent: Some(70),
+            ..Default::default()
+        };
+        let expected = make_assignments_balanced(&mut config.conflict_matrix(), 3, &config.categories, 70).unwrap();
+        assert_eq!(solve_with_config(&config).unwrap(), expected);
+        let unlimited = SolverConfig { max_category_percent: None, ..config.clone() };
+        assert!(solve_with_config(&unlimited).unwrap().len() > expected.len());
+    }
+
+    #[test]
+    fn solve_balances_scores() {
+        let config = SolverConfig {
+            vertices: 6,
+            min_group_size: 3,
+            scores: vec![1, 2, 3, 4, 5, 6],
+            score_tolerance: Some(1),
+            ..Default::default()
+        };
+        let expected = make_assignments_mean_balanced(&mut config.conflict_matrix(), 3, &config.scores, 1).unwrap();
+        assert_eq!(solve_with_config(&config).unwrap(), expected);
+        let short = SolverConfig { scores: vec![1], ..config };
+        assert_eq!(solve_with_config(&short), Err(GroupError::AttributeMismatch { len: 1, n: 6 }));
+    }
+
+    #[test]
+    fn solve_keeps_linked_pairs_together() {
+        let config = SolverConfig {
+            vertices: 6,
+            together: vec![(0, 1), (1, 2)],
+            min_group_size: 3,
+            ..Default::default()
+        };
+        let res = solve_with_config(&config).unwrap();
+        assert_eq!(res, make_assignments_together(&mut config.conflict_matrix(), 3, &config.together).unwrap());
+        // The trio is its own group in every round, so nobody else can meet twice
+        assert!(res.iter().all(|assignment| assignment.len() == 1));
+        let config = SolverConfig { together: vec![(0, 6)], ..config };
+        assert_eq!(solve_with_config(&config), Err(GroupError::VertexOutOfRange { vertex: 6, n: 6 }));
+    }
+
+    #[test]
+    fn solve_respects_max_group_size() {
+        let config = SolverConfig {
+            vertices: 5,
+            min_group_size: 2,
+            max_group_size: Some(3),
+            max_assignments: Some(5),
+            ..Default::default()
+        };
+        let res = solve_with_config(&config).unwrap();
+        assert!(res.iter().flatten().flatten().all(|g| g.len() <= 3));
+
+        let tight = SolverConfig { max_group_size: Some(2), ..config.clone() };
+        let err = GroupError::NoFeasibleShape { n: 5, min_group_size: 2, max_group_size: 2 };
+        assert_eq!(solve_with_config(&tight), Err(err));
+        // Evenly spread, 8 vertices fit in groups of 3 to 4 as [4, 4], but
+        // not as [3, 5]
+        let spread = SolverConfig {
+            vertices: 8,
+            min_group_size: 3,
+            max_group_size: Some(4),
+            ..config
+        };
+        assert!(solve_with_config(&spread).is_ok());
+        let trailing = SolverConfig { leftover_policy: LeftoverPolicy::TrailingRemainder, ..spread };
+        assert!(matches!(solve_with_config(&trailing), Err(GroupError::NoFeasibleShape { .. })));
+    }
+
+    #[test]
+    fn fixed_group_count() {
+        let config = SolverConfig {
+            vertices: 7,
+            min_group_size: 2,
+            num_groups: Some(2),
+            max_assignments: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(config.round_shape(), Ok(vec![4, 3]));
+        let trailing = SolverConfig { leftover_policy: LeftoverPolicy::TrailingRemainder, ..config.clone() };
+        assert_eq!(trailing.round_shape(), Ok(vec![3, 4]));
+        let res = solve_with_config(&config).unwrap();
+        assert_eq!(res.len(), 3);
+        assert!(res.iter().flatten().all(|round| round.iter().map(Vec::len).eq([4, 3])));
+
+        let crowded = SolverConfig { num_groups: Some(4), ..config };
+        let err = GroupError::InvalidGroupCount { num_groups: 4, n: 7, min_group_size: 2 };
+        assert_eq!(solve_with_config(&crowded), Err(err));
+    }
+
+    #[test]
+    fn explicit_group_shape() {
+        let config = SolverConfig {
+            vertices: 6,
+            group_shape: Some(vec![2, 4]),
+            num_groups: Some(3),
+            ..Default::default()
+        };
+        assert_eq!(config.round_shape(), Ok(vec![2, 4]));
+        let res = solve_with_config(&config).unwrap();
+        assert!(res.iter().flatten().all(|round| round.iter().map(Vec::len).eq([2, 4])));
+
+        let capped = SolverConfig { max_group_size: Some(3), ..config.clone() };
+        assert!(matches!(capped.round_shape(), Err(GroupError::NoFeasibleShape { .. })));
+        let short = SolverConfig { group_shape: Some(vec![2, 3]), ..config };
+        assert_eq!(solve_with_config(&short), Err(GroupError::InvalidGroupShape { n: 6 }));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let config = SolverConfig {
+            vertices: 5,
+            together: vec![(1, 2)],
+            max_group_size: Some(3),
+            ..Default::default()
+        };
+        let json = serde_json::to_string(&config).unwrap();
+        assert_eq!(serde_json::from_str::<SolverConfig>(&json).unwrap(), config);
+    }
+}
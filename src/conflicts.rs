@@ -0,0 +1,570 @@
+//! # Conflict Matrices
+//!
+//! The [`ConflictMatrix`] type, and convenience constructors for building one
+//! from common real-world sources instead of setting entries by hand.
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use bitvec::prelude::*;
+
+use crate::{add_conflicts_between, remove_conflicts_between, GroupError, VertexIndex};
+
+/// Symmetric record of which pairs of vertices may **not** share a group.
+///
+/// Every vertex conflicts with itself, so the diagonal is always set and
+/// cannot be cleared. All other entries are kept symmetric: setting or
+/// clearing `(i, j)` also sets or clears `(j, i)`.
+///
+/// The solvers update the matrix while searching (marking the pairs of every
+/// committed round), but always return it to its previous state.
+///
+/// # Hyperedges
+///
+/// Besides pairs, the matrix can hold conflicts over larger sets: a
+/// [hyperedge](ConflictMatrix::add_hyperedge) such as `{a, b, c}` forbids
+/// all of its members sharing a group, even when every pair among them is
+/// fine. Hyperedges are fixed for the problem, and the solvers check them
+/// while building groups but never add any.
+///
+/// # Storage
+///
+/// Only the strict upper triangle is stored, row after row in a single packed
+/// bit vector, so `n` vertices take `n(n - 1) / 2` bits. The diagonal is
+/// implied rather than stored.
+///
+/// With the `serde` feature, the matrix is serialized independently of this
+/// layout, as the number of vertices and the list of conflicting pairs
+/// (`{"n": 4, "pairs": [[0, 1]]}` in JSON), plus a list of `hyperedges`
+/// when there are any.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(into = "PairList", try_from = "PairList")
+)]
+pub struct ConflictMatrix {
+    n: usize,
+    /// Entry `(i, j)` for `i < j` is at `row_start(i) + (j - i - 1)`.
+    bits: BitVec,
+    /// Conflicting sets of three or more vertices, each ascending.
+    hyperedges: Vec<Vec<usize>>,
+}
+
+impl ConflictMatrix {
+    /// A matrix on `n` vertices with no conflicts besides the diagonal.
+    pub fn empty(n: usize) -> Self {
+        Self {
+            n,
+            bits: bitvec![0; n * n.saturating_sub(1) / 2],
+            hyperedges: Vec::new(),
+        }
+    }
+
+    /// Add a conflict for every pair in `pairs`.
+    ///
+    /// ```
+    /// # use group_generator::ConflictMatrix;
+    /// let conflicts = ConflictMatrix::empty(4).with_pairs([(0, 1), (2, 3)]);
+    /// assert!(conflicts.is_conflicted(1, 0));
+    /// assert!(!conflicts.is_conflicted(0, 2));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if a pair refers to a vertex out of range.
+    pub fn with_pairs(mut self, pairs: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        for (i, j) in pairs {
+            self.add_conflict(i, j);
+        }
+        self
+    }
+
+    /// A matrix on `n` vertices with a conflict for every edge `(i, j)` in
+    /// `edges`, e.g. pairs who have already worked together.
+    ///
+    /// ```
+    /// # use group_generator::ConflictMatrix;
+    /// let conflicts = ConflictMatrix::from_edges(3, [(0, 2)]).unwrap();
+    /// assert!(conflicts.is_conflicted(2, 0));
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// [`GroupError::VertexOutOfRange`] if an edge refers to a vertex which
+    /// does not exist.
+    pub fn from_edges(n: usize, edges: impl IntoIterator<Item = (usize, usize)>) -> Result<Self, GroupError> {
+        let mut conflicts = Self::empty(n);
+        for (i, j) in edges {
+            if let Some(vertex) = [i, j].into_iter().find(|v| *v >= n) {
+                return Err(GroupError::VertexOutOfRange { vertex, n });
+            }
+            conflicts.add_conflict(i, j);
+        }
+        Ok(conflicts)
+    }
+
+    /// A matrix with one vertex per entry of `adjacency`, where vertex `i`
+    /// conflicts with every vertex listed in `adjacency[i]`.
+    ///
+    /// Each conflict only needs to be listed from one side.
+    ///
+    /// # Errors
+    ///
+    /// [`GroupError::VertexOutOfRange`] if a list refers to a vertex which
+    /// does not exist.
+    pub fn from_adjacency_list<L: AsRef<[usize]>>(adjacency: &[L]) -> Result<Self, GroupError> {
+        let edges = adjacency
+            .iter()
+            .enumerate()
+            .flat_map(|(i, list)| list.as_ref().iter().map(move |j| (i, *j)));
+        Self::from_edges(adjacency.len(), edges)
+    }
+
+    /// Number of vertices.
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    /// Whether the matrix has no vertices.
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+
+    /// Whether vertices `i` and `j` may not share a group. Always true when
+    /// `i == j`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` or `j` is out of range.
+    #[inline]
+    pub fn is_conflicted(&self, i: usize, j: usize) -> bool {
+        match self.index(i, j) {
+            Some(idx) => self.bits[idx],
+            None => true,
+        }
+    }
+
+    /// Number of other vertices `v` conflicts with.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` is out of range.
+    pub fn degree(&self, v: usize) -> usize {
+        assert!(v < self.n, "Vertex {v} is out of range for {} vertices.", self.n);
+        let before = (0..v).filter(|u| self.bits[self.row_start(*u) + (v - u - 1)]).count();
+        before + self.row(v).count_ones()
+    }
+
+    /// Forbid vertices `i` and `j` from sharing a group.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` or `j` is out of range.
+    #[inline]
+    pub fn add_conflict(&mut self, i: usize, j: usize) {
+        if let Some(idx) = self.index(i, j) {
+            self.bits.set(idx, true);
+        }
+    }
+
+    /// Allow vertices `i` and `j` to share a group again. The diagonal is left
+    /// untouched.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i` or `j` is out of range.
+    #[inline]
+    pub fn remove_conflict(&mut self, i: usize, j: usize) {
+        if let Some(idx) = self.index(i, j) {
+            self.bits.set(idx, false);
+        }
+    }
+
+    /// Forbid all of `members` from sharing a group, while any smaller subset
+    /// of them may.
+    ///
+    /// ```
+    /// # use group_generator::ConflictMatrix;
+    /// let mut conflicts = ConflictMatrix::empty(4);
+    /// conflicts.add_hyperedge(&[2, 0, 1]);
+    /// assert!(conflicts.is_group_conflicted(&[0, 1, 2, 3]));
+    /// assert!(!conflicts.is_group_conflicted(&[0, 1, 3]));
+    /// ```
+    ///
+    /// Repeated members count once, and a set of two is the same as
+    /// [`add_conflict()`](ConflictMatrix::add_conflict).
+    ///
+    /// # Panics
+    ///
+    /// Panics if a member is out of range, or if `members` has fewer than two
+    /// distinct vertices.
+    pub fn add_hyperedge(&mut self, members: &[usize]) {
+        let mut members = members.to_vec();
+        members.sort_unstable();
+        members.dedup();
+        if let Some(v) = members.iter().find(|v| **v >= self.n) {
+            panic!("Vertex {v} is out of range for {} vertices.", self.n);
+        }
+        match members[..] {
+            [] | [_] => panic!("A conflict needs at least two vertices."),
+            [i, j] => self.add_conflict(i, j),
+            _ if self.hyperedges.contains(&members) => {}
+            _ => self.hyperedges.push(members),
+        }
+    }
+
+    /// The conflicting sets of three or more vertices, each ascending, in the
+    /// order they were added.
+    pub fn hyperedges(&self) -> &[Vec<usize>] {
+        &self.hyperedges
+    }
+
+    /// Whether `group` contains a conflicting pair, or every member of a
+    /// hyperedge.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a member of `group` is out of range.
+    pub fn is_group_conflicted(&self, group: &[usize]) -> bool {
+        let has_pair = group.iter().enumerate().any(|(i, u)| group[..i].iter().any(|v| self.is_conflicted(*u, *v)));
+        has_pair || self.hyperedges.iter().any(|edge| edge.iter().all(|v| group.contains(v)))
+    }
+
+    /// Whether adding `candidate` to `partial` would complete a hyperedge.
+    /// Pairs are not checked.
+    #[inline]
+    pub(crate) fn completes_hyperedge<V: VertexIndex>(&self, partial: &[V], candidate: usize) -> bool {
+        self.hyperedges.iter().any(|edge| {
+            edge.contains(&candidate) && edge.iter().all(|v| *v == candidate || partial.iter().any(|p| p.index() == *v))
+        })
+    }
+
+    /// Every conflicting pair `(i, j)` with `i < j`, in ascending order.
+    pub fn pairs(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..self.n).flat_map(move |i| self.row(i).iter_ones().map(move |k| (i, i + 1 + k)))
+    }
+
+    /// Position of entry `(i, j)` in the packed triangle, or `None` on the
+    /// diagonal.
+    #[inline]
+    fn index(&self, i: usize, j: usize) -> Option<usize> {
+        let (i, j) = (i.min(j), i.max(j));
+        assert!(j < self.n, "Vertex {j} is out of range for {} vertices.", self.n);
+        (i != j).then(|| self.row_start(i) + (j - i - 1))
+    }
+
+    /// Position of entry `(i, i + 1)`, where the stored part of row `i` starts.
+    #[inline]
+    fn row_start(&self, i: usize) -> usize {
+        i * (2 * self.n - i - 1) / 2
+    }
+
+    /// The stored part of row `i`: entries `(i, j)` for `j > i`.
+    fn row(&self, i: usize) -> &BitSlice {
+        let start = self.row_start(i);
+        &self.bits[start..start + (self.n - i - 1)]
+    }
+}
+
+/// Check that raw rows form a square, symmetric matrix, setting the diagonal.
+///
+/// # Errors
+///
+/// - [`GroupError::DimensionMismatch`] if the rows are not all as long as the
+///   number of rows.
+/// - [`GroupError::AsymmetricConflict`] if `rows[i][j]` is set but
+///   `rows[j][i]` is not.
+impl TryFrom<Vec<BitVec>> for ConflictMatrix {
+    type Error = GroupError;
+
+    fn try_from(rows: Vec<BitVec>) -> Result<Self, GroupError> {
+        let n = rows.len();
+        if let Some((row, v)) = rows.iter().enumerate().find(|(_, v)| v.len() != n) {
+            return Err(GroupError::DimensionMismatch { row, len: v.len(), n });
+        }
+        for (i, row) in rows.iter().enumerate() {
+            if let Some(j) = row.iter_ones().find(|j| !rows[*j][i]) {
+                return Err(GroupError::AsymmetricConflict { i, j });
+            }
+        }
+        let pairs = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(i, row)| row.iter_ones().filter(move |j| *j > i).map(move |j| (i, j)));
+        Ok(Self::empty(n).with_pairs(pairs))
+    }
+}
+
+/// Expand into one full row per vertex, with the diagonal set. Hyperedges
+/// have no place in the rows and are dropped.
+impl From<ConflictMatrix> for Vec<BitVec> {
+    fn from(matrix: ConflictMatrix) -> Self {
+        let n = matrix.len();
+        (0..n)
+            .map(|i| (0..n).map(|j| matrix.is_conflicted(i, j)).collect())
+            .collect()
+    }
+}
+
+/// Serialized form of a [`ConflictMatrix`].
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct PairList {
+    n: usize,
+    pairs: Vec<(usize, usize)>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    hyperedges: Vec<Vec<usize>>,
+}
+
+#[cfg(feature = "serde")]
+impl From<ConflictMatrix> for PairList {
+    fn from(matrix: ConflictMatrix) -> Self {
+        let pairs = matrix.pairs().collect();
+        Self { n: matrix.n, pairs, hyperedges: matrix.hyperedges }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<PairList> for ConflictMatrix {
+    type Error = GroupError;
+
+    fn try_from(list: PairList) -> Result<Self, GroupError> {
+        let mut matrix = Self::from_edges(list.n, list.pairs)?;
+        for edge in &list.hyperedges {
+            check_group(&matrix, edge)?;
+            // Sets of fewer than two distinct vertices conflict with nothing
+            if edge.iter().any(|v| *v != edge[0]) {
+                matrix.add_hyperedge(edge);
+            }
+        }
+        Ok(matrix)
+    }
+}
+
+/// Build a conflict matrix which keeps apart vertices sharing an attribute.
+///
+/// `attribute[i]` is some categorical value for vertex `i` (a department, a
+/// home city, ...). Vertices `i != j` conflict exactly when
+/// `attribute[i] == attribute[j]`. The diagonal is set, as usual.
+///
+/// # Panics
+///
+/// Panics if `attribute` does not have exactly `n` entries.
+pub fn conflicts_from_groups(n: usize, attribute: &[usize]) -> ConflictMatrix {
+    assert_eq!(attribute.len(), n, "Expected one attribute per vertex.");
+    let pairs = (0..n).flat_map(|i| (i + 1..n).map(move |j| (i, j)));
+    ConflictMatrix::empty(n).with_pairs(pairs.filter(|(i, j)| attribute[*i] == attribute[*j]))
+}
+
+/// A named set of vertices no two of which may ever share a group, such as
+/// siblings in a class or competitors at a networking event.
+///
+/// Rather than listing every pair among `k` members as a conflict by hand,
+/// the set is given once and expanded with [`add_separation_sets()`]. Its
+/// name is kept so that [diagnostics](crate::explain_missing_pair_separated)
+/// can tell these rules apart from conflicts recording earlier meetings.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SeparationSet {
+    /// Name of the rule, for reporting.
+    pub name: String,
+    /// The vertices to keep apart.
+    pub members: Vec<usize>,
+}
+
+/// Mark every pair within each of the `sets` as conflicting.
+///
+/// ```
+/// # use group_generator::{add_separation_sets, ConflictMatrix, SeparationSet};
+/// let siblings = SeparationSet { name: "siblings".into(), members: vec![0, 2, 3] };
+/// let mut conflicts = ConflictMatrix::empty(4);
+/// add_separation_sets(&mut conflicts, &[siblings]).unwrap();
+/// assert_eq!(conflicts.pairs().collect::<Vec<_>>(), vec![(0, 2), (0, 3), (2, 3)]);
+/// ```
+///
+/// All indices are checked before anything is modified, so on error the
+/// matrix is left untouched.
+pub fn add_separation_sets(conflicts: &mut ConflictMatrix, sets: &[SeparationSet]) -> Result<(), GroupError> {
+    for set in sets {
+        check_group(conflicts, &set.members)?;
+    }
+    for set in sets {
+        add_conflicts_between(conflicts, &set.members);
+    }
+    Ok(())
+}
+
+/// Mark every pair of vertices in `group` as conflicting, symmetrically, as
+/// happens when a round containing `group` is committed.
+///
+/// All indices are checked before anything is modified, so on error the
+/// matrix is left untouched.
+pub fn mark_group_conflicts(conflicts: &mut ConflictMatrix, group: &[usize]) -> Result<(), GroupError> {
+    check_group(conflicts, group)?;
+    add_conflicts_between(conflicts, group);
+    Ok(())
+}
+
+/// Clear every pair of vertices in `group`, undoing
+/// [`mark_group_conflicts()`]. The diagonal is left untouched.
+///
+/// All indices are checked before anything is modified, so on error the
+/// matrix is left untouched.
+pub fn unmark_group_conflicts(conflicts: &mut ConflictMatrix, group: &[usize]) -> Result<(), GroupError> {
+    check_group(conflicts, group)?;
+    remove_conflicts_between(conflicts, group);
+    Ok(())
+}
+
+fn check_group(conflicts: &ConflictMatrix, group: &[usize]) -> Result<(), GroupError> {
+    let n = conflicts.len();
+    match group.iter().find(|v| **v >= n) {
+        Some(&vertex) => Err(GroupError::VertexOutOfRange { vertex, n }),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn same_attribute_conflicts() {
+        let conflicts = conflicts_from_groups(4, &[0, 0, 1, 1]);
+        let expected = vec![
+            bitvec![1, 1, 0, 0],
+            bitvec![1, 1, 0, 0],
+            bitvec![0, 0, 1, 1],
+            bitvec![0, 0, 1, 1],
+        ];
+        assert_eq!(Vec::from(conflicts), expected);
+    }
+
+    #[test]
+    fn mark_and_unmark_group() {
+        let mut conflicts = crate::tests::diagonal(4);
+        mark_group_conflicts(&mut conflicts, &[0, 2, 3]).unwrap();
+        assert!(conflicts.is_conflicted(0, 2) && conflicts.is_conflicted(2, 0) && conflicts.is_conflicted(3, 2));
+        assert!(!conflicts.is_conflicted(0, 1));
+        unmark_group_conflicts(&mut conflicts, &[0, 2, 3]).unwrap();
+        assert_eq!(conflicts, crate::tests::diagonal(4));
+    }
+
+    #[test]
+    fn separation_sets_expand_to_pairs() {
+        let sets = [
+            SeparationSet { name: "a".into(), members: vec![0, 1, 2] },
+            SeparationSet { name: "b".into(), members: vec![3, 1] },
+        ];
+        let mut conflicts = crate::tests::diagonal(4);
+        add_separation_sets(&mut conflicts, &sets).unwrap();
+        assert_eq!(conflicts.pairs().collect::<Vec<_>>(), vec![(0, 1), (0, 2), (1, 2), (1, 3)]);
+        let bad = [sets[0].clone(), SeparationSet { name: "c".into(), members: vec![4] }];
+        let mut conflicts = crate::tests::diagonal(4);
+        assert_eq!(add_separation_sets(&mut conflicts, &bad), Err(GroupError::VertexOutOfRange { vertex: 4, n: 4 }));
+        assert_eq!(conflicts, crate::tests::diagonal(4));
+    }
+
+    #[test]
+    fn out_of_range_group_is_rejected() {
+        let mut conflicts = crate::tests::diagonal(3);
+        let err = mark_group_conflicts(&mut conflicts, &[0, 1, 5]);
+        assert_eq!(err, Err(GroupError::VertexOutOfRange { vertex: 5, n: 3 }));
+        assert_eq!(conflicts, crate::tests::diagonal(3));
+        assert!(unmark_group_conflicts(&mut conflicts, &[3]).is_err());
+    }
+
+    #[test]
+    fn matrix_stays_symmetric() {
+        let mut conflicts = ConflictMatrix::empty(4).with_pairs([(0, 1), (3, 0)]);
+        assert!(conflicts.is_conflicted(1, 0) && conflicts.is_conflicted(0, 3));
+        assert_eq!((conflicts.degree(0), conflicts.degree(2)), (2, 0));
+        assert_eq!(conflicts.pairs().collect::<Vec<_>>(), vec![(0, 1), (0, 3)]);
+        conflicts.remove_conflict(1, 0);
+        conflicts.remove_conflict(2, 2);
+        assert!(!conflicts.is_conflicted(0, 1) && conflicts.is_conflicted(2, 2));
+        assert_eq!(conflicts.degree(0), 1);
+    }
+
+    #[test]
+    fn packed_entries_are_independent() {
+        let n = 5;
+        let mut conflicts = ConflictMatrix::empty(n);
+        for i in 0..n {
+            for j in i + 1..n {
+                conflicts.add_conflict(j, i);
+                let set: Vec<(usize, usize)> = conflicts.pairs().collect();
+                assert_eq!(set, vec![(i, j)]);
+                assert_eq!((conflicts.degree(i), conflicts.degree(j)), (1, 1));
+                conflicts.remove_conflict(i, j);
+            }
+        }
+        assert_eq!(conflicts, ConflictMatrix::empty(n));
+        assert_eq!(ConflictMatrix::empty(1).pairs().count(), 0);
+    }
+
+    #[test]
+    fn hyperedges_need_every_member() {
+        let mut conflicts = ConflictMatrix::empty(5);
+        conflicts.add_hyperedge(&[3, 1, 2, 1]);
+        conflicts.add_hyperedge(&[1, 2, 3]);
+        conflicts.add_hyperedge(&[4, 0]);
+        assert_eq!(conflicts.hyperedges(), &[vec![1, 2, 3]]);
+        assert!(conflicts.is_conflicted(0, 4));
+        assert!(conflicts.is_group_conflicted(&[3, 2, 1]) && conflicts.is_group_conflicted(&[0, 4]));
+        assert!(!conflicts.is_group_conflicted(&[0, 1, 2]));
+        assert!(conflicts.completes_hyperedge(&[1usize, 2], 3) && !conflicts.completes_hyperedge(&[1usize], 3));
+        assert_ne!(conflicts, ConflictMatrix::empty(5).with_pairs([(0, 4)]));
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn out_of_range_entry_panics() {
+        ConflictMatrix::empty(3).is_conflicted(0, 3);
+    }
+
+    #[test]
+    fn edges_and_adjacency_lists_agree() {
+        let expected = ConflictMatrix::empty(4).with_pairs([(0, 1), (1, 3)]);
+        assert_eq!(ConflictMatrix::from_edges(4, [(1, 0), (3, 1)]), Ok(expected.clone()));
+        let adjacency = vec![vec![1], vec![], vec![], vec![1]];
+        assert_eq!(ConflictMatrix::from_adjacency_list(&adjacency), Ok(expected));
+        let err = ConflictMatrix::from_edges(3, [(0, 1), (2, 3)]);
+        assert_eq!(err, Err(GroupError::VertexOutOfRange { vertex: 3, n: 3 }));
+        assert!(ConflictMatrix::from_adjacency_list(&[[1], [2]]).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let conflicts = ConflictMatrix::empty(4).with_pairs([(0, 1), (3, 2)]);
+        let json = serde_json::to_string(&conflicts).unwrap();
+        assert_eq!(json, r#"{"n":4,"pairs":[[0,1],[2,3]]}"#);
+        assert_eq!(serde_json::from_str::<ConflictMatrix>(&json).unwrap(), conflicts);
+        assert!(serde_json::from_str::<ConflictMatrix>(r#"{"n":2,"pairs":[[0,2]]}"#).is_err());
+        let mut hyper = conflicts.clone();
+        hyper.add_hyperedge(&[0, 2, 3]);
+        let json = serde_json::to_string(&hyper).unwrap();
+        assert_eq!(json, r#"{"n":4,"pairs":[[0,1],[2,3]],"hyperedges":[[0,2,3]]}"#);
+        assert_eq!(serde_json::from_str::<ConflictMatrix>(&json).unwrap(), hyper);
+        assert!(serde_json::from_str::<ConflictMatrix>(r#"{"n":2,"pairs":[],"hyperedges":[[0,1,2]]}"#).is_err());
+    }
+
+    #[test]
+    fn raw_rows_are_checked() {
+        let rows = vec![bitvec![0, 1], bitvec![1, 0]];
+        let conflicts = ConflictMatrix::try_from(rows).unwrap();
+        assert_eq!(conflicts, ConflictMatrix::empty(2).with_pairs([(0, 1)]));
+        let ragged = vec![bitvec![1, 0], bitvec![1]];
+        let err = ConflictMatrix::try_from(ragged);
+        assert_eq!(err, Err(GroupError::DimensionMismatch { row: 1, len: 1, n: 2 }));
+        let asymmetric = vec![bitvec![1, 1], bitvec![0, 1]];
+        assert_eq!(ConflictMatrix::try_from(asymmetric), Err(GroupError::AsymmetricConflict { i: 0, j: 1 }));
+    }
+
+    #[test]
+    fn distinct_attributes_leave_diagonal() {
+        let conflicts = conflicts_from_groups(3, &[2, 0, 1]);
+        assert_eq!(conflicts, crate::tests::diagonal(3));
+    }
+}
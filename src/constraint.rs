@@ -0,0 +1,325 @@
+//! # Custom Constraints
+//!
+//! Extension points for rules the crate does not provide itself. They are
+//! checked inside the search, so downstream code can add its own rules
+//! without forking the crate or filtering enormous result sets afterwards.
+use alloc::vec;
+use alloc::vec::Vec;
+use core::ops::ControlFlow;
+
+use bitvec::prelude::*;
+
+use crate::{
+    check_problem, commit_round, explore_rounds, group_sizes, maximal_assignments, potential_groups_where, rollback_round, rounds_excluding_where,
+    single_assignment, singletons, visit_maximal_filtered, with_member, ConflictMatrix, RawGroup, Slot,
+};
+
+/// A group-level rule, checked incrementally while groups are built.
+///
+/// Groups are built one member at a time in ascending order, and
+/// [`allows()`](GroupConstraint::allows) is asked before each member is
+/// added. Rejecting a candidate abandons every group which would extend the
+/// members so far with it, so rules which can tell early that a group will
+/// fail prune the search the most. Rules which need the whole group can
+/// check it in [`accepts()`](GroupConstraint::accepts) instead.
+///
+/// Any `Fn(&[usize], usize) -> bool` closure is a constraint:
+///
+/// ```
+/// # use group_generator::{make_assignments_with_constraint, ConflictMatrix};
+/// // At most one of the first two vertices per group
+/// let spread = |partial: &[usize], candidate: usize| candidate >= 2 || partial.iter().all(|v| *v >= 2);
+/// let sols = make_assignments_with_constraint(&mut ConflictMatrix::empty(4), 2, &spread);
+/// assert!(sols.iter().flatten().flatten().all(|g| g.iter().filter(|v| **v < 2).count() <= 1));
+/// ```
+pub trait GroupConstraint {
+    /// Whether `candidate` may join the members chosen so far, `partial`,
+    /// which are all below `candidate`.
+    fn allows(&self, partial: &[usize], candidate: usize) -> bool;
+
+    /// Whether a complete `group`, with members ascending, is acceptable.
+    /// Every group is by default.
+    fn accepts(&self, group: &[usize]) -> bool {
+        let _ = group;
+        true
+    }
+}
+
+impl<F: Fn(&[usize], usize) -> bool> GroupConstraint for F {
+    fn allows(&self, partial: &[usize], candidate: usize) -> bool {
+        self(partial, candidate)
+    }
+}
+
+/// [`potential_groups()`](crate::potential_groups), only building the groups
+/// `constraint` allows and accepts.
+pub fn potential_groups_with(
+    conflicts: &mut ConflictMatrix,
+    k: usize,
+    skip: &BitVec,
+    constraint: &(impl GroupConstraint + ?Sized),
+) -> Vec<RawGroup> {
+    let slot = Slot { round: 0, index: 0, size: k };
+    potential_groups_where(conflicts, k, skip, |partial, candidate| constraint_allows(constraint, slot, partial, candidate))
+}
+
+/// Generate the *maximum-round* assignments (as in
+/// [`make_assignments()`](crate::make_assignments)) in which every group
+/// meets `constraint`.
+///
+/// # Panics
+///
+/// Panics under the same conditions as
+/// [`make_assignments()`](crate::make_assignments).
+pub fn make_assignments_with_constraint(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    constraint: &(impl GroupConstraint + ?Sized),
+) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut sols = Vec::new();
+    let allows = |slot, partial: &[usize], candidate| constraint_allows(constraint, slot, partial, candidate);
+    visit_maximal_filtered::<()>(conflicts, &[group_sizes], allows, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    });
+    sols
+}
+
+/// A round-level rule, checked incrementally while rounds are built.
+///
+/// Rounds are built one group at a time in slot order, and
+/// [`allows()`](RoundConstraint::allows) is asked before each group is
+/// added. Rejecting a group abandons every round which would extend the
+/// groups so far with it. Rules which need the whole round can check it in
+/// [`accepts()`](RoundConstraint::accepts) instead.
+///
+/// Any `Fn(&[Vec<usize>], &[usize]) -> bool` closure is a constraint:
+///
+/// ```
+/// # use group_generator::{make_assignments_with_round_constraint, ConflictMatrix};
+/// // Vertex 0's group always comes first
+/// let zero_first = |partial: &[Vec<usize>], group: &[usize]| !partial.is_empty() || group.contains(&0);
+/// let sols = make_assignments_with_round_constraint(&mut ConflictMatrix::empty(6), 3, &zero_first);
+/// assert!(sols.iter().flatten().all(|round| round[0].contains(&0)));
+/// ```
+pub trait RoundConstraint {
+    /// Whether `group` may follow the groups chosen so far, `partial`.
+    fn allows(&self, partial: &[RawGroup], group: &[usize]) -> bool;
+
+    /// Whether a complete `round` is acceptable. Every round is by default.
+    fn accepts(&self, round: &[RawGroup]) -> bool {
+        let _ = round;
+        true
+    }
+}
+
+impl<F: Fn(&[RawGroup], &[usize]) -> bool> RoundConstraint for F {
+    fn allows(&self, partial: &[RawGroup], group: &[usize]) -> bool {
+        self(partial, group)
+    }
+}
+
+/// [`single_assignment()`](crate::single_assignment), only building the
+/// rounds `constraint` allows and accepts.
+pub fn single_assignment_with(
+    conflicts: &mut ConflictMatrix,
+    group_sizes: &[usize],
+    constraint: &(impl RoundConstraint + ?Sized),
+) -> Vec<Vec<RawGroup>> {
+    let keep = |partial: &[RawGroup], group: &[usize]| round_constraint_allows(constraint, group_sizes.len(), partial, group);
+    rounds_excluding_where(conflicts, group_sizes, bitvec![0; conflicts.len()], |_, _, _| true, keep)
+}
+
+/// Generate all assignments reaching the most rounds possible (as in
+/// [`make_assignments()`](crate::make_assignments)) in which every round
+/// meets `constraint`.
+///
+/// A round-level rule can rule out the rounds the usual bounds count on, so
+/// unlike [`make_assignments()`](crate::make_assignments) this searches the
+/// whole tree without pruning by bounds.
+///
+/// # Panics
+///
+/// Panics under the same conditions as
+/// [`make_assignments()`](crate::make_assignments).
+pub fn make_assignments_with_round_constraint(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    constraint: &(impl RoundConstraint + ?Sized),
+) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    let n = conflicts.len();
+    if min_group_size == 1 {
+        let round = singletons(n);
+        let allowed = (0..n).all(|i| round_constraint_allows(constraint, n, &round[..i], &round[i]));
+        return vec![if allowed { vec![round] } else { vec![] }];
+    }
+    let group_sizes = group_sizes(n, min_group_size);
+    let next_rounds = |conflicts: &mut ConflictMatrix| single_assignment_with(conflicts, &group_sizes, constraint);
+    maximal_assignments(conflicts, next_rounds, commit_round, rollback_round)
+}
+
+/// Generate the assignments reaching the most rounds possible (as in
+/// [`make_assignments()`](crate::make_assignments)) among those whose every
+/// prefix `keep` accepts.
+///
+/// `keep` is asked about the partial schedule each time a round is added to
+/// it, and returning `false` backtracks out of that round, so no schedule
+/// extending it is explored. This is an escape hatch for rules on whole
+/// schedules which the crate does not model.
+///
+/// ```
+/// # use group_generator::{make_assignments_pruned, ConflictMatrix};
+/// // Vertices 0 and 1 may not meet in the first round
+/// let keep = |schedule: &[Vec<Vec<usize>>]| !schedule[0].contains(&vec![0, 1]);
+/// let sols = make_assignments_pruned(&mut ConflictMatrix::empty(4), 2, keep);
+/// assert!(sols.iter().all(|a| !a[0].contains(&vec![0, 1])));
+/// ```
+///
+/// # Panics
+///
+/// Panics under the same conditions as
+/// [`make_assignments()`](crate::make_assignments).
+pub fn make_assignments_pruned(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    keep: impl Fn(&[Vec<RawGroup>]) -> bool,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    let n = conflicts.len();
+    if min_group_size == 1 {
+        let schedule = vec![singletons(n)];
+        return vec![if keep(&schedule) { schedule } else { vec![] }];
+    }
+    let group_sizes = group_sizes(n, min_group_size);
+    let mut curr = vec![];
+    let next_rounds = |conflicts: &mut ConflictMatrix, prefix: &[Vec<RawGroup>]| {
+        curr.clear();
+        curr.extend_from_slice(prefix);
+        let mut rounds = single_assignment(conflicts, &group_sizes);
+        rounds.retain(|round| {
+            curr.push(round.clone());
+            let kept = keep(&curr);
+            curr.pop();
+            kept
+        });
+        rounds
+    };
+    let mut sols = vec![];
+    let mut best = 0;
+    explore_rounds(conflicts, next_rounds, commit_round, rollback_round, |schedule| {
+        if schedule.len() >= best {
+            if schedule.len() > best {
+                sols.clear();
+            }
+            sols.push(schedule.to_vec());
+            best = schedule.len();
+        }
+        ControlFlow::Continue(())
+    });
+    sols
+}
+
+/// Whether `constraint` lets `group` follow `partial` in a round of
+/// `num_groups`, checking the whole round once the group completes it.
+fn round_constraint_allows(constraint: &(impl RoundConstraint + ?Sized), num_groups: usize, partial: &[RawGroup], group: &[usize]) -> bool {
+    if !constraint.allows(partial, group) {
+        return false;
+    }
+    if partial.len() + 1 < num_groups {
+        return true;
+    }
+    let mut round = partial.to_vec();
+    round.push(group.to_vec());
+    constraint.accepts(&round)
+}
+
+/// Whether `constraint` lets `candidate` join `partial` in `slot`, checking
+/// the whole group once the candidate completes it.
+pub(crate) fn constraint_allows(constraint: &(impl GroupConstraint + ?Sized), slot: Slot, partial: &[usize], candidate: usize) -> bool {
+    constraint.allows(partial, candidate) && (!slot.completed_by(partial) || constraint.accepts(&with_member(partial, candidate)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::make_assignments;
+    use crate::tests::diagonal;
+
+    /// Groups whose member indices sum to a multiple of three.
+    struct SumOfThrees;
+
+    impl GroupConstraint for SumOfThrees {
+        fn allows(&self, _: &[usize], _: usize) -> bool {
+            true
+        }
+
+        fn accepts(&self, group: &[usize]) -> bool {
+            group.iter().sum::<usize>() % 3 == 0
+        }
+    }
+
+    #[test]
+    fn constraint_filters_groups() {
+        let res = make_assignments_with_constraint(&mut diagonal(6), 3, &SumOfThrees);
+        let expected: Vec<_> = make_assignments(&mut diagonal(6), 3)
+            .into_iter()
+            .filter(|a| a.iter().flatten().all(|g| SumOfThrees.accepts(g)))
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(res, expected);
+        let groups = potential_groups_with(&mut diagonal(4), 2, &bitvec![0; 4], &SumOfThrees);
+        assert_eq!(groups, vec![vec![0, 3], vec![1, 2]]);
+    }
+
+    /// Rounds listing their groups by least member.
+    struct Ordered;
+
+    impl RoundConstraint for Ordered {
+        fn allows(&self, _: &[RawGroup], _: &[usize]) -> bool {
+            true
+        }
+
+        fn accepts(&self, round: &[RawGroup]) -> bool {
+            round.windows(2).all(|w| w[0][0] < w[1][0])
+        }
+    }
+
+    #[test]
+    fn round_constraints_filter_rounds() {
+        let res = make_assignments_with_round_constraint(&mut diagonal(5), 2, &Ordered);
+        let expected: Vec<_> = make_assignments(&mut diagonal(5), 2)
+            .into_iter()
+            .filter(|a| a.iter().all(|round| Ordered.accepts(round)))
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(res, expected);
+        let three_first = |partial: &[RawGroup], g: &[usize]| partial.is_empty() == g.contains(&3);
+        let rounds = single_assignment_with(&mut diagonal(4), &[2, 2], &three_first);
+        assert_eq!(rounds, vec![vec![vec![0, 3], vec![1, 2]], vec![vec![1, 3], vec![0, 2]], vec![vec![2, 3], vec![0, 1]]]);
+        let none = make_assignments_with_round_constraint(&mut diagonal(3), 1, &|_: &[RawGroup], g: &[usize]| g != [1]);
+        assert!(none.len() == 1 && none[0].is_empty());
+    }
+
+    #[test]
+    fn pruned_schedules_backtrack() {
+        let keep = |schedule: &[Vec<RawGroup>]| !schedule[0].contains(&vec![0, 1]);
+        let expected: Vec<_> = make_assignments(&mut diagonal(4), 2).into_iter().filter(|a| keep(a)).collect();
+        assert_eq!(make_assignments_pruned(&mut diagonal(4), 2, keep), expected);
+        let short = make_assignments_pruned(&mut diagonal(4), 2, |schedule| schedule.len() <= 2);
+        let mut prefixes: Vec<_> = make_assignments(&mut diagonal(4), 2).into_iter().map(|a| a[..2].to_vec()).collect();
+        prefixes.dedup();
+        assert_eq!(short, prefixes);
+        let none = make_assignments_pruned(&mut diagonal(3), 1, |_| false);
+        assert!(none.len() == 1 && none[0].is_empty());
+    }
+
+    #[test]
+    fn closures_prune_partial_groups() {
+        let no_zero_with_one = |partial: &[usize], candidate: usize| !(candidate == 1 && partial.contains(&0));
+        let res = make_assignments_with_constraint(&mut diagonal(4), 2, &no_zero_with_one);
+        assert_eq!(res, make_assignments(&mut diagonal(4).with_pairs([(0, 1)]), 2));
+    }
+}
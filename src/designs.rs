@@ -0,0 +1,155 @@
+//! # Constructive Designs
+//!
+//! Schedules built directly from known combinatorial designs rather than by
+//! search. These are instant and provably optimal, but only exist for
+//! specific sizes.
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::RawGroup;
+
+/// Build the resolvable design of the affine plane of order `g`: `g + 1`
+/// rounds of `g` groups of `g`, on `n = g²` vertices.
+///
+/// Vertex `x * g + y` is the point `(x, y)`. One round groups the points by
+/// `x`, and for each slope `m` in `0..g` one round groups them by the line
+/// `y = m * x + b`, with arithmetic in the finite field of order `g`. These
+/// are the rows, and the cells of each symbol in the Latin squares
+/// `L_m(x, b) = m * x + b`, which are mutually orthogonal over a field.
+///
+/// Every pair of vertices shares a group exactly once, which is the most any
+/// schedule of groups of `g` can achieve: each vertex meets `g - 1` new
+/// partners per round and there are only `g² - 1` to meet.
+///
+/// # Size Constraints
+///
+/// The construction requires `g` to be a prime power, so that a field of
+/// order `g` exists. For prime `g` this is arithmetic mod `g`; otherwise field
+/// elements are polynomials over the integers mod `p` (digits of `x` and `y`
+/// in base `p`). For any other `g` (including 0 and 1) this returns `None`,
+/// and a schedule has to come from the search instead, e.g.
+/// [`make_first_assignment()`](crate::make_first_assignment) on `g²` vertices
+/// with minimum group size `g`. That quickly becomes intractable: there is no
+/// affine plane of order 6, so the search must rule out every deeper branch.
+///
+/// Groups are ordered by least member, with members ascending.
+///
+/// ```
+/// # use group_generator::{latin_square_schedule, validate_assignment};
+/// let schedule = latin_square_schedule(4).unwrap();
+/// assert!(validate_assignment(&schedule, 16, 4));
+/// assert_eq!(latin_square_schedule(6), None);
+/// ```
+pub fn latin_square_schedule(g: usize) -> Option<Vec<Vec<RawGroup>>> {
+    let (add, mul) = field_tables(g)?;
+    let columns: Vec<RawGroup> = (0..g).map(|x| (0..g).map(|y| x * g + y).collect()).collect();
+    let mut rounds = vec![columns];
+    for m in 0..g {
+        let lines: Vec<RawGroup> = (0..g)
+            .map(|b| (0..g).map(|x| x * g + add[mul[m][x]][b]).collect())
+            .collect();
+        rounds.push(lines);
+    }
+    Some(rounds)
+}
+
+/// A binary operation on `0..g`, as a table indexed by both operands.
+type OpTable = Vec<Vec<usize>>;
+
+/// Addition and multiplication tables of the field of order `g`, or `None`
+/// if `g` is not a prime power.
+///
+/// For `g = p^k`, element `a` is the polynomial whose coefficients are the
+/// base-`p` digits of `a` (least significant first), and products are reduced
+/// mod the first monic polynomial of degree `k` which leaves no zero divisors,
+/// i.e. the first irreducible one.
+fn field_tables(g: usize) -> Option<(OpTable, OpTable)> {
+    let p = (2..=g).find(|d| g.is_multiple_of(*d))?;
+    let mut k = 0;
+    let mut q = 1;
+    while q < g {
+        q *= p;
+        k += 1;
+    }
+    if q != g {
+        return None;
+    }
+    let digits = |a: usize| (0..k).scan(a, |rest, _| {
+        let digit = *rest % p;
+        *rest /= p;
+        Some(digit)
+    });
+    let number = |digits: &[usize]| digits.iter().rev().fold(0, |acc, d| acc * p + d);
+    let add: OpTable = (0..g)
+        .map(|a| (0..g).map(|b| number(&digits(a).zip(digits(b)).map(|(x, y)| (x + y) % p).collect::<Vec<_>>())).collect())
+        .collect();
+    for low in 0..g {
+        // The modulus is x^k plus the polynomial `low`
+        let modulus: Vec<usize> = digits(low).collect();
+        let mul_mod = |a: usize, b: usize| {
+            let mut product = vec![0; 2 * k];
+            for (i, x) in digits(a).enumerate() {
+                for (j, y) in digits(b).enumerate() {
+                    product[i + j] = (product[i + j] + x * y) % p;
+                }
+            }
+            for i in (k..2 * k).rev() {
+                let c = product[i];
+                for (j, m) in modulus.iter().enumerate() {
+                    product[i - k + j] = (product[i - k + j] + (p - c) * m) % p;
+                }
+                product[i] = 0;
+            }
+            number(&product[..k])
+        };
+        let mul: OpTable = (0..g).map(|a| (0..g).map(|b| mul_mod(a, b)).collect()).collect();
+        if mul.iter().skip(1).all(|row| row.iter().skip(1).all(|c| *c != 0)) {
+            return Some((add, mul));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{distinct_partners, validate_assignment};
+
+    #[test]
+    fn prime_orders_pair_everyone_once() {
+        for g in [2, 3, 5, 7] {
+            let n = g * g;
+            let schedule = latin_square_schedule(g).unwrap();
+            assert_eq!(schedule.len(), g + 1);
+            assert!(schedule.iter().flatten().all(|group| group.len() == g));
+            assert!(validate_assignment(&schedule, n, g));
+            assert!(distinct_partners(&schedule, n).iter().all(|p| *p == n - 1));
+        }
+    }
+
+    #[test]
+    fn groups_ordered_by_least_member() {
+        let schedule = latin_square_schedule(3).unwrap();
+        assert_eq!(schedule[0], vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7, 8]]);
+        assert_eq!(schedule[1], vec![vec![0, 3, 6], vec![1, 4, 7], vec![2, 5, 8]]);
+        assert_eq!(schedule[2], vec![vec![0, 4, 8], vec![1, 5, 6], vec![2, 3, 7]]);
+    }
+
+    #[test]
+    fn prime_power_orders_use_field_arithmetic() {
+        for g in [4, 8, 9] {
+            let n = g * g;
+            let schedule = latin_square_schedule(g).unwrap();
+            assert_eq!(schedule.len(), g + 1);
+            assert!(validate_assignment(&schedule, n, g));
+            assert!(distinct_partners(&schedule, n).iter().all(|p| *p == n - 1));
+        }
+    }
+
+    #[test]
+    fn other_orders_have_no_design() {
+        for g in [0, 1, 6, 10, 12, 15] {
+            assert_eq!(latin_square_schedule(g), None, "Built a design of order {g}");
+        }
+    }
+}
@@ -0,0 +1,180 @@
+//! # Diagnostics
+//!
+//! Explanations for why the optimal schedules look the way they do.
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{
+    add_conflicts_between, canonical_round, check_problem, group_sizes, max_rounds, remove_conflicts_between, rounds_remaining_after,
+    single_assignment, ConflictMatrix, SeparationSet,
+};
+
+/// Why a pair of vertices does or does not appear together in the maximal
+/// assignments, as reported by [`explain_missing_pair()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PairExplanation {
+    /// The pair is directly in conflict, so it can never share a group.
+    DirectConflict,
+    /// Both vertices belong to the [`SeparationSet`] at index `set`, so they
+    /// can never share a group.
+    Separated { set: usize },
+    /// No round can place the pair in the same group at all, given the
+    /// planned group sizes and the other conflicts.
+    NoRoundContainsPair,
+    /// Grouping the pair is possible, but every schedule doing so reaches at
+    /// most `with_pair` rounds, fewer than the `max_rounds` achievable
+    /// otherwise, so no maximal assignment contains it.
+    CostsRounds { with_pair: usize, max_rounds: usize },
+    /// Some maximal assignment does group the pair.
+    NotExcluded,
+}
+
+/// Explain why vertices `a` and `b` are never grouped together in any maximal
+/// assignment (or report that they sometimes are).
+///
+/// The reasons are checked in order:
+///
+/// 1. Whether `a` and `b` are in conflict.
+/// 2. Whether any valid round has them in the same group.
+/// 3. The most rounds achievable when forcing them together in the first
+///    round, found by committing each such round and calling
+///    [`max_rounds()`] on the rest, compared to the global [`max_rounds()`].
+///
+/// Since the order of rounds never affects how many can be formed, forcing
+/// the pair into the first round covers schedules meeting them in any round.
+/// `conflicts` is left exactly as it was.
+///
+/// # Cost
+///
+/// One [`max_rounds()`] search, plus one per distinct round containing
+/// the pair.
+///
+/// # Panics
+///
+/// Panics if `a` or `b` is out of range or `a == b`, or under the same
+/// conditions as [`make_assignments()`](crate::make_assignments).
+pub fn explain_missing_pair(conflicts: &mut ConflictMatrix, min_group_size: usize, a: usize, b: usize) -> PairExplanation {
+    check_problem(conflicts, min_group_size);
+    let n = conflicts.len();
+    assert!(a < n && b < n, "Pair ({a}, {b}) is out of range for {n} vertices.");
+    assert!(a != b, "A vertex cannot be paired with itself.");
+    if conflicts.is_conflicted(a, b) {
+        return PairExplanation::DirectConflict;
+    }
+    if min_group_size == 1 {
+        return PairExplanation::NoRoundContainsPair;
+    }
+
+    let group_sizes = group_sizes(n, min_group_size);
+    let mut rounds: Vec<_> = single_assignment(conflicts, &group_sizes)
+        .into_iter()
+        .filter(|round| round.iter().any(|g| g.contains(&a) && g.contains(&b)))
+        .map(|round| canonical_round(&round))
+        .collect();
+    rounds.sort_unstable();
+    rounds.dedup();
+    if rounds.is_empty() {
+        return PairExplanation::NoRoundContainsPair;
+    }
+
+    let max_rounds = max_rounds(conflicts, min_group_size);
+    let mut with_pair = 0;
+    for round in &rounds {
+        with_pair = with_pair.max(1 + rounds_remaining_after(conflicts, min_group_size, round));
+        if with_pair == max_rounds {
+            return PairExplanation::NotExcluded;
+        }
+    }
+    PairExplanation::CostsRounds { with_pair, max_rounds }
+}
+
+/// [`explain_missing_pair()`] where the `sets` are kept apart on top of
+/// `conflicts`, reporting a pair split by one of them as
+/// [`Separated`](PairExplanation::Separated) rather than as a
+/// [`DirectConflict`](PairExplanation::DirectConflict).
+///
+/// `conflicts` should then hold the other conflicts only, such as the pairs
+/// which met in earlier rounds. The pairs of the `sets` are added for the
+/// search and removed again, leaving `conflicts` exactly as it was.
+///
+/// # Panics
+///
+/// Panics if a set refers to a vertex out of range, or under the same
+/// conditions as [`explain_missing_pair()`].
+pub fn explain_missing_pair_separated(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    sets: &[SeparationSet],
+    a: usize,
+    b: usize,
+) -> PairExplanation {
+    if let Some(set) = sets.iter().position(|s| s.members.contains(&a) && s.members.contains(&b)) {
+        check_problem(conflicts, min_group_size);
+        assert!(a != b, "A vertex cannot be paired with itself.");
+        return PairExplanation::Separated { set };
+    }
+    // Only the pairs not already conflicting, so removing them restores `conflicts`
+    let mut added = vec![];
+    for set in sets {
+        for (i, u) in set.members.iter().enumerate() {
+            for v in &set.members[..i] {
+                if !conflicts.is_conflicted(*u, *v) {
+                    added.push([*u, *v]);
+                    add_conflicts_between(conflicts, &[*u, *v]);
+                }
+            }
+        }
+    }
+    let explanation = explain_missing_pair(conflicts, min_group_size, a, b);
+    for pair in &added {
+        remove_conflicts_between(conflicts, pair);
+    }
+    explanation
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::conflicts_from_groups;
+    use crate::tests::diagonal;
+
+    #[test]
+    fn pair_excluded_for_losing_a_round() {
+        // Pairing 4 with 5 forces 0-3 into one of the two matchings avoiding
+        // the conflicts, which leaves too few partners for four rounds
+        let mut conflicts = diagonal(6);
+        for (u, v) in [(0, 1), (2, 3)] {
+            conflicts.add_conflict(u, v);
+        }
+        let original = conflicts.clone();
+        let explanation = explain_missing_pair(&mut conflicts, 2, 4, 5);
+        assert_eq!(explanation, PairExplanation::CostsRounds { with_pair: 3, max_rounds: 4 });
+        assert_eq!(conflicts, original);
+        assert_eq!(explain_missing_pair(&mut conflicts, 2, 0, 1), PairExplanation::DirectConflict);
+    }
+
+    #[test]
+    fn separated_pairs_reported_apart() {
+        let sets = [SeparationSet { name: "siblings".into(), members: vec![0, 1, 2] }];
+        let mut conflicts = diagonal(6);
+        conflicts.add_conflict(3, 4);
+        let original = conflicts.clone();
+        assert_eq!(explain_missing_pair_separated(&mut conflicts, 2, &sets, 2, 0), PairExplanation::Separated { set: 0 });
+        assert_eq!(explain_missing_pair_separated(&mut conflicts, 2, &sets, 3, 4), PairExplanation::DirectConflict);
+        assert_eq!(explain_missing_pair_separated(&mut conflicts, 2, &sets, 0, 3), PairExplanation::NotExcluded);
+        assert_eq!(conflicts, original);
+    }
+
+    #[test]
+    fn grouped_and_impossible_pairs() {
+        let mut conflicts = conflicts_from_groups(6, &[0, 0, 1, 1, 2, 2]);
+        assert_eq!(explain_missing_pair(&mut conflicts, 3, 0, 2), PairExplanation::NotExcluded);
+        assert_eq!(explain_missing_pair(&mut diagonal(3), 1, 0, 2), PairExplanation::NoRoundContainsPair);
+        // Vertex 0 may only sit with 1, so 2 and 3 must always be together
+        let mut conflicts = diagonal(4);
+        for v in [2, 3] {
+            conflicts.add_conflict(0, v);
+        }
+        assert_eq!(explain_missing_pair(&mut conflicts, 2, 1, 2), PairExplanation::NoRoundContainsPair);
+    }
+}
@@ -0,0 +1,208 @@
+//! # Constraint DSL
+//!
+//! A small line-based format for describing a problem in a config file, so
+//! the solver can be driven without writing Rust.
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::SolverConfig;
+
+/// Error from [`parse_constraints()`], pointing at the offending line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// Parse the constraint DSL into a [`SolverConfig`].
+///
+/// Each line holds one directive followed by its integer arguments:
+///
+/// ```text
+/// # Blank lines and comments are ignored
+/// vertices 6
+/// min-size 2
+/// max-size 3
+/// groups 2
+/// conflict 1 2
+/// together 3 4
+/// anchor 0
+/// ```
+///
+/// | Directive        | Meaning                                             |
+/// |------------------|-----------------------------------------------------|
+/// | `vertices N`     | Number of vertices (else one past the largest used) |
+/// | `min-size K`     | Minimum group size (default 2)                      |
+/// | `max-size K`     | Maximum group size                                  |
+/// | `groups G`       | Exactly `G` groups per round                        |
+/// | `conflict I J`   | `I` and `J` may never share a group                 |
+/// | `together I J`   | `I` and `J` must share a group every round          |
+/// | `anchor V`       | `V` is anchored to the next fixed group slot        |
+///
+/// Errors report the 1-based line number of the offending directive.
+pub fn parse_constraints(s: &str) -> Result<SolverConfig, ParseError> {
+    let mut config = SolverConfig::default();
+    let mut declared_vertices = None;
+    let mut seen_min = false;
+    let mut max_line = 0;
+    // Largest vertex index mentioned, with the line it was mentioned on
+    let mut largest: Option<(usize, usize)> = None;
+
+    for (i, raw) in s.lines().enumerate() {
+        let line = i + 1;
+        let error = |message: String| ParseError { line, message };
+        let content = raw.split('#').next().unwrap_or_default().trim();
+        let mut words = content.split_whitespace();
+        let Some(directive) = words.next() else {
+            continue;
+        };
+        let args = words
+            .map(|w| w.parse::<usize>().map_err(|_| error(format!("expected a non-negative integer, found `{w}`"))))
+            .collect::<Result<Vec<usize>, ParseError>>()?;
+        let arity = match directive {
+            "vertices" | "min-size" | "max-size" | "groups" | "anchor" => 1,
+            "conflict" | "together" => 2,
+            _ => return Err(error(format!("unknown directive `{directive}`"))),
+        };
+        if args.len() != arity {
+            return Err(error(format!("`{directive}` takes {arity} argument(s), found {}", args.len())));
+        }
+        match directive {
+            "vertices" => {
+                if declared_vertices.replace(args[0]).is_some() {
+                    return Err(error("`vertices` given more than once".into()));
+                }
+            }
+            "min-size" => {
+                if core::mem::replace(&mut seen_min, true) {
+                    return Err(error("`min-size` given more than once".into()));
+                }
+                if args[0] == 0 {
+                    return Err(error("`min-size` must be at least 1".into()));
+                }
+                config.min_group_size = args[0];
+            }
+            "max-size" => {
+                if config.max_group_size.replace(args[0]).is_some() {
+                    return Err(error("`max-size` given more than once".into()));
+                }
+                max_line = line;
+            }
+            "groups" => {
+                if args[0] == 0 {
+                    return Err(error("`groups` must be at least 1".into()));
+                }
+                if config.num_groups.replace(args[0]).is_some() {
+                    return Err(error("`groups` given more than once".into()));
+                }
+            }
+            "conflict" | "together" => {
+                let (u, v) = (args[0], args[1]);
+                if u == v {
+                    return Err(error(format!("`{directive}` needs two different vertices")));
+                }
+                let pairs = if directive == "conflict" { &mut config.conflicts } else { &mut config.together };
+                pairs.push((u, v));
+            }
+            _ => config.anchors.push(args[0]),
+        }
+        if matches!(directive, "conflict" | "together" | "anchor") {
+            let most = args.iter().copied().max().unwrap_or_default();
+            if largest.is_none_or(|(v, _)| most > v) {
+                largest = Some((most, line));
+            }
+        }
+    }
+
+    config.vertices = match (declared_vertices, largest) {
+        (Some(n), Some((v, line))) if v >= n => {
+            return Err(ParseError {
+                line,
+                message: format!("vertex {v} is out of range for {n} vertices"),
+            })
+        }
+        (Some(n), _) => n,
+        (None, largest) => largest.map_or(0, |(v, _)| v + 1),
+    };
+    if let Some(max) = config.max_group_size {
+        if max < config.min_group_size {
+            return Err(ParseError {
+                line: max_line,
+                message: format!("`max-size` {max} is below `min-size` {}", config.min_group_size),
+            });
+        }
+    }
+    Ok(config)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_all_directives() {
+        let src = "
+            # Workshop setup
+            vertices 6
+            min-size 2
+            max-size 3
+            groups 2
+            conflict 1 2   # they argued
+            together 3 4
+            anchor 0
+        ";
+        let config = parse_constraints(src).unwrap();
+        let expected = SolverConfig {
+            vertices: 6,
+            conflicts: vec![(1, 2)],
+            together: vec![(3, 4)],
+            anchors: vec![0],
+            min_group_size: 2,
+            max_group_size: Some(3),
+            num_groups: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(config, expected);
+    }
+
+    #[test]
+    fn infers_vertex_count() {
+        let config = parse_constraints("conflict 0 4\nanchor 2").unwrap();
+        assert_eq!(config.vertices, 5);
+        assert_eq!(config.min_group_size, 2);
+        assert_eq!(parse_constraints("").unwrap(), SolverConfig::default());
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let cases = [
+            ("min-size 2\nfrobnicate 1", 2),
+            ("conflict 1", 1),
+            ("\n\nconflict 1 x", 3),
+            ("together 2 2", 1),
+            ("min-size 2\nmin-size 3", 2),
+            ("min-size 0", 1),
+            ("vertices 3\nconflict 0 1\nanchor 3", 3),
+            ("max-size 2\nmin-size 3", 1),
+            ("groups 0", 1),
+            ("groups 2\ngroups 3", 2),
+        ];
+        for (src, line) in cases {
+            let err = parse_constraints(src).unwrap_err();
+            assert_eq!(err.line, line, "Wrong line for {src:?}: {err}");
+        }
+        let err = parse_constraints("conflict 0 1\nconflict 1 x").unwrap_err();
+        assert_eq!(err.to_string(), "line 2: expected a non-negative integer, found `x`");
+    }
+}
@@ -0,0 +1,90 @@
+//! # Errors
+//!
+//! Error type for the fallible parts of the public API.
+use core::fmt;
+
+/// Errors reported for invalid input instead of panicking.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GroupError {
+    /// A vertex index is not below the number of vertices `n`.
+    VertexOutOfRange { vertex: usize, n: usize },
+    /// The problem has no vertices.
+    NoVertices,
+    /// The minimum group size is 0 or exceeds the number of vertices `n`.
+    InvalidMinGroupSize { min_group_size: usize, n: usize },
+    /// Row `row` of a raw conflict matrix has `len` entries instead of `n`.
+    DimensionMismatch { row: usize, len: usize, n: usize },
+    /// Raw conflict row `i` has entry `j` set, but row `j` does not have
+    /// entry `i` set.
+    AsymmetricConflict { i: usize, j: usize },
+    /// A round is not a conflict-free partition of the vertices into groups
+    /// of at least the minimum size.
+    InvalidRound,
+    /// No round of `n` vertices has every group between `min_group_size` and
+    /// `max_group_size` members.
+    NoFeasibleShape { n: usize, min_group_size: usize, max_group_size: usize },
+    /// `n` vertices cannot be split into `num_groups` groups of at least
+    /// `min_group_size`.
+    InvalidGroupCount { num_groups: usize, n: usize, min_group_size: usize },
+    /// An explicit list of group sizes is empty, has a group of 0, or does
+    /// not sum to the number of vertices `n`.
+    InvalidGroupShape { n: usize },
+    /// A group index is not below the number of groups per round.
+    GroupIndexOutOfRange { group: usize, num_groups: usize },
+    /// A list of per-vertex values has `len` entries instead of one for each
+    /// of the `n` vertices.
+    AttributeMismatch { len: usize, n: usize },
+    /// No choice of rooms can hold all `n` vertices in groups of at least
+    /// `min_group_size`, one group per room.
+    InsufficientRooms { n: usize, min_group_size: usize },
+    /// No assignment has `rounds` rounds, as the most possible is
+    /// `max_rounds`.
+    UnreachableRounds { rounds: usize, max_rounds: usize },
+}
+
+impl fmt::Display for GroupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::VertexOutOfRange { vertex, n } => {
+                write!(f, "Vertex {vertex} is out of range for {n} vertices.")
+            }
+            Self::NoVertices => write!(f, "Cannot make groups from 0 vertices."),
+            Self::InvalidMinGroupSize { min_group_size: 0, .. } => {
+                write!(f, "Minimum group size must be at least 1.")
+            }
+            Self::InvalidMinGroupSize { min_group_size, n } => {
+                write!(f, "Cannot require groups of {min_group_size} from {n} vertices.")
+            }
+            Self::DimensionMismatch { row, len, n } => {
+                write!(f, "Conflicts matrix must be N x N, but row {row} has {len} entries for N = {n}.")
+            }
+            Self::AsymmetricConflict { i, j } => {
+                write!(f, "Conflict ({i}, {j}) is set but ({j}, {i}) is not.")
+            }
+            Self::InvalidRound => write!(f, "Round is not a conflict-free partition into large enough groups."),
+            Self::NoFeasibleShape { n, min_group_size, max_group_size } => {
+                write!(f, "Cannot split {n} vertices into groups of {min_group_size} to {max_group_size}.")
+            }
+            Self::InvalidGroupCount { num_groups, n, min_group_size } => {
+                write!(f, "Cannot split {n} vertices into {num_groups} groups of at least {min_group_size}.")
+            }
+            Self::InvalidGroupShape { n } => {
+                write!(f, "Group sizes must be positive and sum to the {n} vertices.")
+            }
+            Self::GroupIndexOutOfRange { group, num_groups } => {
+                write!(f, "Group {group} is out of range for {num_groups} groups per round.")
+            }
+            Self::AttributeMismatch { len, n } => {
+                write!(f, "Expected a value for each of the {n} vertices, but got {len}.")
+            }
+            Self::InsufficientRooms { n, min_group_size } => {
+                write!(f, "Cannot fit {n} vertices into the rooms in groups of at least {min_group_size}.")
+            }
+            Self::UnreachableRounds { rounds, max_rounds } => {
+                write!(f, "Cannot make {rounds} rounds, as at most {max_rounds} are possible.")
+            }
+        }
+    }
+}
+
+impl core::error::Error for GroupError {}
@@ -0,0 +1,110 @@
+//! # Tabular Export
+//!
+//! Flattening of the nested assignment structure into one row per placement,
+//! for storing schedules in a relational table.
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::analysis::canonical_round;
+use crate::RawGroup;
+
+/// One placement of a vertex: the row `(round, group, vertex)` of a
+/// normalized schedule table.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScheduleRecord {
+    /// Index of the round, in schedule order.
+    pub round: usize,
+    /// Index of the group within its round, with groups ordered by least
+    /// member so the index does not depend on the solver's group order.
+    pub group: usize,
+    /// The vertex placed in the group.
+    pub vertex: usize,
+}
+
+/// Flatten `assignment` into one [`ScheduleRecord`] per placed vertex.
+///
+/// Records are ordered by round, then group, then vertex, which matches the
+/// order of a typical `INSERT`.
+pub fn to_records(assignment: &[Vec<RawGroup>]) -> Vec<ScheduleRecord> {
+    let mut records = vec![];
+    for (round, groups) in assignment.iter().enumerate() {
+        for (group, members) in canonical_round(groups).into_iter().enumerate() {
+            records.extend(members.into_iter().map(|vertex| ScheduleRecord { round, group, vertex }));
+        }
+    }
+    records
+}
+
+/// As [`to_records()`], with each record paired with the label of its vertex
+/// (`labels[vertex]`).
+///
+/// # Panics
+///
+/// Panics if any placed vertex has no label.
+pub fn to_labeled_records<'a, S: AsRef<str>>(
+    assignment: &[Vec<RawGroup>],
+    labels: &'a [S],
+) -> Vec<(ScheduleRecord, &'a str)> {
+    to_records(assignment)
+        .into_iter()
+        .map(|record| (record, labels[record.vertex].as_ref()))
+        .collect()
+}
+
+/// Rebuild an assignment from its records, in any order.
+///
+/// The result has every round in canonical form (see
+/// [`canonical_round()`](crate::canonical_round)), so
+/// `from_records(&to_records(a))` reproduces `a` up to the order of groups
+/// within rounds and of members within groups.
+pub fn from_records(records: &[ScheduleRecord]) -> Vec<Vec<RawGroup>> {
+    let mut assignment: Vec<Vec<RawGroup>> = vec![];
+    for record in records {
+        if assignment.len() <= record.round {
+            assignment.resize(record.round + 1, vec![]);
+        }
+        let round = &mut assignment[record.round];
+        if round.len() <= record.group {
+            round.resize(record.group + 1, vec![]);
+        }
+        round[record.group].push(record.vertex);
+    }
+    for round in &mut assignment {
+        for g in round {
+            g.sort_unstable();
+        }
+    }
+    assignment
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::complete_seven_two;
+
+    #[test]
+    fn records_round_trip() {
+        let n = 7;
+        for assignment in complete_seven_two().iter().take(50) {
+            let records = to_records(assignment);
+            assert_eq!(records.len(), assignment.len() * n);
+            assert!(records.windows(2).all(|w| w[0] < w[1]));
+            let canonical: Vec<Vec<RawGroup>> = assignment.iter().map(|round| canonical_round(round)).collect();
+            let mut shuffled = records.clone();
+            shuffled.reverse();
+            assert_eq!(from_records(&shuffled), canonical);
+        }
+    }
+
+    #[test]
+    fn groups_indexed_by_least_member() {
+        let assignment = vec![vec![vec![3, 1], vec![2, 0]]];
+        let records = to_records(&assignment);
+        assert_eq!(records[0], ScheduleRecord { round: 0, group: 0, vertex: 0 });
+        assert_eq!(records[2], ScheduleRecord { round: 0, group: 1, vertex: 1 });
+        let labels = ["ann", "bo", "cy", "di"];
+        let labeled = to_labeled_records(&assignment, &labels);
+        assert_eq!(labeled[3], (ScheduleRecord { round: 0, group: 1, vertex: 3 }, "di"));
+    }
+}
@@ -0,0 +1,185 @@
+//! # JSON Lines Serialization
+//!
+//! Assignments written one per line as nested JSON arrays, e.g.
+//! `[[[0,1],[2,3]],[[0,2],[1,3]]]`, so that arbitrarily many can be streamed
+//! to and from disk without holding them all in memory.
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::ops::ControlFlow;
+use std::path::Path;
+
+use crate::{make_assignments_with, ConflictMatrix, RawGroup};
+
+/// Write `assignment` as a single JSON line (including the trailing newline).
+pub fn write_assignment(writer: &mut impl Write, assignment: &[Vec<RawGroup>]) -> io::Result<()> {
+    write!(writer, "[")?;
+    for (r, round) in assignment.iter().enumerate() {
+        if r > 0 {
+            write!(writer, ",")?;
+        }
+        write!(writer, "[")?;
+        for (i, g) in round.iter().enumerate() {
+            if i > 0 {
+                write!(writer, ",")?;
+            }
+            write!(writer, "[")?;
+            for (j, v) in g.iter().enumerate() {
+                if j > 0 {
+                    write!(writer, ",")?;
+                }
+                write!(writer, "{v}")?;
+            }
+            write!(writer, "]")?;
+        }
+        write!(writer, "]")?;
+    }
+    writeln!(writer, "]")
+}
+
+/// Lazily read back assignments written by [`write_assignment()`], one per
+/// non-empty line.
+///
+/// Lines which are not a nested array of vertex indices produce an
+/// [`io::ErrorKind::InvalidData`] error.
+pub fn read_assignments(reader: impl BufRead) -> impl Iterator<Item = io::Result<Vec<Vec<RawGroup>>>> {
+    reader
+        .lines()
+        .filter(|line| !matches!(line, Ok(line) if line.trim().is_empty()))
+        .map(|line| parse_assignment(&line?).map_err(|msg| io::Error::new(io::ErrorKind::InvalidData, msg)))
+}
+
+pub(crate) fn parse_assignment(line: &str) -> Result<Vec<Vec<RawGroup>>, String> {
+    let mut assignment = vec![];
+    let mut round = vec![];
+    let mut group = vec![];
+    let mut num: Option<usize> = None;
+    let mut depth = 0;
+    let mut closed = false;
+    for c in line.chars().filter(|c| !c.is_whitespace()) {
+        if closed {
+            return Err(format!("Unexpected '{c}' after the assignment."));
+        }
+        match c {
+            '[' if depth < 3 => depth += 1,
+            ']' | ',' if depth > 0 => {
+                if let Some(v) = num.take() {
+                    group.push(v);
+                }
+                if c == ']' {
+                    match depth {
+                        3 => round.push(std::mem::take(&mut group)),
+                        2 => assignment.push(std::mem::take(&mut round)),
+                        _ => closed = true,
+                    }
+                    depth -= 1;
+                }
+            }
+            '0'..='9' if depth == 3 => {
+                let digit = c as usize - '0' as usize;
+                num = num
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|v| v.checked_add(digit))
+                    .map(Some)
+                    .ok_or("Vertex index overflows usize.")?;
+            }
+            _ => return Err(format!("Unexpected '{c}' at nesting depth {depth}.")),
+        }
+    }
+    if !closed {
+        return Err("Unterminated assignment.".to_string());
+    }
+    Ok(assignment)
+}
+
+/// Stream every assignment [`make_assignments()`](crate::make_assignments)
+/// would return to the file at `path` (created or truncated) as JSON lines,
+/// returning the number written and the maximum number of rounds.
+///
+/// Only one assignment is held in memory at a time, so this can enumerate
+/// solution sets far too large to collect. Read them back with
+/// [`read_assignments()`].
+///
+/// # Two Passes
+///
+/// Whether an assignment is maximal is only known once the whole search tree
+/// has been explored, so the search runs twice: once to find
+/// [`max_rounds()`](crate::max_rounds), and again to write out exactly the
+/// assignments reaching it. The first pass is a branch-and-bound search, so
+/// it is usually much cheaper than the second.
+///
+/// # Errors
+///
+/// Returns the first error from creating or writing the file. The search
+/// stops at the first error, and the file may be left partially written.
+///
+/// # Panics
+///
+/// Panics under the same conditions as
+/// [`make_assignments()`](crate::make_assignments).
+pub fn make_assignments_to_file(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    path: impl AsRef<Path>,
+) -> io::Result<(usize, usize)> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    let mut count = 0;
+    let mut rounds = 0;
+    let failed = make_assignments_with(conflicts, min_group_size, |assignment| {
+        if let Err(err) = write_assignment(&mut writer, assignment) {
+            return ControlFlow::Break(err);
+        }
+        count += 1;
+        rounds = assignment.len();
+        ControlFlow::Continue(())
+    });
+    if let Some(err) = failed {
+        return Err(err);
+    }
+    writer.flush()?;
+    Ok((count, rounds))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::make_assignments;
+    use crate::tests::diagonal;
+    use std::io::BufReader;
+
+    #[test]
+    fn round_trip_line() {
+        let assignment = vec![vec![vec![0, 1], vec![2, 3]], vec![vec![0, 2], vec![1, 3]]];
+        let mut buf = vec![];
+        write_assignment(&mut buf, &assignment).unwrap();
+        assert_eq!(String::from_utf8(buf.clone()).unwrap(), "[[[0,1],[2,3]],[[0,2],[1,3]]]\n");
+        let read: Vec<_> = read_assignments(&buf[..]).collect::<io::Result<_>>().unwrap();
+        assert_eq!(read, vec![assignment]);
+    }
+
+    #[test]
+    fn malformed_lines_are_rejected() {
+        for line in ["[[[0,1]]", "[[0,1]]", "[[[0,x]]]", "[[[0,1]]]]", "[[[0,1]]][]"] {
+            let err = read_assignments(line.as_bytes()).next().unwrap().unwrap_err();
+            assert_eq!(err.kind(), io::ErrorKind::InvalidData, "Accepted {line}");
+        }
+        let spaced = read_assignments("[ [ [0, 1], [2] ] ]\n\n[]".as_bytes());
+        let read: Vec<_> = spaced.collect::<io::Result<_>>().unwrap();
+        assert_eq!(read, vec![vec![vec![vec![0, 1], vec![2]]], vec![]]);
+    }
+
+    #[test]
+    fn spills_maximal_assignments_to_file() {
+        let path = std::env::temp_dir().join(format!("group_generator_{}.jsonl", std::process::id()));
+        let (count, rounds) = make_assignments_to_file(&mut diagonal(5), 2, &path).unwrap();
+        let expected = make_assignments(&mut diagonal(5), 2);
+        assert_eq!((count, rounds), (expected.len(), 1));
+        let file = BufReader::new(File::open(&path).unwrap());
+        let read: Vec<_> = read_assignments(file).collect::<io::Result<_>>().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read, expected);
+
+        let missing = std::env::temp_dir().join("group_generator_missing_dir").join("out.jsonl");
+        assert!(make_assignments_to_file(&mut diagonal(4), 2, missing).is_err());
+    }
+}
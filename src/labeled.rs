@@ -0,0 +1,144 @@
+//! # Labeled Vertices
+//!
+//! A layer over [`AssignmentProblem`] for problems whose vertices are known by
+//! labels (names, IDs, ...) rather than indices.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use crate::{AssignmentProblem, GroupError, RawGroup};
+
+/// A scheduling problem over labeled vertices, solved in terms of the labels.
+///
+/// Each distinct label is one vertex, indexed in order of first appearance.
+///
+/// ```
+/// # use group_generator::LabeledProblem;
+/// let problem = LabeledProblem::new(["ann", "bo", "cy", "di"]).conflict("ann", "bo");
+/// for assignment in problem.solve().unwrap() {
+///     assert!(assignment.iter().flatten().all(|g| !(g.contains(&"ann") && g.contains(&"bo"))));
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct LabeledProblem<T> {
+    labels: Vec<T>,
+    indices: HashMap<T, usize>,
+    conflicts: Vec<(usize, usize)>,
+    min_group_size: usize,
+}
+
+impl<T: Eq + Hash + Clone> LabeledProblem<T> {
+    /// Start describing a problem on the given labels with no conflicts and a
+    /// minimum group size of 2. Repeated labels are the same vertex.
+    pub fn new(labels: impl IntoIterator<Item = T>) -> Self {
+        let mut problem = Self {
+            labels: vec![],
+            indices: HashMap::new(),
+            conflicts: vec![],
+            min_group_size: 2,
+        };
+        for label in labels {
+            problem.vertex(label);
+        }
+        problem
+    }
+
+    /// Set the minimum group size.
+    pub fn min_group_size(mut self, min_group_size: usize) -> Self {
+        self.min_group_size = min_group_size;
+        self
+    }
+
+    /// Forbid `a` and `b` from ever sharing a group. Labels not seen before
+    /// are added as new vertices.
+    pub fn conflict(mut self, a: T, b: T) -> Self {
+        let pair = (self.vertex(a), self.vertex(b));
+        self.conflicts.push(pair);
+        self
+    }
+
+    /// The labels, in index order.
+    pub fn labels(&self) -> &[T] {
+        &self.labels
+    }
+
+    /// The index of `label`, if it is a vertex.
+    pub fn index_of(&self, label: &T) -> Option<usize> {
+        self.indices.get(label).copied()
+    }
+
+    /// Generate all *maximum-round* assignments (see
+    /// [`make_assignments()`](crate::make_assignments)), with every vertex
+    /// replaced by its label.
+    ///
+    /// # Errors
+    ///
+    /// As for [`AssignmentProblem::build()`].
+    pub fn solve(&self) -> Result<Vec<Vec<Vec<Vec<T>>>>, GroupError> {
+        let solver = AssignmentProblem::new(self.labels.len())
+            .min_group_size(self.min_group_size)
+            .conflicts(self.conflicts.iter().copied())
+            .build()?;
+        Ok(solver.solve().iter().map(|assignment| self.to_labels(assignment)).collect())
+    }
+
+    /// Replace every vertex of an index-based assignment by its label.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the assignment refers to a vertex out of range.
+    pub fn to_labels(&self, assignment: &[Vec<RawGroup>]) -> Vec<Vec<Vec<T>>> {
+        assignment
+            .iter()
+            .map(|round| {
+                round
+                    .iter()
+                    .map(|g| g.iter().map(|v| self.labels[*v].clone()).collect())
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// The index of `label`, adding it as a new vertex if needed.
+    fn vertex(&mut self, label: T) -> usize {
+        if let Some(&v) = self.indices.get(&label) {
+            return v;
+        }
+        self.labels.push(label.clone());
+        self.indices.insert(label, self.labels.len() - 1);
+        self.labels.len() - 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::make_assignments;
+    use crate::tests::diagonal;
+
+    #[test]
+    fn labels_map_to_indices() {
+        let names = ["ann", "bo", "cy", "di", "ann"].map(String::from);
+        let problem = LabeledProblem::new(names).conflict("bo".into(), "di".into());
+        assert_eq!(problem.labels().len(), 4);
+        assert_eq!(problem.index_of(&"cy".to_string()), Some(2));
+        assert_eq!(problem.index_of(&"ed".to_string()), None);
+
+        let mut conflicts = diagonal(4);
+        conflicts.add_conflict(1, 3);
+        let expected: Vec<_> = make_assignments(&mut conflicts, 2)
+            .iter()
+            .map(|assignment| problem.to_labels(assignment))
+            .collect();
+        assert_eq!(problem.solve().unwrap(), expected);
+        assert_eq!(expected[0][0][0], vec!["ann".to_string(), "bo".to_string()]);
+    }
+
+    #[test]
+    fn conflicts_add_new_labels() {
+        let problem = LabeledProblem::new([10, 20]).conflict(20, 30).min_group_size(4);
+        assert_eq!(problem.labels(), &[10, 20, 30]);
+        let err = problem.solve().unwrap_err();
+        assert_eq!(err, GroupError::InvalidMinGroupSize { min_group_size: 4, n: 3 });
+        assert_eq!(LabeledProblem::<u8>::new([]).solve().unwrap_err(), GroupError::NoVertices);
+    }
+}
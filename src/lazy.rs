@@ -0,0 +1,123 @@
+//! # Lazy Enumeration
+//!
+//! A resumable version of the round-level search, yielding maximal
+//! assignments one at a time.
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{
+    check_problem, commit_round, group_sizes, max_rounds, rollback_round, single_assignment, singletons, Assignment,
+    ConflictMatrix, Frame, RawGroup,
+};
+
+/// Lazily generate the *maximum-round* assignments (as in
+/// [`make_assignments()`](crate::make_assignments)), in the same order.
+///
+/// The search state lives in the returned iterator, so only as much of the
+/// tree is explored as is needed to produce the items actually taken, and no
+/// assignment is kept once it has been yielded. `conflicts` is cloned, so the
+/// iterator does not borrow it.
+///
+/// Maximality is only known once the whole tree has been searched, so the
+/// maximum number of rounds is computed up front with [`max_rounds()`].
+/// Branches are then cut off as soon as they reach that depth.
+///
+/// # Panics
+///
+/// Panics under the same conditions as
+/// [`make_assignments()`](crate::make_assignments).
+pub fn assignments_iter(conflicts: &ConflictMatrix, min_group_size: usize) -> impl Iterator<Item = Assignment> {
+    check_problem(conflicts, min_group_size);
+    let mut conflicts = conflicts.clone();
+    let best = max_rounds(&mut conflicts, min_group_size);
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut iter = Assignments {
+        conflicts,
+        group_sizes,
+        best,
+        curr: vec![],
+        stack: vec![],
+        pending: None,
+    };
+    if min_group_size == 1 {
+        iter.pending = Some(Assignment::from(vec![singletons(iter.conflicts.len())]));
+    } else if best == 0 {
+        iter.pending = Some(Assignment::default());
+    } else {
+        let options = single_assignment(&mut iter.conflicts, &iter.group_sizes);
+        iter.stack.push(Frame::new(options));
+    }
+    iter
+}
+
+/// Suspended round-level search, mirroring the explicit stack of
+/// [`explore_rounds()`](crate::explore_rounds).
+struct Assignments {
+    conflicts: ConflictMatrix,
+    group_sizes: Vec<usize>,
+    best: usize,
+    curr: Vec<Vec<RawGroup>>,
+    stack: Vec<Frame<Vec<RawGroup>>>,
+    /// Answer for the trivial cases, which need no search.
+    pending: Option<Assignment>,
+}
+
+impl Iterator for Assignments {
+    type Item = Assignment;
+
+    fn next(&mut self) -> Option<Assignment> {
+        if let Some(assignment) = self.pending.take() {
+            return Some(assignment);
+        }
+        loop {
+            let frame = self.stack.last_mut()?;
+            let Some(opt) = frame.next_option() else {
+                self.stack.pop();
+                if let Some(opt) = self.curr.pop() {
+                    rollback_round(&mut self.conflicts, &opt);
+                }
+                continue;
+            };
+            commit_round(&mut self.conflicts, &opt);
+            self.curr.push(opt);
+            // Nothing can go deeper than the best depth, so stop there
+            if self.curr.len() < self.best {
+                let options = single_assignment(&mut self.conflicts, &self.group_sizes);
+                if !options.is_empty() {
+                    self.stack.push(Frame::new(options));
+                    continue;
+                }
+            }
+            let leaf = (self.curr.len() == self.best).then(|| Assignment::from(self.curr.clone()));
+            if let Some(opt) = self.curr.pop() {
+                rollback_round(&mut self.conflicts, &opt);
+            }
+            if leaf.is_some() {
+                return leaf;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::make_assignments_typed;
+    use crate::tests::diagonal;
+
+    #[test]
+    fn lazy_matches_eager() {
+        let mut conflicted = diagonal(5);
+        conflicted.add_conflict(0, 3);
+        for (conflicts, k) in [(diagonal(4), 2), (diagonal(5), 2), (diagonal(6), 3), (conflicted, 2), (diagonal(3), 1)] {
+            let eager = make_assignments_typed(&mut conflicts.clone(), k);
+            assert_eq!(assignments_iter(&conflicts, k).collect::<Vec<_>>(), eager);
+            assert_eq!(assignments_iter(&conflicts, k).take(2).collect::<Vec<_>>(), eager[..eager.len().min(2)]);
+        }
+        let mut isolated = diagonal(4);
+        for v in 1..4 {
+            isolated.add_conflict(0, v);
+        }
+        assert_eq!(assignments_iter(&isolated, 2).collect::<Vec<_>>(), vec![Assignment::default()]);
+    }
+}
@@ -23,9 +23,9 @@
 //! The system never stores custom data—just relationships (conflicts).
 //!
 //! ### Conflict Matrix
-//! The `conflicts: Vec<BitVec>` parameter is a square boolean adjacency
-//! matrix where `conflicts[i][j] == true` means vertices `i` and `j` may **not**
-//! appear in the same group.  
+//! The `conflicts: ConflictMatrix` parameter is a symmetric boolean adjacency
+//! matrix (stored as bit vectors) where `conflicts.is_conflicted(i, j)` means
+//! vertices `i` and `j` may **not** appear in the same group.  
 //!
 //! Conflicts are updated *dynamically* while exploring group combinations:
 //! - When building a group, temporary conflicts are injected to prune search.
@@ -37,10 +37,10 @@
 //!
 //! ### Groups and Rounds
 //! A **group** is a `Vec<usize>`.  
-//! A **round** is a `Vec<Group>`: a full partition of all vertices.  
-//! An **assignment** is a `Vec<Vec<Group>>`: one valid sequence of rounds.  
+//! A **round** is a `Vec<Vec<usize>>`: a full partition of all vertices.  
+//! An **assignment** is a `Vec<Vec<Vec<usize>>>`: one valid sequence of rounds.  
 //!
-//! The solver returns a `Vec<Vec<Vec<Group>>>`, meaning:
+//! The solver returns a `Vec<Vec<Vec<Vec<usize>>>>`, meaning:
 //!
 //! ```text
 //! // Many possible assignments
@@ -50,12 +50,16 @@
 //!         // Single round, containing groups with predetermined group size
 //!         Vec<
 //!             // Single group, containing a vector of vertex indices
-//!             Group
+//!             Vec<usize>
 //!         >
 //!     >
 //! >
 //! ```
 //!
+//! The [`Group`], [`Round`] and [`Assignment`] newtypes wrap the raw
+//! representations with domain methods, indexing and `Display` output;
+//! [`make_assignments_typed()`] returns them directly.
+//!
 //! ### Group Size Planning
 //!
 //! A predetermined vector of group sizes for each round is produced by
@@ -67,7 +71,8 @@
 //!
 //! ## Algorithm Summary
 //!
-//! 1. **Determine per-round group sizes** (`group_sizes()`).
+//! 1. **Determine per-round group sizes** (`group_sizes()`), and bail out
+//!    early if no round can possibly be formed (`can_form_any_round()`).
 //! 2. **Enumerate all conflict-valid rounds** given the current conflict state
 //!    (`single_assignment()`).
 //! 3. For each round found:
@@ -84,10 +89,149 @@
 //!   a modest, constant factor speedup over less compact approaches.
 //! - Pruning recursion tree by not including invalid groups (as determined via
 //!   conflicts list) during recursive calls.
+//!
+//! All three levels of backtracking (rounds, groups within a round, vertices
+//! within a group) keep their state on explicit heap-allocated stacks rather
+//! than native recursion, so deep searches cannot overflow the thread stack.
+//!
+//! ## Optional Features
+//!
+//! - `std` (default): everything needing the standard library, namely file
+//!   output ([`make_assignments_to_file()`] and friends, and checkpoints of
+//!   a [`ResumableSearch`]),
+//!   [`make_assignments_parallel()`], [`LabeledProblem`],
+//!   [`make_assignments_memoized()`] and [`count_distinct_maximal()`].
+//!   Without it the crate is `no_std` and only needs `alloc`, so the core
+//!   solver can be embedded in restricted runtimes such as WASM.
+//! - `serde`: `Serialize` and `Deserialize` for [`ConflictMatrix`],
+//!   [`SolverConfig`], [`Pin`], [`RoleQuota`], [`SubsetQuota`],
+//!   [`SeparationSet`], [`ScheduleRecord`] and the
+//!   [`Group`], [`Round`] and [`Assignment`] newtypes (which serialize as
+//!   their raw forms).
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cell::{Cell, RefCell};
+use core::ops::ControlFlow;
+#[cfg(feature = "std")]
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "std")]
+use std::hash::{DefaultHasher, Hash, Hasher};
+
 use bitvec::prelude::*;
 
+mod analysis;
+mod assignment;
+mod calendar;
+#[cfg(feature = "std")]
+mod checkpoint;
+mod cohorts;
+mod coloring;
+mod compact;
+mod composition;
+mod config;
+mod conflicts;
+mod constraint;
+mod designs;
+mod diagnostics;
+mod dsl;
+mod error;
+mod export;
+#[cfg(feature = "std")]
+mod jsonl;
+#[cfg(feature = "std")]
+mod labeled;
+mod lazy;
+mod objective;
+#[cfg(feature = "std")]
+mod parallel;
+mod planner;
+mod problem;
+mod rooms;
+mod scheduler;
+mod ternary;
+
+pub use analysis::{
+    affinity_score, canonical_assignment, canonical_round, dedup_canonical, distinct_partners, dropout_robustness, has_continuity, isomorphism_classes, meeting_counts, met_rounds_matrix, pair_distance, rank_by_affinity, rank_by_partner_spread, rank_by_trajectory, satisfies_continuity,
+    soft_conflict_penalty, sort_canonical, trajectory_score, validate_assignment, RoundTrajectory,
+};
+pub use assignment::{make_assignments_typed, Assignment, Group, Round};
+pub use calendar::plan_sessions;
+#[cfg(feature = "std")]
+pub use checkpoint::ResumableSearch;
+pub use cohorts::{partition_and_solve, Cohort};
+pub use coloring::{conflict_chromatic_bound, conflict_clique_bound};
+pub use compact::make_assignments_compact;
+pub use composition::{
+    make_assignments_balanced, make_assignments_diverse, make_assignments_mean_balanced, make_assignments_with_roles, make_assignments_with_size_limits,
+    make_assignments_with_subset_quotas, RoleQuota, SubsetQuota,
+};
+pub use config::{solve_with_config, SolverConfig};
+pub use conflicts::{add_separation_sets, conflicts_from_groups, mark_group_conflicts, unmark_group_conflicts, ConflictMatrix, SeparationSet};
+pub use constraint::{
+    make_assignments_pruned, make_assignments_with_constraint, make_assignments_with_round_constraint, potential_groups_with, single_assignment_with,
+    GroupConstraint,
+    RoundConstraint,
+};
+pub use designs::latin_square_schedule;
+pub use diagnostics::{explain_missing_pair, explain_missing_pair_separated, PairExplanation};
+pub use dsl::{parse_constraints, ParseError};
+pub use error::GroupError;
+pub use export::{from_records, to_labeled_records, to_records, ScheduleRecord};
+#[cfg(feature = "std")]
+pub use jsonl::{make_assignments_to_file, read_assignments, write_assignment};
+#[cfg(feature = "std")]
+pub use labeled::LabeledProblem;
+#[cfg(feature = "std")]
+pub use parallel::make_assignments_parallel;
+pub use lazy::assignments_iter;
+pub use objective::{make_assignments_ranked, make_assignments_top_k, Score};
+pub use planner::OnlinePlanner;
+pub use problem::{AssignmentProblem, AssignmentSolver};
+pub use rooms::make_assignments_with_rooms;
+pub use scheduler::Scheduler;
+pub use ternary::{make_assignments_ternary, resolve_conflicts, ConflictStatus, UnknownMode};
+
 /// Vector of vertex indices corresponding to one group
-type Group = Vec<usize>;
+type RawGroup<V = usize> = Vec<V>;
+
+/// An integer type the search can store vertex indices in.
+///
+/// The search works with `usize` by default, but can hold its groups and
+/// intermediate rounds in a narrower type (see
+/// [`make_assignments_compact()`]) when the number of vertices allows.
+pub trait VertexIndex: Copy + Eq + core::fmt::Debug + Default {
+    /// The largest index the type can hold.
+    const MAX_INDEX: usize;
+    /// The vertex with index `v`, which must fit the type.
+    fn from_index(v: usize) -> Self;
+    /// The index of this vertex.
+    fn index(self) -> usize;
+}
+
+macro_rules! impl_vertex_index {
+    ($($t:ty),*) => {
+        $(impl VertexIndex for $t {
+            const MAX_INDEX: usize = <$t>::MAX as usize;
+
+            #[inline]
+            fn from_index(v: usize) -> Self {
+                debug_assert!(v <= Self::MAX_INDEX);
+                v as $t
+            }
+
+            #[inline]
+            fn index(self) -> usize {
+                self as usize
+            }
+        })*
+    };
+}
+
+impl_vertex_index!(u8, u16, u32, usize);
 
 /// Generate all possible *maximum-round* group assignments such that:
 ///
@@ -101,7 +245,7 @@ type Group = Vec<usize>;
 /// # Arguments
 ///
 /// - `conflicts`:  
-///   A square adjacency matrix (bit-matrix) where `conflicts[i][j] == true`
+///   A symmetric [`ConflictMatrix`] where `conflicts.is_conflicted(i, j)`
 ///   means that vertices `i` and `j` may **not** be placed in the same group.
 ///   This matrix gets *updated* as groups are tentatively formed during
 ///   backtracking, but is always returned to its previous state.
@@ -117,289 +261,3701 @@ type Group = Vec<usize>;
 ///
 /// # Panics
 ///
-/// Panics if the `conflicts` matrix is empty, not square, or has fewer vertices
-/// than required by `min_group_size`.
-pub fn make_assignments(conflicts: &mut Vec<BitVec>, min_group_size: usize) -> Vec<Vec<Vec<Group>>> {
-    assert!(conflicts.len() > 0, "Cannot make groups from 0 vertices.");
-    assert!(conflicts.iter().all(|v| v.len() == conflicts.len()), "Conflicts matrix must have matching dimensions (N x N)");
-    assert!(min_group_size <= conflicts.len(), "Cannot require groups larger than the number of potential vertices.");
-
-    fn backtrack(
-        conflicts: &mut Vec<BitVec>,
-        sols: &mut Vec<Vec<Vec<Group>>>,
-        curr: &mut Vec<Vec<Group>>,
-        best: &mut usize,
-        group_sizes: &[usize],
-    ) {
-        let options = single_assignment(conflicts, group_sizes);
-        if options.is_empty() && curr.len() >= *best {
-            if curr.len() > *best {
-                sols.clear();
-            }
-            sols.push(curr.clone());
-            *best = curr.len();
-        } else {
-            for opt in options {
-                for g in &opt {
-                    add_conflicts_between(conflicts, g);
-                }
-                curr.push(opt);
-                backtrack(conflicts, sols, curr, best, group_sizes);
-                if let Some(opt) = curr.pop() {
-                    for g in &opt {
-                        remove_conflicts_between(conflicts, g);
-                    }
-                }
-            }
-        }
+/// Panics if the `conflicts` matrix is empty or has fewer vertices than
+/// required by `min_group_size`, or if `min_group_size` is 0. See [`try_make_assignments()`] for a version which
+/// returns these as errors instead.
+///
+/// # Singleton Groups
+///
+/// With `min_group_size == 1` every vertex can sit alone, which never creates
+/// a conflict, so singleton rounds could be repeated forever without changing
+/// anything. This is defined as exactly **one** maximal round: the single
+/// assignment returned contains one round of singletons in vertex order.
+pub fn make_assignments(conflicts: &mut ConflictMatrix, min_group_size: usize) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+
+    if min_group_size == 1 {
+        return vec![vec![singletons(conflicts.len())]];
+    }
+    if !can_form_any_round(conflicts, min_group_size) {
+        return vec![vec![]];
     }
-    let mut sols = vec![];
-    let mut curr = vec![];
-    let mut best = 0;
     let group_sizes = group_sizes(conflicts.len(), min_group_size);
-    backtrack(conflicts, &mut sols, &mut curr, &mut best, &group_sizes);
-    sols
+    maximal_assignments(
+        conflicts,
+        |conflicts| single_assignment(conflicts, &group_sizes),
+        commit_round,
+        rollback_round,
+    )
 }
 
-/// Compute the group sizes for a single round, given
-/// `n` total vertices and a minimum group size `min_group_size`.
+/// Fallible version of [`make_assignments()`], reporting invalid input as a
+/// [`GroupError`] instead of panicking.
 ///
-/// Produces a vector of sizes that:
-///
-/// - All sum to exactly `n`.
-/// - Are each at least `min_group_size`.
-/// - Distribute any leftover vertices as evenly as possible.
+/// # Errors
 ///
-/// # Example
+/// - [`GroupError::NoVertices`] if `conflicts` is empty.
+/// - [`GroupError::InvalidMinGroupSize`] if `min_group_size` is 0 or exceeds
+///   the number of vertices.
 ///
-/// ```
-/// assert_eq!(group_sizes(7, 2), vec![3, 2, 2]);
-/// ```
-pub fn group_sizes(n: usize, min_group_size: usize) -> Vec<usize> {
-    let mut remaining = n % min_group_size;
-    let mut sizes = vec![min_group_size; n / min_group_size]; 
-    if sizes.is_empty() {
-        return sizes;
-    }
-    // Evenly distribute leftover across vector
-    'outer: loop {
-        for i in 0..sizes.len() {
-            if remaining == 0 {
-                break 'outer sizes;
-            }
-            sizes[i] += 1;
-            remaining -= 1;
-        }
-    }
+/// A [`ConflictMatrix`] is always square and symmetric; raw rows are checked
+/// for that when converted with [`ConflictMatrix::try_from()`].
+pub fn try_make_assignments(conflicts: &mut ConflictMatrix, min_group_size: usize) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    Ok(make_assignments(conflicts, min_group_size))
 }
 
-/// Generate all possible **single-round** assignments respecting current
-/// conflict constraints.
+/// Non-mutating version of [`make_assignments()`], solving against a private
+/// copy of `conflicts`.
 ///
-/// A *single assignment* is one full round of grouping using the provided
-/// `group_sizes` (which must sum to the total vertex count).
+/// The caller keeps shared access to its canonical conflict data, so several
+/// solves may run concurrently (e.g. from scoped threads) against the same
+/// matrix. The copy costs one clone of the matrix per call.
 ///
-/// # Behavior
+/// # Panics
 ///
-/// - Each group in the round is conflict-free according to `conflicts`.
-/// - Once a vertex is placed in a group, it is marked as “skipped” for the
-///   remainder of this round.
-/// - All assignments are returned; no maximality filtering occurs here.
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_shared(conflicts: &ConflictMatrix, min_group_size: usize) -> Vec<Vec<Vec<RawGroup>>> {
+    make_assignments(&mut conflicts.clone(), min_group_size)
+}
+
+/// Generate the *maximum-round* continuations of a schedule whose first
+/// rounds have already happened, as full assignments starting with `history`.
 ///
-/// # Returns
+/// The conflicts are seeded by committing every round of `history` in turn,
+/// and only the remaining rounds are searched, so the result is the best
+/// continuation rather than a schedule from scratch. `conflicts` is restored
+/// afterwards. If no further round is possible, the only assignment returned
+/// is `history` itself.
 ///
-/// A list of all valid ways to construct one round:
-pub fn single_assignment(conflicts: &mut Vec<BitVec>, group_sizes: &[usize]) -> Vec<Vec<Group>> {
-    fn backtrack(
-        conflicts: &mut Vec<BitVec>,
-        sols: &mut Vec<Vec<Group>>,
-        curr: &mut Vec<Group>,
-        group_sizes: &[usize],
-        skip: &mut BitVec,
-    ) {
-        let k = group_sizes[curr.len()];
-        for g in potential_groups(conflicts, k, skip) {
-            if curr.len() == group_sizes.len() - 1 {
-                curr.push(g);
-                sols.push(curr.clone());
-                curr.pop();
-            } else {
-                for e in &g {
-                    skip.set(*e, true)
-                }
-                curr.push(g);
-                backtrack(conflicts, sols, curr, group_sizes, skip);
-                if let Some(g) = curr.pop() {
-                    for e in g {
-                        skip.set(e, false)
-                    }
-                }
+/// With `min_group_size == 1` a schedule is a single round of singletons (see
+/// [`make_assignments()`]), so any history is already complete.
+///
+/// # Errors
+///
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`].
+/// - [`GroupError::InvalidRound`] if a round of `history` is not a partition
+///   into groups of at least `min_group_size`, or groups a pair which
+///   conflicts or already met in an earlier round.
+pub fn make_assignments_continuing(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    history: &[Vec<RawGroup>],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut sols = vec![];
+    visit_continuing::<()>(conflicts, min_group_size, &group_sizes, history, |_, _, _| true, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    })?;
+    Ok(sols)
+}
+
+/// [`visit_maximal_filtered()`] continuing from `history` (see
+/// [`make_assignments_continuing()`]), handing `visit` the full assignments.
+/// Rounds are numbered from the start of `history`.
+pub(crate) fn visit_continuing<B>(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    group_sizes: &[usize],
+    history: &[Vec<RawGroup>],
+    allows: impl Fn(Slot, &[usize], usize) -> bool,
+    mut visit: impl FnMut(&[Vec<RawGroup>]) -> ControlFlow<B>,
+) -> Result<Option<B>, GroupError> {
+    commit_history(conflicts, min_group_size, history, commit_round, rollback_round)?;
+    let stopped = if !history.is_empty() && group_sizes.iter().all(|k| *k == 1) {
+        visit(history).break_value()
+    } else {
+        let mut full = history.to_vec();
+        let allows = |slot: Slot, partial: &[usize], candidate| {
+            allows(Slot { round: history.len() + slot.round, ..slot }, partial, candidate)
+        };
+        visit_maximal_filtered(conflicts, &[group_sizes.to_vec()], allows, |rest| {
+            full.truncate(history.len());
+            full.extend_from_slice(rest);
+            visit(&full)
+        })
+    };
+    for round in history.iter().rev() {
+        rollback_round(conflicts, round);
+    }
+    Ok(stopped)
+}
+
+/// Check each round of `history` with [`check_round()`] and `commit` it in
+/// turn. If one is invalid, the rounds already committed are undone with
+/// `rollback` before returning the error.
+fn commit_history(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    history: &[Vec<RawGroup>],
+    commit: impl Fn(&mut ConflictMatrix, &[RawGroup]),
+    rollback: impl Fn(&mut ConflictMatrix, &[RawGroup]),
+) -> Result<(), GroupError> {
+    for (i, round) in history.iter().enumerate() {
+        if let Err(err) = check_round(conflicts, min_group_size, round) {
+            for prev in history[..i].iter().rev() {
+                rollback(conflicts, prev);
             }
+            return Err(err);
         }
+        commit(conflicts, round);
     }
+    Ok(())
+}
 
-    let n = conflicts.len();
-    let mut res: Vec<Vec<Group>> = vec![];
-    let mut skip = bitvec![0; n];
-    let mut curr = vec![];
-    backtrack(conflicts, &mut res, &mut curr, &group_sizes, &mut skip);
-    res
+/// Generate the *maximum-round* assignments (as in [`make_assignments()`])
+/// with every round shaped exactly by `group_sizes`, in order, instead of by
+/// [`group_sizes()`].
+///
+/// ```
+/// # use group_generator::{make_assignments_shaped, ConflictMatrix};
+/// let sols = make_assignments_shaped(&mut ConflictMatrix::empty(10), &[4, 3, 3]).unwrap();
+/// assert!(sols[0].iter().all(|round| round.iter().map(Vec::len).eq([4, 3, 3])));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::NoVertices`] if `conflicts` is empty.
+/// - [`GroupError::InvalidGroupShape`] if `group_sizes` is empty, contains
+///   0, or does not sum to the number of vertices.
+pub fn make_assignments_shaped(conflicts: &mut ConflictMatrix, group_sizes: &[usize]) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_shape(conflicts.len(), group_sizes)?;
+    let mut sols = vec![];
+    visit_maximal::<()>(conflicts, group_sizes, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    });
+    Ok(sols)
 }
 
-/// Enumerate all possible **groups of size `k`** that are valid given the
-/// conflict matrix and current "skip" mask.
+/// Generate the *maximum-round* assignments (as in [`make_assignments()`])
+/// with a different minimum group size, and so a different shape (see
+/// [`group_sizes()`]), for each round.
 ///
-/// A group is valid if:
+/// Round `i` uses `min_group_sizes[i]`, and every round past the end of the
+/// list uses its last entry, so `[2, 3]` means pairs in the first round and
+/// groups of three from then on. Maximality is over the whole schedule, so
+/// the result may have fewer rounds than the list.
 ///
-/// - It contains exactly `k` vertices.
-/// - None of the vertices are marked in `skip` (already chosen).
-/// - No pair inside the group has a conflict (`conflicts[i][j] == true`).
+/// ```
+/// # use group_generator::{make_assignments_per_round, ConflictMatrix};
+/// let sols = make_assignments_per_round(&mut ConflictMatrix::empty(6), &[2, 3]).unwrap();
+/// let shape = |round: &Vec<Vec<usize>>| round.iter().map(Vec::len).collect::<Vec<_>>();
+/// assert_eq!(shape(&sols[0][0]), vec![2, 2, 2]);
+/// assert_eq!(shape(&sols[0][1]), vec![3, 3]);
+/// ```
 ///
-/// The function **temporarily** marks conflict edges while exploring deeper
-/// combinations to prune invalid partial groups.
+/// # Errors
 ///
-/// # Arguments
+/// - [`GroupError::NoVertices`] if `conflicts` is empty.
+/// - [`GroupError::InvalidMinGroupSize`] if `min_group_sizes` is empty
+///   (reported as a size of 0), or any entry is 0 or exceeds the number of
+///   vertices.
+pub fn make_assignments_per_round(
+    conflicts: &mut ConflictMatrix,
+    min_group_sizes: &[usize],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    let n = conflicts.len();
+    if min_group_sizes.is_empty() {
+        validate_problem(conflicts, 0)?;
+    }
+    for min_group_size in min_group_sizes {
+        validate_problem(conflicts, *min_group_size)?;
+    }
+    let shapes: Vec<Vec<usize>> = min_group_sizes.iter().map(|k| group_sizes(n, *k)).collect();
+    let mut sols = vec![];
+    visit_maximal_per_round::<()>(conflicts, &shapes, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    });
+    Ok(sols)
+}
+
+/// Generate every assignment of exactly `rounds` rounds where each round may
+/// take the shape (see [`group_sizes()`]) of any of `min_group_sizes`, ranked
+/// best-first by how closely their round sizes follow `trajectory`.
 ///
-/// - `conflicts`: mutable matrix tracking current conflict constraints.
-/// - `k`: required group size.
-/// - `skip`: bitmask of vertices that are already taken in this round.
+/// Unlike [`make_assignments_per_round()`], the shape of each round is part
+/// of the search, so the same rounds can be scheduled in different orders and
+/// [`rank_by_trajectory()`] decides between them, e.g. putting a large
+/// icebreaker round first. The round count is fixed (as in
+/// [`make_assignments_exact_rounds()`]) since the deepest schedules tend to
+/// settle on the smallest shape, which would leave nothing to rank.
 ///
-/// # Returns
+/// ```
+/// # use group_generator::{make_assignments_with_trajectory, ConflictMatrix, RoundTrajectory};
+/// let sols = make_assignments_with_trajectory(&mut ConflictMatrix::empty(6), &[2, 3], 2, RoundTrajectory::Decreasing).unwrap();
+/// let shape = |round: &Vec<Vec<usize>>| round.iter().map(Vec::len).collect::<Vec<_>>();
+/// assert_eq!(shape(&sols[0][0]), vec![3, 3]);
+/// assert_eq!(shape(&sols[0][1]), vec![2, 2, 2]);
+/// ```
 ///
-/// Eery valid `k`-set of vertex indices. 
-pub fn potential_groups(conflicts: &mut Vec<BitVec>, k: usize, skip: &BitVec) -> Vec<Group> {
-    fn backtrack(
-        conflicts: &mut Vec<BitVec>,
-        sols: &mut Vec<Vec<usize>>,
-        curr: &mut Vec<usize>,
-        row: usize,
-        n: usize,
-        k: usize,
-        skip: &BitVec,
-    ) {
-        for col in (row + 1)..n {
-            if skip[col] {
-                continue;
+/// # Errors
+///
+/// - [`GroupError::NoVertices`] if `conflicts` is empty.
+/// - [`GroupError::InvalidMinGroupSize`] if `min_group_sizes` is empty
+///   (reported as a size of 0), or any entry is 0 or exceeds the number of
+///   vertices.
+/// - [`GroupError::UnreachableRounds`] if no assignment has `rounds` rounds.
+pub fn make_assignments_with_trajectory(
+    conflicts: &mut ConflictMatrix,
+    min_group_sizes: &[usize],
+    rounds: usize,
+    trajectory: RoundTrajectory,
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    let n = conflicts.len();
+    if min_group_sizes.is_empty() {
+        validate_problem(conflicts, 0)?;
+    }
+    for min_group_size in min_group_sizes {
+        validate_problem(conflicts, *min_group_size)?;
+    }
+    let mut shapes: Vec<Vec<usize>> = min_group_sizes.iter().map(|k| group_sizes(n, *k)).collect();
+    shapes.sort_unstable_by(|a, b| b.cmp(a));
+    shapes.dedup();
+    let mut sols = vec![];
+    let mut deepest = 0;
+    explore_rounds(
+        conflicts,
+        |conflicts, curr| {
+            if curr.len() == rounds {
+                return vec![];
             }
-            let is_valid = curr.iter().all(|row| !conflicts[*row][col]);
-            if is_valid {
-                curr.push(col);
-                if curr.len() == k {
-                    sols.push(curr.clone());
+            let mut options = vec![];
+            for shape in &shapes {
+                if shape.iter().all(|k| *k == 1) {
+                    options.push(singletons(n));
                 } else {
-                    add_conflicts(conflicts, col, curr.iter());
-                    backtrack(conflicts, sols, curr, col, n, k, skip);
-                    remove_conflicts(conflicts, col, curr.iter());
+                    options.extend(single_assignment(conflicts, shape));
                 }
-                curr.pop();
             }
-        }
+            options
+        },
+        commit_round,
+        rollback_round,
+        |curr| {
+            deepest = deepest.max(curr.len());
+            if curr.len() == rounds {
+                sols.push(curr.to_vec());
+            }
+            ControlFlow::Continue(())
+        },
+    );
+    if sols.is_empty() {
+        // Only branches reaching `rounds` are cut short, so `deepest` is exact
+        return Err(GroupError::UnreachableRounds { rounds, max_rounds: deepest });
     }
+    rank_by_trajectory(&mut sols, trajectory);
+    Ok(sols)
+}
 
-    let mut res = vec![];
+/// Generate the *maximum-round* assignments (as in [`make_assignments()`])
+/// where vertex `v` takes up `weights[v]` seats, and group sizes count seats
+/// rather than members.
+///
+/// A pair of co-teachers, for example, can be one vertex of weight 2. Rounds
+/// are planned with [`group_sizes()`] over the total weight, and every group
+/// fills its seats exactly, so `min_group_size` is a number of seats. A
+/// vertex of weight 0 takes no seat but still joins a group.
+///
+/// ```
+/// # use group_generator::{make_assignments_weighted, ConflictMatrix};
+/// let weights = [2, 1, 1, 1, 1];
+/// let sols = make_assignments_weighted(&mut ConflictMatrix::empty(5), 3, &weights).unwrap();
+/// for g in sols.iter().flatten().flatten() {
+///     assert_eq!(g.iter().map(|v| weights[*v]).sum::<usize>(), 3);
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::NoVertices`] if `conflicts` is empty.
+/// - [`GroupError::AttributeMismatch`] if there is not exactly one weight per
+///   vertex.
+/// - [`GroupError::InvalidMinGroupSize`] if `min_group_size` is 0 or exceeds
+///   the total weight (reported as `n`).
+pub fn make_assignments_weighted(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    weights: &[usize],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
     let n = conflicts.len();
-    for row in 0..n {
-        if skip[row] {
+    if n == 0 {
+        return Err(GroupError::NoVertices);
+    }
+    if weights.len() != n {
+        return Err(GroupError::AttributeMismatch { len: weights.len(), n });
+    }
+    let seats: usize = weights.iter().sum();
+    if min_group_size == 0 || min_group_size > seats {
+        return Err(GroupError::InvalidMinGroupSize { min_group_size, n: seats });
+    }
+    if min_group_size == 1 {
+        return Ok(vec![vec![singletons(n)]]);
+    }
+    let group_sizes = group_sizes(seats, min_group_size);
+    let next_rounds = |conflicts: &mut ConflictMatrix| weighted_rounds(conflicts, weights, &group_sizes);
+    Ok(maximal_assignments(conflicts, next_rounds, commit_round, rollback_round))
+}
+
+/// Generate all ways of partitioning the vertices into conflict-free groups
+/// filling exactly the given numbers of seats (see
+/// [`make_assignments_weighted()`]).
+fn weighted_rounds(conflicts: &ConflictMatrix, weights: &[usize], group_sizes: &[usize]) -> Vec<Vec<RawGroup>> {
+    let mut res = vec![];
+    let mut skip = bitvec![0; conflicts.len()];
+    let mut curr: Vec<RawGroup> = vec![];
+    // Frame `i` holds the candidates for group `i` given the groups in `curr`
+    let mut stack = vec![Frame::new(weighted_groups(conflicts, weights, group_sizes[0], &skip))];
+    while let Some(frame) = stack.last_mut() {
+        let Some(g) = frame.next_option() else {
+            stack.pop();
+            if let Some(g) = curr.pop() {
+                for v in g {
+                    skip.set(v, false);
+                }
+            }
             continue;
+        };
+        for v in &g {
+            skip.set(*v, true);
+        }
+        curr.push(g);
+        if curr.len() < group_sizes.len() {
+            stack.push(Frame::new(weighted_groups(conflicts, weights, group_sizes[curr.len()], &skip)));
+            continue;
+        }
+        // Vertices of weight 0 may still be left over once every seat is filled
+        if skip.all() {
+            res.push(curr.clone());
+        }
+        if let Some(g) = curr.pop() {
+            for v in g {
+                skip.set(v, false);
+            }
         }
-        let mut curr = vec![row];
-        backtrack(conflicts, &mut res, &mut curr, row, n, k, skip);
     }
     res
 }
 
-/// Mark all pairs inside `between` as mutually conflicting.
-#[inline]
-fn add_conflicts_between(conflicts: &mut Vec<BitVec>, between: &[usize]) {
-    for i in between {
-        for j in between {
-            conflicts[*i].set(*j, true);
+/// Enumerate the conflict-free groups of vertices not in `skip` whose weights
+/// sum to exactly `seats`, with members ascending.
+fn weighted_groups(conflicts: &ConflictMatrix, weights: &[usize], seats: usize, skip: &BitVec) -> Vec<RawGroup> {
+    let n = conflicts.len();
+    let mut res = vec![];
+    let mut curr: Vec<usize> = vec![];
+    let mut filled = 0;
+    // The next vertex to try adding to `curr`
+    let mut next = 0;
+    loop {
+        if next == n {
+            let Some(v) = curr.pop() else {
+                break;
+            };
+            filled -= weights[v];
+            next = v + 1;
+            continue;
+        }
+        let v = next;
+        next += 1;
+        if skip[v] || filled + weights[v] > seats || curr.iter().any(|u| conflicts.is_conflicted(*u, v)) || conflicts.completes_hyperedge(&curr, v) {
+            continue;
+        }
+        curr.push(v);
+        filled += weights[v];
+        if filled == seats {
+            res.push(curr.clone());
         }
     }
+    res
 }
 
-/// Remove all conflicts previously added by `add_conflicts_between`.
-#[inline]
-fn remove_conflicts_between(conflicts: &mut Vec<BitVec>, between: &[usize]) {
-    for i in between {
-        for j in between {
-            conflicts[*i].set(*j, false);
-        }
-    }
+/// Generate the *maximum-round* assignments (as in [`make_assignments()`])
+/// where each pair in `together` shares a group in every round.
+///
+/// Linked pairs, and any vertices linked through them, are placed as one
+/// unit: groups containing only part of a unit are never formed. Vertices
+/// within a unit are exempt from the no-repeat rule among themselves, while
+/// every other pair still meets at most once. A unit which is internally
+/// conflicting, or too large for any group, leaves no valid round.
+///
+/// Repeats within units break the round bounds used by
+/// [`make_assignments()`], so the whole search tree is explored.
+///
+/// ```
+/// # use group_generator::{make_assignments_together, ConflictMatrix};
+/// let sols = make_assignments_together(&mut ConflictMatrix::empty(6), 3, &[(0, 1)]).unwrap();
+/// assert!(sols.iter().flatten().all(|round| round.iter().any(|g| g.contains(&0) && g.contains(&1))));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::VertexOutOfRange`] if a pair refers to a vertex which
+///   does not exist.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`].
+pub fn make_assignments_together(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    together: &[(usize, usize)],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut sols = vec![];
+    visit_maximal_together::<()>(conflicts, min_group_size, &group_sizes, together, &[], |_, _, _| true, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    })?;
+    Ok(sols)
 }
 
-/// Add conflicts between one vertex `col` and all vertices from an iterator.
-#[inline]
-fn add_conflicts<'a>(conflicts: &mut Vec<BitVec>, col: usize, rows: impl Iterator<Item = &'a usize>) {
-    for row in rows {
-        conflicts[*row].set(col, true);
-        conflicts[col].set(*row, true);
+/// [`visit_maximal()`] where each pair in `together` shares a group in every
+/// round (see [`make_assignments_together()`]), continuing from `history` and
+/// building groups as `allows` accepts as in [`visit_continuing()`].
+pub(crate) fn visit_maximal_together<B>(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    group_sizes: &[usize],
+    together: &[(usize, usize)],
+    history: &[Vec<RawGroup>],
+    allows: impl Fn(Slot, &[usize], usize) -> bool,
+    mut visit: impl FnMut(&[Vec<RawGroup>]) -> ControlFlow<B>,
+) -> Result<Option<B>, GroupError> {
+    let n = conflicts.len();
+    check_vertices(n, together.iter().flat_map(|(u, v)| [*u, *v]))?;
+    // The least vertex of each vertex's unit
+    let mut unit: Vec<usize> = (0..n).collect();
+    for &(u, v) in together {
+        let (from, to) = (unit[u].max(unit[v]), unit[u].min(unit[v]));
+        unit.iter_mut().filter(|x| **x == from).for_each(|x| *x = to);
+    }
+    let whole_units = |g: &[usize]| g.iter().all(|v| (0..n).filter(|u| unit[*u] == unit[*v]).all(|u| g.contains(&u)));
+    if history.iter().flatten().any(|g| !whole_units(g)) {
+        return Err(GroupError::InvalidRound);
+    }
+    if group_sizes.iter().all(|k| *k == 1) {
+        let rounds = match history {
+            [] if unit.iter().enumerate().all(|(v, u)| v == *u) && allows_singletons(n, &allows) => vec![singletons(n)],
+            _ => history.to_vec(),
+        };
+        return Ok(visit(&rounds).break_value());
     }
-}
 
-/// Remove conflicts previously added by `add_conflicts`.
-#[inline]
-fn remove_conflicts<'a>(conflicts: &mut Vec<BitVec>, col: usize, rows: impl Iterator<Item = &'a usize>) {
-    for row in rows {
-        conflicts[*row].set(col, false);
-        conflicts[col].set(*row, false);
+    let unlinked_pairs = |round: &[RawGroup]| {
+        let mut pairs = vec![];
+        for g in round {
+            for (i, u) in g.iter().enumerate() {
+                pairs.extend(g[..i].iter().filter(|v| unit[**v] != unit[*u]).map(|v| (*u, *v)));
+            }
+        }
+        pairs
+    };
+    let commit = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        for (u, v) in unlinked_pairs(round) {
+            conflicts.add_conflict(u, v);
+        }
+    };
+    let rollback = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        for (u, v) in unlinked_pairs(round) {
+            conflicts.remove_conflict(u, v);
+        }
+    };
+    // Like a round without pairs, a round of whole units commits nothing and
+    // could be repeated indefinitely, so it ends the assignment
+    let next_rounds = |conflicts: &mut ConflictMatrix, curr: &[Vec<RawGroup>], depth: usize| {
+        if curr.len() == depth || curr.last().is_some_and(|round| unlinked_pairs(round).is_empty()) {
+            return vec![];
+        }
+        let round = history.len() + curr.len();
+        let allows = |slot: Slot, partial: &[usize], candidate| {
+            let slot = Slot { round, ..slot };
+            allows(slot, partial, candidate) && (!slot.completed_by(partial) || whole_units(&with_member(partial, candidate)))
+        };
+        rounds_excluding(conflicts, group_sizes, bitvec![0; n], allows)
+    };
+
+    commit_history(conflicts, min_group_size, history, commit, rollback)?;
+    let mut best = 0;
+    explore_rounds(conflicts, |conflicts, curr| next_rounds(conflicts, curr, usize::MAX), commit, rollback, |curr| {
+        best = best.max(curr.len());
+        ControlFlow::Continue(())
+    });
+    let mut stopped = None;
+    let mut full = history.to_vec();
+    explore_rounds(conflicts, |conflicts, curr| next_rounds(conflicts, curr, best), commit, rollback, |curr| {
+        if curr.len() < best {
+            return ControlFlow::Continue(());
+        }
+        full.truncate(history.len());
+        full.extend_from_slice(curr);
+        visit(&full).map_break(|value| stopped = Some(value))
+    });
+    for round in history.iter().rev() {
+        rollback(conflicts, round);
     }
+    Ok(stopped)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use std::collections::HashSet;
+/// Generate the *maximum-round* assignments (as in [`make_assignments()`])
+/// in which no group contains every member of a set in `forbidden`.
+///
+/// This rules out combinations which are pairwise conflict-free but should
+/// not all be together, such as three people who get along individually. A
+/// forbidden set of two is the same as a conflict, and a set of one or none
+/// rules out every group, leaving no valid round. Sets which are part of the
+/// problem itself can instead be stored in the matrix as
+/// [hyperedges](ConflictMatrix::add_hyperedge), which every solver respects.
+///
+/// ```
+/// # use group_generator::{make_assignments_forbidding, ConflictMatrix};
+/// let sols = make_assignments_forbidding(&mut ConflictMatrix::empty(6), 3, &[vec![0, 1, 2]]).unwrap();
+/// assert!(sols.iter().flatten().flatten().all(|g| !(g.contains(&0) && g.contains(&1) && g.contains(&2))));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::VertexOutOfRange`] if a set refers to a vertex which does
+///   not exist.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`].
+pub fn make_assignments_forbidding(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    forbidden: &[Vec<usize>],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    check_vertices(conflicts.len(), forbidden.iter().flatten().copied())?;
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut sols = vec![];
+    let allows = |_, partial: &[usize], candidate| !completes_forbidden(partial, candidate, forbidden);
+    visit_maximal_filtered::<()>(conflicts, &[group_sizes], allows, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    });
+    Ok(sols)
+}
 
-    fn diagonal(n: usize) -> Vec<BitVec> {
-        (0..n).map(|i| {
-                let mut vec = bitvec![0; n];
-                vec.set(i, true);
-                vec
-            }).collect()
-    }
+/// Whether adding `candidate` to `partial` would complete a set in
+/// `forbidden`.
+pub(crate) fn completes_forbidden(partial: &[usize], candidate: usize, forbidden: &[Vec<usize>]) -> bool {
+    forbidden.iter().any(|set| set.iter().all(|v| *v == candidate || partial.contains(v)))
+}
 
-    #[test]
-    fn no_conflicts() {
-        let n = 5;
-        let k = 3;
-        let mut conflicts = diagonal(n);
-        let skip = bitvec![0; n];
-        let res = potential_groups(&mut conflicts, k, &skip);
-        assert_eq!(res.len(), 10);
+/// Generate the *maximum-round* assignments where every group is drawn from
+/// a pool of pre-approved `candidates`.
+///
+/// Each round is an exact cover of the vertices by candidates, none of which
+/// groups a conflicting pair or a pair which met in an earlier round. Group
+/// sizes come from the candidates themselves, so there is no minimum group
+/// size or round shape. Within a round, groups are listed by least member,
+/// so each cover appears once rather than once per ordering of its groups.
+///
+/// Rounds of singletons end the assignment, as in [`make_assignments()`].
+///
+/// ```
+/// # use group_generator::{make_assignments_from_candidates, ConflictMatrix};
+/// let candidates = [vec![0, 1], vec![2, 3], vec![0, 2], vec![1, 3], vec![0, 3]];
+/// let sols = make_assignments_from_candidates(&mut ConflictMatrix::empty(4), &candidates).unwrap();
+/// assert_eq!(sols.len(), 2);
+/// assert!(sols.iter().all(|a| a.len() == 2));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::NoVertices`] if `conflicts` is empty.
+/// - [`GroupError::VertexOutOfRange`] if a candidate refers to a vertex
+///   which does not exist.
+pub fn make_assignments_from_candidates(
+    conflicts: &mut ConflictMatrix,
+    candidates: &[Vec<usize>],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    if conflicts.is_empty() {
+        return Err(GroupError::NoVertices);
     }
+    check_vertices(conflicts.len(), candidates.iter().flatten().copied())?;
+    let mut pool: Vec<RawGroup> = candidates
+        .iter()
+        .filter(|g| !g.is_empty())
+        .map(|g| {
+            let mut g = g.clone();
+            g.sort_unstable();
+            g.dedup();
+            g
+        })
+        .collect();
+    pool.sort_unstable();
+    pool.dedup();
+    let next_rounds = |conflicts: &mut ConflictMatrix| candidate_rounds(conflicts, &pool);
+    Ok(maximal_assignments(conflicts, next_rounds, commit_round, rollback_round))
+}
 
-    fn test_single_assignment(n: usize, k: usize) {
-        let mut conflicts = diagonal(n);
-        let group_sizes = group_sizes(n, k);
-        let res = single_assignment(&mut conflicts, &group_sizes);
-        for groups in res {
-            let mut seen: HashSet<usize> = HashSet::new();
-            let mut count = 0;
-            for g in groups {
-                count += g.len();
-                seen.extend(&g);
+/// Enumerate the exact covers of the vertices by the conflict-free groups of
+/// `pool`, with groups ordered by least member.
+fn candidate_rounds(conflicts: &ConflictMatrix, pool: &[RawGroup]) -> Vec<Vec<RawGroup>> {
+    let n = conflicts.len();
+    let usable: Vec<&RawGroup> = pool
+        .iter()
+        .filter(|g| !conflicts.is_group_conflicted(g))
+        .collect();
+    // The next group must cover the least uncovered vertex
+    let options = |covered: &BitVec| -> Vec<usize> {
+        let Some(v) = covered.first_zero() else {
+            return vec![];
+        };
+        (0..usable.len())
+            .filter(|i| usable[*i].contains(&v) && usable[*i].iter().all(|u| !covered[*u]))
+            .collect()
+    };
+    let mut res = vec![];
+    let mut covered = bitvec![0; n];
+    let mut curr: Vec<usize> = vec![];
+    let mut stack = vec![Frame::new(options(&covered))];
+    while let Some(frame) = stack.last_mut() {
+        let Some(i) = frame.next_option() else {
+            stack.pop();
+            if let Some(i) = curr.pop() {
+                for v in usable[i] {
+                    covered.set(*v, false);
+                }
             }
-            assert_eq!(count, n);
-            assert_eq!(seen.len(), count);
+            continue;
+        };
+        for v in usable[i] {
+            covered.set(*v, true);
         }
-    }
-
-    #[test]
-    fn odd_complete_graph_single_assignment() {
-        let tests = [
-            (3, 2),
-            (5, 2),
-        ];
-        for (n, k) in tests {
-            test_single_assignment(n, k);
+        curr.push(i);
+        if covered.all() {
+            res.push(curr.iter().map(|i| usable[*i].clone()).collect());
+            for v in usable[i] {
+                covered.set(*v, false);
+            }
+            curr.pop();
+        } else {
+            stack.push(Frame::new(options(&covered)));
         }
     }
+    res
+}
 
-    #[test]
-    fn even_complete_graph_single_assignment() {
-        let tests = [
-            (2, 2),
+/// Fixes a vertex to a group slot: [`vertex`](Pin::vertex) must be in group
+/// [`group`](Pin::group) of round [`round`](Pin::round), counting from 0.
+///
+/// Group slots follow the round shape, so slot `i` has `group_sizes[i]`
+/// members (see [`group_sizes()`]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pin {
+    pub vertex: usize,
+    pub round: usize,
+    pub group: usize,
+}
+
+/// Generate the *maximum-round* assignments (as in [`make_assignments()`])
+/// which respect every pin, placing each pinned vertex in its group slot.
+///
+/// Groups are only formed in a slot if they hold every vertex pinned there
+/// and no vertex pinned elsewhere in that round. Pins on rounds past the end
+/// of the schedule have no effect, so pinning can shorten the schedule rather
+/// than ruling it out. A vertex pinned to two slots of the same round leaves
+/// no valid round there.
+///
+/// ```
+/// # use group_generator::{make_assignments_pinned, ConflictMatrix, Pin};
+/// let pin = Pin { vertex: 5, round: 0, group: 0 };
+/// let sols = make_assignments_pinned(&mut ConflictMatrix::empty(6), 3, &[pin]).unwrap();
+/// assert!(sols.iter().all(|a| a[0][0].contains(&5)));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::VertexOutOfRange`] if a pin refers to a vertex which does
+///   not exist.
+/// - [`GroupError::GroupIndexOutOfRange`] if a pin refers to a group slot
+///   past the end of the round shape.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`].
+pub fn make_assignments_pinned(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    pins: &[Pin],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut sols = vec![];
+    visit_maximal_pinned::<()>(conflicts, &group_sizes, pins, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    })?;
+    Ok(sols)
+}
+
+/// [`visit_maximal()`] respecting `pins` (see [`make_assignments_pinned()`]).
+pub(crate) fn visit_maximal_pinned<B>(
+    conflicts: &mut ConflictMatrix,
+    group_sizes: &[usize],
+    pins: &[Pin],
+    visit: impl FnMut(&[Vec<RawGroup>]) -> ControlFlow<B>,
+) -> Result<Option<B>, GroupError> {
+    check_vertices(conflicts.len(), pins.iter().map(|pin| pin.vertex))?;
+    for pin in pins {
+        if pin.group >= group_sizes.len() {
+            return Err(GroupError::GroupIndexOutOfRange { group: pin.group, num_groups: group_sizes.len() });
+        }
+    }
+    let allows = |slot: Slot, partial: &[usize], candidate: usize| {
+        let mut pinned = pins.iter().filter(|pin| pin.round == slot.round);
+        if slot.completed_by(partial) {
+            let group = with_member(partial, candidate);
+            pinned.all(|pin| (pin.group == slot.index) == group.contains(&pin.vertex))
+        } else {
+            pinned.all(|pin| pin.vertex != candidate || pin.group == slot.index)
+        }
+    };
+    Ok(visit_maximal_filtered(conflicts, &[group_sizes.to_vec()], allows, visit))
+}
+
+/// Generate the *maximum-round* assignments (as in [`make_assignments()`])
+/// where `hosts[i]` stays in group slot `i` in every round while everyone
+/// else rotates, as with the table hosts of a progressive dinner.
+///
+/// Group slots follow the round shape, as for a [`Pin`]. Slots past the end
+/// of `hosts` have no host. Since hosts never leave their slots, they never
+/// meet one another, and a vertex hosting two slots leaves no valid round.
+///
+/// ```
+/// # use group_generator::{make_assignments_with_hosts, ConflictMatrix};
+/// let sols = make_assignments_with_hosts(&mut ConflictMatrix::empty(6), 2, &[4, 0]).unwrap();
+/// assert!(sols.iter().flatten().all(|round| round[0].contains(&4) && round[1].contains(&0)));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::VertexOutOfRange`] if a host does not exist.
+/// - [`GroupError::GroupIndexOutOfRange`] if there are more hosts than
+///   group slots.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`].
+pub fn make_assignments_with_hosts(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    hosts: &[usize],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    check_hosts(conflicts.len(), group_sizes.len(), hosts)?;
+    let mut sols = vec![];
+    let allows = |slot, partial: &[usize], candidate| hosts_allow(hosts, slot, partial, candidate);
+    visit_maximal_filtered::<()>(conflicts, &[group_sizes], allows, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    });
+    Ok(sols)
+}
+
+/// Check that every host exists and has a group slot to stay in.
+pub(crate) fn check_hosts(n: usize, num_groups: usize, hosts: &[usize]) -> Result<(), GroupError> {
+    check_vertices(n, hosts.iter().copied())?;
+    if hosts.len() > num_groups {
+        return Err(GroupError::GroupIndexOutOfRange { group: num_groups, num_groups });
+    }
+    Ok(())
+}
+
+/// Whether `candidate` may join `partial` in `slot` with `hosts[i]` kept in
+/// slot `i` (see [`make_assignments_with_hosts()`]).
+///
+/// Members are added in ascending order, so a group which has passed its
+/// host without taking it can never get it, and is abandoned straight away.
+pub(crate) fn hosts_allow(hosts: &[usize], slot: Slot, partial: &[usize], candidate: usize) -> bool {
+    if let Some(hosted) = hosts.iter().position(|h| *h == candidate) {
+        return hosted == slot.index && hosts[slot.index + 1..].iter().all(|h| *h != candidate);
+    }
+    match hosts.get(slot.index) {
+        Some(host) if !partial.contains(host) => *host > candidate && !slot.completed_by(partial),
+        _ => true,
+    }
+}
+
+/// Check that every vertex index is below `n`.
+pub(crate) fn check_vertices(n: usize, vertices: impl IntoIterator<Item = usize>) -> Result<(), GroupError> {
+    match vertices.into_iter().find(|v| *v >= n) {
+        Some(vertex) => Err(GroupError::VertexOutOfRange { vertex, n }),
+        None => Ok(()),
+    }
+}
+
+/// Check that `group_sizes` is a valid round shape for `n` vertices.
+pub(crate) fn validate_shape(n: usize, group_sizes: &[usize]) -> Result<(), GroupError> {
+    if n == 0 {
+        return Err(GroupError::NoVertices);
+    }
+    if group_sizes.contains(&0) || group_sizes.iter().sum::<usize>() != n {
+        return Err(GroupError::InvalidGroupShape { n });
+    }
+    Ok(())
+}
+
+/// Validate the inputs shared by all solver entry points.
+pub(crate) fn validate_problem(conflicts: &ConflictMatrix, min_group_size: usize) -> Result<(), GroupError> {
+    let n = conflicts.len();
+    if n == 0 {
+        return Err(GroupError::NoVertices);
+    }
+    if min_group_size == 0 || min_group_size > n {
+        return Err(GroupError::InvalidMinGroupSize { min_group_size, n });
+    }
+    Ok(())
+}
+
+/// Panicking form of [`validate_problem()`], for the infallible entry points.
+pub(crate) fn check_problem(conflicts: &ConflictMatrix, min_group_size: usize) {
+    if let Err(err) = validate_problem(conflicts, min_group_size) {
+        panic!("{err}");
+    }
+}
+
+/// The round placing each of the `n` vertices in its own group.
+fn singletons(n: usize) -> Vec<RawGroup> {
+    (0..n).map(|v| vec![v]).collect()
+}
+
+/// Collect every assignment reaching the maximal depth of the round-level
+/// search (see [`explore_rounds()`]).
+fn maximal_assignments(
+    conflicts: &mut ConflictMatrix,
+    mut next_rounds: impl FnMut(&mut ConflictMatrix) -> Vec<Vec<RawGroup>>,
+    commit: impl Fn(&mut ConflictMatrix, &[RawGroup]),
+    rollback: impl Fn(&mut ConflictMatrix, &[RawGroup]),
+) -> Vec<Vec<Vec<RawGroup>>> {
+    let mut sols = vec![];
+    let mut best = 0;
+    explore_rounds(conflicts, |conflicts, _| next_rounds(conflicts), commit, rollback, |curr| {
+        if curr.len() >= best {
+            if curr.len() > best {
+                sols.clear();
+            }
+            sols.push(curr.to_vec());
+            best = curr.len();
+        }
+        ControlFlow::Continue(())
+    });
+    sols
+}
+
+/// Round-level backtracking shared by the solver entry points.
+///
+/// Repeatedly asks `next_rounds` for the rounds available under the current
+/// conflict state (given the rounds committed so far), applying `commit`
+/// before descending into a round and `rollback` when backing out of it.
+/// Whenever no further round is available, the sequence of rounds leading
+/// there is handed to `on_leaf`, which may stop the search early. The
+/// conflicts are restored either way.
+fn explore_rounds<V: VertexIndex>(
+    conflicts: &mut ConflictMatrix,
+    mut next_rounds: impl FnMut(&mut ConflictMatrix, &[Vec<RawGroup<V>>]) -> Vec<Vec<RawGroup<V>>>,
+    commit: impl Fn(&mut ConflictMatrix, &[RawGroup<V>]),
+    rollback: impl Fn(&mut ConflictMatrix, &[RawGroup<V>]),
+    mut on_leaf: impl FnMut(&[Vec<RawGroup<V>>]) -> ControlFlow<()>,
+) {
+    let mut curr: Vec<Vec<RawGroup<V>>> = vec![];
+
+    // Explicit stack of round options, one frame per committed round (plus the
+    // root). Frame `i` holds the options available after `curr[..i]` has been
+    // committed and the index of the next option to try.
+    let mut stack = vec![Frame::new(next_rounds(conflicts, &curr))];
+    if stack[0].options.is_empty() {
+        let _ = on_leaf(&curr);
+    }
+    while let Some(frame) = stack.last_mut() {
+        let Some(opt) = frame.next_option() else {
+            stack.pop();
+            if let Some(opt) = curr.pop() {
+                rollback(conflicts, &opt);
+            }
+            continue;
+        };
+        // A round without any pairs commits no conflicts, so it could be
+        // repeated indefinitely. Treat it as the end of the assignment instead.
+        let is_stuck = opt.iter().all(|g| g.len() < 2);
+        commit(conflicts, &opt);
+        curr.push(opt);
+        let options = if is_stuck { vec![] } else { next_rounds(conflicts, &curr) };
+        if !options.is_empty() {
+            stack.push(Frame::new(options));
+            continue;
+        }
+        if on_leaf(&curr).is_break() {
+            for opt in curr.iter().rev() {
+                rollback(conflicts, opt);
+            }
+            return;
+        }
+        if let Some(opt) = curr.pop() {
+            rollback(conflicts, &opt);
+        }
+    }
+}
+
+/// Permanently mark every pair grouped together in `round` as conflicting.
+pub(crate) fn commit_round<V: VertexIndex>(conflicts: &mut ConflictMatrix, round: &[RawGroup<V>]) {
+    for g in round {
+        add_conflicts_between(conflicts, g);
+    }
+}
+
+/// Check that `round` places every vertex exactly once, in groups of at
+/// least `min_group_size` which are not
+/// [conflicted](ConflictMatrix::is_group_conflicted).
+pub(crate) fn check_round(conflicts: &ConflictMatrix, min_group_size: usize, round: &[RawGroup]) -> Result<(), GroupError> {
+    if !validate_assignment(&[round], conflicts.len(), min_group_size) {
+        return Err(GroupError::InvalidRound);
+    }
+    if round.iter().any(|g| conflicts.is_group_conflicted(g)) {
+        return Err(GroupError::InvalidRound);
+    }
+    Ok(())
+}
+
+/// Undo the conflicts added by [`commit_round()`].
+pub(crate) fn rollback_round<V: VertexIndex>(conflicts: &mut ConflictMatrix, round: &[RawGroup<V>]) {
+    for g in round {
+        remove_conflicts_between(conflicts, g);
+    }
+}
+
+/// One level of an explicit backtracking stack: the candidates available at
+/// this depth and a cursor to the next one to try.
+#[derive(Debug, Clone)]
+struct Frame<T> {
+    options: Vec<T>,
+    next: usize,
+}
+
+impl<T: Default> Frame<T> {
+    fn new(options: Vec<T>) -> Self {
+        Self { options, next: 0 }
+    }
+
+    /// Take ownership of the next untried candidate, if any remain.
+    fn next_option(&mut self) -> Option<T> {
+        let opt = self.options.get_mut(self.next).map(core::mem::take);
+        self.next += 1;
+        opt
+    }
+}
+
+/// Hit/miss counters for the round cache used by
+/// [`make_assignments_memoized()`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CacheStats {
+    pub hits: usize,
+    pub misses: usize,
+}
+
+#[cfg(feature = "std")]
+impl CacheStats {
+    /// Fraction of round enumerations answered from the cache.
+    pub fn hit_rate(&self) -> f64 {
+        let total = self.hits + self.misses;
+        if total == 0 {
+            return 0.0;
+        }
+        self.hits as f64 / total as f64
+    }
+}
+
+/// Generate all *maximum-round* assignments (as in [`make_assignments()`]),
+/// memoizing the rounds available from each conflict state.
+///
+/// Committing the same set of rounds in a different order leads to the same
+/// conflict state, so deeper levels of the search repeatedly enumerate
+/// identical rounds. Here, the result of [`single_assignment()`] is cached
+/// keyed by the full conflict matrix, which is the only state it depends on
+/// (group sizes are fixed for the whole run).
+///
+/// The cache holds every distinct state's round list for the duration of the
+/// call and can grow very large, so this is opt-in. It pays off for
+/// structured inputs where many orderings of the same rounds are explored.
+/// Results are identical to [`make_assignments()`], returned alongside the
+/// cache's hit/miss counts.
+#[cfg(feature = "std")]
+pub fn make_assignments_memoized(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+) -> (Vec<Vec<Vec<RawGroup>>>, CacheStats) {
+    check_problem(conflicts, min_group_size);
+
+    let mut stats = CacheStats::default();
+    if min_group_size == 1 {
+        return (vec![vec![singletons(conflicts.len())]], stats);
+    }
+    if !can_form_any_round(conflicts, min_group_size) {
+        return (vec![vec![]], stats);
+    }
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut cache: HashMap<ConflictMatrix, Vec<Vec<RawGroup>>> = HashMap::new();
+    let next_rounds = |conflicts: &mut ConflictMatrix| {
+        if let Some(rounds) = cache.get(conflicts) {
+            stats.hits += 1;
+            return rounds.clone();
+        }
+        stats.misses += 1;
+        let rounds = single_assignment(conflicts, &group_sizes);
+        cache.insert(conflicts.clone(), rounds.clone());
+        rounds
+    };
+    let sols = maximal_assignments(conflicts, next_rounds, commit_round, rollback_round);
+    (sols, stats)
+}
+
+/// Optional pruning used by [`make_assignments_with_stats()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchOptions {
+    /// Abandon a branch once the rounds committed so far plus
+    /// [`max_rounds_upper_bound()`] on the remaining conflicts cannot reach
+    /// the best depth found so far.
+    pub bound_pruning: bool,
+    /// Only explore canonical rounds, in ascending order (as in
+    /// [`make_assignments_lex_min()`]), so each distinct schedule is reached
+    /// once instead of once per ordering of its groups and rounds.
+    pub symmetry_breaking: bool,
+}
+
+/// Counters collected by [`make_assignments_with_stats()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct SearchStats {
+    /// Search nodes expanded, i.e. conflict states whose next rounds were
+    /// enumerated.
+    pub nodes_visited: usize,
+    /// Nodes abandoned by [`SearchOptions::bound_pruning`].
+    pub branches_pruned_by_bound: usize,
+    /// Candidate rounds skipped by [`SearchOptions::symmetry_breaking`]
+    /// because they were not greater than the previous round.
+    pub branches_pruned_by_symmetry: usize,
+    /// Number of maximal assignments returned.
+    pub solutions_found: usize,
+}
+
+/// Generate *maximum-round* assignments with the pruning in `options`
+/// enabled, returning the search counters alongside them.
+///
+/// With no options enabled, the results are identical to
+/// [`make_assignments()`]. Bound pruning never changes the results. Symmetry
+/// breaking returns each distinct schedule exactly once, in canonical form
+/// (see [`canonical_assignment()`]).
+///
+/// The counters live in this variant only, so the plain solver pays nothing
+/// for them.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_with_stats(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    options: SearchOptions,
+) -> (Vec<Vec<Vec<RawGroup>>>, SearchStats) {
+    check_problem(conflicts, min_group_size);
+
+    let mut stats = SearchStats::default();
+    let sols = if min_group_size == 1 {
+        vec![vec![singletons(conflicts.len())]]
+    } else if !can_form_any_round(conflicts, min_group_size) {
+        vec![vec![]]
+    } else {
+        let group_sizes = group_sizes(conflicts.len(), min_group_size);
+        // Read while choosing rounds and updated at the leaves
+        let best = Cell::new(0);
+        let mut sols = vec![];
+        explore_rounds(
+            conflicts,
+            |conflicts, curr| {
+                stats.nodes_visited += 1;
+                if options.bound_pruning && curr.len() + max_rounds_upper_bound(conflicts, min_group_size) < best.get() {
+                    stats.branches_pruned_by_bound += 1;
+                    return vec![];
+                }
+                if !options.symmetry_breaking {
+                    return single_assignment(conflicts, &group_sizes);
+                }
+                let mut rounds = canonical_rounds(conflicts, &group_sizes);
+                if let Some(prev) = curr.last() {
+                    let before = rounds.len();
+                    rounds.retain(|round| round > prev);
+                    stats.branches_pruned_by_symmetry += before - rounds.len();
+                }
+                rounds
+            },
+            commit_round,
+            rollback_round,
+            |curr| {
+                if curr.len() >= best.get() {
+                    if curr.len() > best.get() {
+                        sols.clear();
+                    }
+                    sols.push(curr.to_vec());
+                    best.set(curr.len());
+                }
+                ControlFlow::Continue(())
+            },
+        );
+        sols
+    };
+    stats.solutions_found = sols.len();
+    (sols, stats)
+}
+
+/// Generate all *maximum-round* assignments (as in [`make_assignments()`]),
+/// ranked best-first by partner spread.
+///
+/// Among the maximal tie set, assignments where vertices meet the most distinct
+/// partners come first (see [`rank_by_partner_spread()`]). Under strict
+/// no-repeat the total is fixed by the round shape, so the ranking is mostly
+/// decided by which assignment leaves its worst-off vertex with the most
+/// partners.
+///
+/// If `best_only` is set, only the top-ranked assignment is returned.
+pub fn make_assignments_by_partner_spread(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    best_only: bool,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    let mut sols = make_assignments(conflicts, min_group_size);
+    rank_by_partner_spread(&mut sols, conflicts.len());
+    if best_only {
+        sols.truncate(1);
+    }
+    sols
+}
+
+/// Generate all *maximum-round* assignments (as in [`make_assignments()`]),
+/// ranked best-first by how much pairwise affinity they satisfy.
+///
+/// Affinity is a soft preference: `affinity[u][v]` is how much `u` would like
+/// to share a group with `v` (see [`affinity_score()`]). Unlike conflicts it
+/// never removes or forces a grouping, so the rounds achieved are exactly
+/// those of [`make_assignments()`], and only the order of the tie set
+/// changes.
+///
+/// # Panics
+///
+/// Panics if `affinity` is smaller than `n x n`, or under the same conditions
+/// as [`make_assignments()`].
+pub fn make_assignments_affinity(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    affinity: &[Vec<u32>],
+) -> Vec<Vec<Vec<RawGroup>>> {
+    let n = conflicts.len();
+    assert!(
+        affinity.len() >= n && affinity.iter().all(|row| row.len() >= n),
+        "Affinity matrix must cover all {n} vertices."
+    );
+    let mut sols = make_assignments(conflicts, min_group_size);
+    rank_by_affinity(&mut sols, affinity);
+    sols
+}
+
+/// Generate only the *maximum-round* assignments (as in
+/// [`make_assignments()`]) satisfying the most pairwise affinity.
+///
+/// This is the top of [`make_assignments_affinity()`]'s ranking, every
+/// assignment tied for the highest [`affinity_score()`], but the assignments
+/// are scored as they are found, so the rest are never collected.
+///
+/// ```
+/// # use group_generator::{make_assignments_max_affinity, ConflictMatrix};
+/// let mut affinity = vec![vec![0; 5]; 5];
+/// affinity[0][1] = 1;
+/// let sols = make_assignments_max_affinity(&mut ConflictMatrix::empty(5), 2, &affinity);
+/// assert!(sols.iter().flatten().flatten().any(|g| g.contains(&0) && g.contains(&1)));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `affinity` is smaller than `n x n`, or under the same conditions
+/// as [`make_assignments()`].
+pub fn make_assignments_max_affinity(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    affinity: &[Vec<u32>],
+) -> Vec<Vec<Vec<RawGroup>>> {
+    let n = conflicts.len();
+    assert!(
+        affinity.len() >= n && affinity.iter().all(|row| row.len() >= n),
+        "Affinity matrix must cover all {n} vertices."
+    );
+    let mut sols = vec![];
+    let mut highest = 0;
+    make_assignments_with::<()>(conflicts, min_group_size, |assignment| {
+        let score = affinity_score(assignment, affinity);
+        if sols.is_empty() || score >= highest {
+            if score > highest {
+                sols.clear();
+            }
+            sols.push(assignment.to_vec());
+            highest = score;
+        }
+        ControlFlow::Continue(())
+    });
+    sols
+}
+
+/// Generate the *maximum-round* assignments (as in [`make_assignments()`])
+/// breaking the least total penalty of soft conflicts.
+///
+/// Hard conflicts in `conflicts` stay forbidden, while a pair with a positive
+/// `penalties[u][v]` may still share a group at that cost (see
+/// [`soft_conflict_penalty()`]). Soft conflicts never cost rounds: only
+/// assignments reaching [`max_rounds()`] are considered, and among them those
+/// with the lowest penalty are returned. Since penalties only add up, a
+/// branch is abandoned once it costs more than the best assignment so far.
+///
+/// ```
+/// # use group_generator::{make_assignments_soft_conflicts, soft_conflict_penalty, ConflictMatrix};
+/// let mut penalties = vec![vec![0; 5]; 5];
+/// penalties[0][1] = 1;
+/// let sols = make_assignments_soft_conflicts(&mut ConflictMatrix::empty(5), 2, &penalties);
+/// assert!(sols.iter().all(|a| soft_conflict_penalty(a, &penalties) == 0));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `penalties` is smaller than `n x n`, or under the same
+/// conditions as [`make_assignments()`].
+pub fn make_assignments_soft_conflicts(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    penalties: &[Vec<u32>],
+) -> Vec<Vec<Vec<RawGroup>>> {
+    let n = conflicts.len();
+    assert!(
+        penalties.len() >= n && penalties.iter().all(|row| row.len() >= n),
+        "Penalty matrix must cover all {n} vertices."
+    );
+    let best = max_rounds(conflicts, min_group_size);
+    if min_group_size == 1 {
+        return vec![vec![singletons(n)]];
+    }
+    let group_sizes = group_sizes(n, min_group_size);
+    let round_penalty = |round: &[RawGroup]| round.iter().map(|g| analysis::group_affinity(g, penalties)).sum::<u64>();
+    // Penalty of the rounds committed so far, and of the best assignment yet
+    let spent = Cell::new(0);
+    let lowest = Cell::new(u64::MAX);
+    let mut sols = vec![];
+    explore_rounds(
+        conflicts,
+        |conflicts, curr| {
+            if curr.len() == best || spent.get() > lowest.get() {
+                return vec![];
+            }
+            single_assignment(conflicts, &group_sizes)
+        },
+        |conflicts, round| {
+            commit_round(conflicts, round);
+            spent.set(spent.get() + round_penalty(round));
+        },
+        |conflicts, round| {
+            rollback_round(conflicts, round);
+            spent.set(spent.get() - round_penalty(round));
+        },
+        |curr| {
+            if curr.len() == best && spent.get() <= lowest.get() {
+                if spent.get() < lowest.get() {
+                    sols.clear();
+                    lowest.set(spent.get());
+                }
+                sols.push(curr.to_vec());
+            }
+            ControlFlow::Continue(())
+        },
+    );
+    sols
+}
+
+/// Generate all *maximum-round* assignments (as in [`make_assignments()`])
+/// which stay valid if up to `buffer` members of each group drop out.
+///
+/// Every group is planned with at least `buffer` spare members beyond
+/// `min_group_size`, so removing up to `buffer` members from a group still
+/// leaves it at the minimum. A `buffer` of 1 survives any single vertex
+/// dropping out (see [`dropout_robustness()`]), while `buffer == 0` adds no
+/// slack at all and is just [`make_assignments()`].
+///
+/// # Panics
+///
+/// Panics if `min_group_size + buffer` exceeds the number of vertices, or
+/// under the same conditions as [`make_assignments()`].
+pub fn make_assignments_robust(conflicts: &mut ConflictMatrix, min_group_size: usize, buffer: usize) -> Vec<Vec<Vec<RawGroup>>> {
+    make_assignments(conflicts, min_group_size + buffer)
+}
+
+/// Generate all *maximum-round* assignments (as in [`make_assignments()`])
+/// where no vertex ever meets more than `max_partners` distinct people.
+///
+/// # Sit-Out Semantics
+///
+/// Once a vertex has used up its partner budget it cannot be grouped with
+/// anyone new, and under no-repeat it cannot be grouped with anyone it has
+/// already met either, so it **sits out**:
+///
+/// - A vertex sits out of a round if joining even a minimum-size group would
+///   take it past `max_partners`.
+/// - The remaining (active) vertices are partitioned as usual, with group
+///   sizes planned from the number of active vertices, and a vertex is only
+///   placed in a group small enough to stay within its budget.
+/// - The schedule ends once the active vertices cannot be partitioned.
+///
+/// Rounds therefore only contain the active vertices. Conflicts present before
+/// the first round do not count towards anyone's budget. For budgets which
+/// differ between vertices, see [`make_assignments_partner_budgets()`].
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_partner_cap(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    max_partners: usize,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    partner_budget_assignments(conflicts, min_group_size, |_| max_partners)
+}
+
+/// [`make_assignments_partner_cap()`] where each vertex `v` has its own
+/// budget of `budgets[v]` distinct partners, e.g. for contact-limiting rules
+/// which are stricter for some people than others.
+///
+/// ```
+/// # use group_generator::{distinct_partners, make_assignments_partner_budgets, ConflictMatrix};
+/// let sols = make_assignments_partner_budgets(&mut ConflictMatrix::empty(4), 2, &[1, 3, 3, 3]).unwrap();
+/// assert!(sols.iter().all(|a| distinct_partners(a, 4)[0] <= 1));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::AttributeMismatch`] if there is not one budget for each
+///   vertex.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`].
+pub fn make_assignments_partner_budgets(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    budgets: &[usize],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    if budgets.len() != conflicts.len() {
+        return Err(GroupError::AttributeMismatch { len: budgets.len(), n: conflicts.len() });
+    }
+    Ok(partner_budget_assignments(conflicts, min_group_size, |v| budgets[v]))
+}
+
+/// The search behind [`make_assignments_partner_cap()`], where vertex `v`
+/// may meet `budget(v)` distinct partners.
+fn partner_budget_assignments(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    budget: impl Fn(usize) -> usize,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    let n = conflicts.len();
+    if min_group_size == 1 {
+        return vec![vec![singletons(n)]];
+    }
+    // Committed rounds only ever add conflicts, so the partners each vertex
+    // has met so far are exactly the conflicts added since the start.
+    let initial: Vec<usize> = (0..n).map(|v| conflicts.degree(v)).collect();
+    let next_rounds = |conflicts: &mut ConflictMatrix| {
+        let mut sitting_out = bitvec![0; n];
+        let max_sizes: Vec<usize> = initial
+            .iter()
+            .enumerate()
+            .map(|(v, initial)| {
+                let budget = budget(v).saturating_sub(conflicts.degree(v) - initial);
+                if budget + 1 < min_group_size {
+                    sitting_out.set(v, true);
+                }
+                budget + 1
+            })
+            .collect();
+        let active = sitting_out.count_zeros();
+        let group_sizes = group_sizes(active, min_group_size);
+        rounds_excluding(conflicts, &group_sizes, sitting_out, |slot, _, v| max_sizes[v] >= slot.size)
+    };
+    maximal_assignments(conflicts, next_rounds, commit_round, rollback_round)
+}
+
+/// Generate all *maximum-round* assignments (as in [`make_assignments()`])
+/// where a pair may share a group up to `max_meetings` times.
+///
+/// Pairs which meet are only marked as conflicting once they have met
+/// `max_meetings` times, so `max_meetings == 1` is exactly
+/// [`make_assignments()`]. Conflicts present before the first round stay
+/// conflicting throughout.
+///
+/// Allowing repeats makes the search tree far larger (and the round bounds
+/// used by [`make_assignments()`] no longer apply), so this is only tractable
+/// for small instances.
+///
+/// # Panics
+///
+/// Panics if `max_meetings` is 0, or under the same conditions as
+/// [`make_assignments()`].
+pub fn make_assignments_with_repeats(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    max_meetings: usize,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    repeat_assignments(conflicts, min_group_size, max_meetings, false)
+}
+
+/// [`make_assignments_with_repeats()`] where every round after the first
+/// keeps each vertex with someone from its previous group (see
+/// [`has_continuity()`]), so groups evolve rather than reshuffle.
+///
+/// ```
+/// # use group_generator::{make_assignments_with_repeats_continuing, satisfies_continuity, ConflictMatrix};
+/// let sols = make_assignments_with_repeats_continuing(&mut ConflictMatrix::empty(4), 2, 2);
+/// assert!(sols.iter().all(|a| satisfies_continuity(a)));
+/// ```
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments_with_repeats()`].
+pub fn make_assignments_with_repeats_continuing(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    max_meetings: usize,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    repeat_assignments(conflicts, min_group_size, max_meetings, true)
+}
+
+/// The search behind [`make_assignments_with_repeats()`], keeping
+/// continuity between rounds if `continuity` is set.
+fn repeat_assignments(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    max_meetings: usize,
+    continuity: bool,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    assert!(max_meetings > 0, "Pairs must be allowed to meet at least once.");
+    let n = conflicts.len();
+    if min_group_size == 1 {
+        return vec![vec![singletons(n)]];
+    }
+    let meetings = MeetingCounts::new(n, max_meetings);
+    // The rounds committed so far, for the continuity check
+    let history: RefCell<Vec<Vec<RawGroup>>> = RefCell::new(vec![]);
+    let commit = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        meetings.commit(conflicts, round);
+        history.borrow_mut().push(round.to_vec());
+    };
+    let rollback = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        history.borrow_mut().pop();
+        meetings.rollback(conflicts, round);
+    };
+    let group_sizes = group_sizes(n, min_group_size);
+    let next_rounds = |conflicts: &mut ConflictMatrix| {
+        let history = history.borrow();
+        continuing_rounds(conflicts, &group_sizes, history.last().filter(|_| continuity))
+    };
+    maximal_assignments(conflicts, next_rounds, commit, rollback)
+}
+
+/// Every round shaped by `group_sizes` which keeps continuity with
+/// `previous` (see [`has_continuity()`]), checked as each group is completed,
+/// or every round at all when there is no previous round to continue.
+fn continuing_rounds(conflicts: &mut ConflictMatrix, group_sizes: &[usize], previous: Option<&Vec<RawGroup>>) -> Vec<Vec<RawGroup>> {
+    let Some(previous) = previous else {
+        return single_assignment(conflicts, group_sizes);
+    };
+    rounds_excluding(conflicts, group_sizes, bitvec![0; conflicts.len()], |slot, partial, candidate| {
+        !slot.completed_by(partial) || has_continuity(previous, &[with_member(partial, candidate)])
+    })
+}
+
+/// Generate the assignments of exactly `rounds` rounds repeating the fewest
+/// pairings, for when more rounds are needed than [`max_rounds()`] allows.
+///
+/// Conflicts in `conflicts` stay forbidden, but pairs which have already met
+/// may meet again, each meeting after a pair's first counting as one repeat.
+/// The result holds every assignment with the fewest repeats in total, so
+/// when `rounds` is reachable without any it matches
+/// [`make_assignments_exact_rounds()`]. Since repeats only add up, a branch
+/// is abandoned once it has more than the best assignment so far, but every
+/// round remains a candidate at every depth, so this is only tractable for
+/// small instances.
+///
+/// ```
+/// # use group_generator::{make_assignments_relaxed, meeting_counts, ConflictMatrix};
+/// // Four vertices only have 3 rounds of pairs, so one of them is repeated
+/// let sols = make_assignments_relaxed(&mut ConflictMatrix::empty(4), 2, 4);
+/// for a in &sols {
+///     let met_twice = meeting_counts(a, 4).iter().flatten().filter(|count| **count == 2).count();
+///     assert_eq!((a.len(), met_twice), (4, 2 * 2));
+/// }
+/// ```
+///
+/// If the conflicts rule out every round, no assignment is returned.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_relaxed(conflicts: &mut ConflictMatrix, min_group_size: usize, rounds: usize) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    let n = conflicts.len();
+    if min_group_size == 1 {
+        return vec![vec![singletons(n); rounds]];
+    }
+    // Meetings never make pairs conflicting, they only count as repeats
+    let meetings = MeetingCounts::new(n, usize::MAX);
+    let repeats_in = |round: &[RawGroup]| MeetingCounts::pairs(round).filter(|(u, v)| meetings.counts[u * n + v].get() > 0).count();
+    let repeats = Cell::new(0);
+    let fewest = Cell::new(usize::MAX);
+    let group_sizes = group_sizes(n, min_group_size);
+    let mut sols = vec![];
+    explore_rounds(
+        conflicts,
+        |conflicts, curr| {
+            if curr.len() == rounds || repeats.get() > fewest.get() {
+                return vec![];
+            }
+            single_assignment(conflicts, &group_sizes)
+        },
+        |conflicts, round| {
+            repeats.set(repeats.get() + repeats_in(round));
+            meetings.commit(conflicts, round);
+        },
+        |conflicts, round| {
+            meetings.rollback(conflicts, round);
+            repeats.set(repeats.get() - repeats_in(round));
+        },
+        |curr| {
+            if curr.len() == rounds && repeats.get() <= fewest.get() {
+                if repeats.get() < fewest.get() {
+                    sols.clear();
+                    fewest.set(repeats.get());
+                }
+                sols.push(curr.to_vec());
+            }
+            ControlFlow::Continue(())
+        },
+    );
+    sols
+}
+
+/// How many times each pair has met, for searches where pairs may meet up
+/// to `max_meetings` times. A pair only becomes conflicting once it has used
+/// up its meetings.
+struct MeetingCounts {
+    n: usize,
+    max_meetings: usize,
+    /// Number of times each pair `(u, v)` with `u < v` has met, at `u * n + v`
+    counts: Vec<Cell<usize>>,
+}
+
+impl MeetingCounts {
+    fn new(n: usize, max_meetings: usize) -> Self {
+        let counts = (0..n * n).map(|_| Cell::new(0)).collect();
+        Self { n, max_meetings, counts }
+    }
+
+    /// Every pair grouped in `round`, as `(u, v)` with `u < v`.
+    fn pairs(round: &[RawGroup]) -> impl Iterator<Item = (usize, usize)> + '_ {
+        round.iter().flat_map(|g| {
+            g.iter().enumerate().flat_map(move |(i, u)| g[..i].iter().map(move |v| (*u.min(v), *u.max(v))))
+        })
+    }
+
+    /// Count the meetings in `round`, marking pairs which have used up theirs.
+    fn commit(&self, conflicts: &mut ConflictMatrix, round: &[RawGroup]) {
+        for (u, v) in Self::pairs(round) {
+            let count = &self.counts[u * self.n + v];
+            count.set(count.get() + 1);
+            if count.get() == self.max_meetings {
+                conflicts.add_conflict(u, v);
+            }
+        }
+    }
+
+    /// Undo [`commit()`](MeetingCounts::commit).
+    fn rollback(&self, conflicts: &mut ConflictMatrix, round: &[RawGroup]) {
+        for (u, v) in Self::pairs(round) {
+            let count = &self.counts[u * self.n + v];
+            if count.get() == self.max_meetings {
+                conflicts.remove_conflict(u, v);
+            }
+            count.set(count.get() - 1);
+        }
+    }
+}
+
+/// Generate all assignments reaching the most rounds possible where pairs
+/// may meet up to `max_meetings` times (as in
+/// [`make_assignments_with_repeats()`]), but no group shares more than
+/// `max_overlap` members with any single group of the round before.
+///
+/// This keeps groups mixing quickly from one round to the next even where
+/// repeats are allowed, e.g. with `max_overlap == 1` nobody carries over a
+/// partner from the previous round. Without repeats (`max_meetings == 1`)
+/// every group already shares at most one member with each group before it,
+/// so any limit of at least 1 has no effect. With `max_overlap == 0` no
+/// round can follow the first.
+///
+/// ```
+/// # use group_generator::{make_assignments_with_overlap_limit, ConflictMatrix};
+/// let sols = make_assignments_with_overlap_limit(&mut ConflictMatrix::empty(4), 2, 2, 1);
+/// assert!(sols.iter().all(|a| a.len() == 6 && a.windows(2).all(|w| w[0] != w[1])));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `max_meetings` is 0, or under the same conditions as
+/// [`make_assignments()`].
+pub fn make_assignments_with_overlap_limit(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    max_meetings: usize,
+    max_overlap: usize,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    assert!(max_meetings > 0, "Pairs must be allowed to meet at least once.");
+    let n = conflicts.len();
+    if min_group_size == 1 {
+        return vec![vec![singletons(n)]];
+    }
+    let meetings = MeetingCounts::new(n, max_meetings);
+    // The index of each vertex's group in every round committed so far
+    let placements: RefCell<Vec<Vec<usize>>> = RefCell::new(vec![]);
+    let commit = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        meetings.commit(conflicts, round);
+        let mut group_of = vec![0; n];
+        for (i, g) in round.iter().enumerate() {
+            for v in g {
+                group_of[*v] = i;
+            }
+        }
+        placements.borrow_mut().push(group_of);
+    };
+    let rollback = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        placements.borrow_mut().pop();
+        meetings.rollback(conflicts, round);
+    };
+    let group_sizes = group_sizes(n, min_group_size);
+    let next_rounds = |conflicts: &mut ConflictMatrix| {
+        let placements = placements.borrow();
+        let Some(prev) = placements.last() else {
+            return single_assignment(conflicts, &group_sizes);
+        };
+        rounds_excluding(conflicts, &group_sizes, bitvec![0; n], |_, partial, candidate| {
+            partial.iter().filter(|u| prev[**u] == prev[candidate]).count() < max_overlap
+        })
+    };
+    maximal_assignments(conflicts, next_rounds, commit, rollback)
+}
+
+/// Generate all assignments reaching the most rounds possible where pairs
+/// may meet up to `max_meetings` times (as in
+/// [`make_assignments_with_repeats()`]), but every vertex meets at least
+/// `min_new` partners it has never met before in each round.
+///
+/// This stops repeats from recycling whole groups: a round only counts if it
+/// keeps introducing everyone to someone new. Conflicts present before the
+/// first round never count as new partners, since they can never meet. A
+/// `min_new` of at least the smallest group size leaves no valid round.
+///
+/// ```
+/// # use group_generator::{make_assignments_with_new_partners, ConflictMatrix};
+/// let sols = make_assignments_with_new_partners(&mut ConflictMatrix::empty(5), 2, 2, 1);
+/// assert!(sols.iter().all(|a| a.windows(2).all(|w| w[0] != w[1])));
+/// ```
+///
+/// # Panics
+///
+/// Panics if `max_meetings` is 0, or under the same conditions as
+/// [`make_assignments()`].
+pub fn make_assignments_with_new_partners(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    max_meetings: usize,
+    min_new: usize,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    assert!(max_meetings > 0, "Pairs must be allowed to meet at least once.");
+    let n = conflicts.len();
+    if min_group_size == 1 {
+        return vec![vec![singletons(n)]];
+    }
+    let meetings = MeetingCounts::new(n, max_meetings);
+    let unmet = |u: usize, v: usize| meetings.counts[u.min(v) * n + u.max(v)].get() == 0;
+    let group_sizes = group_sizes(n, min_group_size);
+    let next_rounds = |conflicts: &mut ConflictMatrix| {
+        rounds_excluding(conflicts, &group_sizes, bitvec![0; n], |slot, partial, candidate| {
+            if !slot.completed_by(partial) {
+                return true;
+            }
+            let group = with_member(partial, candidate);
+            group.iter().all(|u| group.iter().filter(|v| *v != u && unmet(*u, **v)).count() >= min_new)
+        })
+    };
+    maximal_assignments(conflicts, next_rounds, |c, r| meetings.commit(c, r), |c, r| meetings.rollback(c, r))
+}
+
+/// Generate all assignments reaching the most rounds possible when each round
+/// may leave up to `max_per_round` vertices sitting out, and no vertex sits
+/// out more than `max_per_vertex` times across the schedule.
+///
+/// The vertices which play in a round are split into [`group_sizes()`] of
+/// their number, and those sitting out appear in none of its groups. When
+/// the group sizes do not divide `n` nicely, resting a few vertices often
+/// unlocks extra rounds:
+///
+/// ```
+/// # use group_generator::{make_assignments, make_assignments_with_sit_outs, ConflictMatrix};
+/// assert_eq!(make_assignments(&mut ConflictMatrix::empty(3), 2)[0].len(), 1);
+/// let sols = make_assignments_with_sit_outs(&mut ConflictMatrix::empty(3), 2, 1, 1);
+/// assert!(sols.iter().all(|a| a.len() == 3));
+/// ```
+///
+/// With either limit 0, this is exactly [`make_assignments()`].
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_with_sit_outs(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    max_per_round: usize,
+    max_per_vertex: usize,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    sit_out_assignments(conflicts, min_group_size, max_per_round, max_per_vertex, |_, _| true)
+}
+
+/// Generate the assignments of [`make_assignments_with_sit_outs()`] where
+/// higher `priorities` are placed first, and nobody sits out while a vertex
+/// of lower priority could sit out instead.
+///
+/// In every round, the vertices sitting out are the ones with the lowest
+/// priority among those who have not used up their `max_per_vertex`, so a
+/// high-priority vertex only ever rests once everyone below it has rested
+/// their most. Vertices of equal priority are interchangeable.
+///
+/// The search also takes vertices in order of decreasing priority, so each
+/// round's first group is built around the highest-priority vertex playing
+/// and the assignments are found in that order. Members are listed in
+/// ascending order within each group, as usual.
+///
+/// ```
+/// # use group_generator::{make_assignments_with_priorities, ConflictMatrix};
+/// let sols = make_assignments_with_priorities(&mut ConflictMatrix::empty(3), 2, &[1, 0, 1], 1, 1).unwrap();
+/// assert!(sols.iter().all(|a| a.len() == 3 && a[0] == vec![vec![0, 2]]));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::AttributeMismatch`] if there is not one priority for each
+///   vertex.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`].
+pub fn make_assignments_with_priorities(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    priorities: &[usize],
+    max_per_round: usize,
+    max_per_vertex: usize,
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    let n = conflicts.len();
+    if priorities.len() != n {
+        return Err(GroupError::AttributeMismatch { len: priorities.len(), n });
+    }
+    // Vertex `order[i]` is searched as vertex `i`, highest priority first
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by_key(|v| core::cmp::Reverse(priorities[*v]));
+    let mut position = vec![0; n];
+    for (i, v) in order.iter().enumerate() {
+        position[*v] = i;
+    }
+    let mut relabeled = ConflictMatrix::empty(n).with_pairs(conflicts.pairs().map(|(u, v)| (position[u], position[v])));
+    for edge in conflicts.hyperedges() {
+        relabeled.add_hyperedge(&edge.iter().map(|v| position[*v]).collect::<Vec<_>>());
+    }
+    let priority = |i: &usize| priorities[order[*i]];
+    let may_rest = |resting: &[usize], eligible: &[usize]| match resting.iter().map(priority).max() {
+        Some(highest) => eligible.iter().filter(|i| !resting.contains(i)).all(|i| priority(i) >= highest),
+        None => true,
+    };
+    let sols = sit_out_assignments(&mut relabeled, min_group_size, max_per_round, max_per_vertex, may_rest);
+    let restore = |g: RawGroup| {
+        let mut g: RawGroup = g.into_iter().map(|i| order[i]).collect();
+        g.sort_unstable();
+        g
+    };
+    Ok(sols
+        .into_iter()
+        .map(|assignment| assignment.into_iter().map(|round| round.into_iter().map(restore).collect()).collect())
+        .collect())
+}
+
+/// The search behind [`make_assignments_with_sit_outs()`], resting a set of
+/// vertices in a round only if `may_rest(resting, eligible)`, where
+/// `eligible` are the vertices which have not used up their sit-outs.
+fn sit_out_assignments(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    max_per_round: usize,
+    max_per_vertex: usize,
+    may_rest: impl Fn(&[usize], &[usize]) -> bool,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    let n = conflicts.len();
+    if min_group_size == 1 {
+        return vec![vec![singletons(n)]];
+    }
+    let max_per_round = if max_per_vertex == 0 { 0 } else { max_per_round.min(n - min_group_size) };
+    // Number of rounds each vertex has sat out so far
+    let sat_out: Vec<Cell<usize>> = (0..n).map(|_| Cell::new(0)).collect();
+    let update = |round: &[RawGroup], delta: isize| {
+        let mut playing = bitvec![0; n];
+        for v in round.iter().flatten() {
+            playing.set(*v, true);
+        }
+        for v in playing.iter_zeros() {
+            sat_out[v].set(sat_out[v].get().wrapping_add_signed(delta));
+        }
+    };
+    let commit = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        commit_round(conflicts, round);
+        update(round, 1);
+    };
+    let rollback = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        update(round, -1);
+        rollback_round(conflicts, round);
+    };
+    let next_rounds = |conflicts: &mut ConflictMatrix| {
+        let eligible: Vec<usize> = (0..n).filter(|v| sat_out[*v].get() < max_per_vertex).collect();
+        let mut rounds = vec![];
+        for resting in subsets_up_to(&eligible, max_per_round) {
+            if !may_rest(&resting, &eligible) {
+                continue;
+            }
+            let mut skip = bitvec![0; n];
+            for v in &resting {
+                skip.set(*v, true);
+            }
+            let group_sizes = group_sizes(n - resting.len(), min_group_size);
+            rounds.extend(rounds_excluding(conflicts, &group_sizes, skip, |_, _, _| true));
+        }
+        rounds
+    };
+    maximal_assignments(conflicts, next_rounds, commit, rollback)
+}
+
+/// Every subset of `items` with at most `k` elements, smallest first and in
+/// lexicographic order of positions within each size.
+fn subsets_up_to(items: &[usize], k: usize) -> Vec<Vec<usize>> {
+    // Positions in `items` of each subset's elements
+    let mut subsets: Vec<Vec<usize>> = vec![vec![]];
+    let mut next = 0;
+    while let Some(positions) = subsets.get(next).cloned() {
+        next += 1;
+        if positions.len() == k {
+            continue;
+        }
+        let start = positions.last().map_or(0, |p| p + 1);
+        for p in start..items.len() {
+            let mut extended = positions.clone();
+            extended.push(p);
+            subsets.push(extended);
+        }
+    }
+    subsets.into_iter().map(|positions| positions.into_iter().map(|p| items[p]).collect()).collect()
+}
+
+/// Generate all assignments reaching the most rounds possible where no
+/// vertex is placed in the same group slot twice, so that with each slot a
+/// "station", everyone visits each station at most once.
+///
+/// Group slots follow the round shape, as for a [`Pin`], so there are never
+/// more rounds than groups per round.
+///
+/// ```
+/// # use group_generator::{make_assignments_with_station_rotation, ConflictMatrix};
+/// let sols = make_assignments_with_station_rotation(&mut ConflictMatrix::empty(6), 2);
+/// assert!(sols.iter().all(|a| a.len() == 3 && a[0][0].iter().all(|v| !a[1][0].contains(v))));
+/// ```
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_with_station_rotation(conflicts: &mut ConflictMatrix, min_group_size: usize) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    let n = conflicts.len();
+    if min_group_size == 1 {
+        return vec![vec![singletons(n)]];
+    }
+    let group_sizes = group_sizes(n, min_group_size);
+    let k = group_sizes.len();
+    // Whether vertex `v` has been in slot `i`, at `v * k + i`
+    let visited: Vec<Cell<bool>> = (0..n * k).map(|_| Cell::new(false)).collect();
+    let mark = |round: &[RawGroup], value: bool| {
+        for (i, g) in round.iter().enumerate() {
+            for v in g {
+                visited[v * k + i].set(value);
+            }
+        }
+    };
+    let commit = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        commit_round(conflicts, round);
+        mark(round, true);
+    };
+    let rollback = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        mark(round, false);
+        rollback_round(conflicts, round);
+    };
+    let next_rounds = |conflicts: &mut ConflictMatrix| {
+        rounds_excluding(conflicts, &group_sizes, bitvec![0; n], |slot, _, candidate| {
+            !visited[candidate * k + slot.index].get()
+        })
+    };
+    maximal_assignments(conflicts, next_rounds, commit, rollback)
+}
+
+/// Generate all assignments reaching the most rounds possible where no
+/// vertex is placed in an oversized group more than `max_large` times.
+///
+/// When the vertices do not divide evenly, the round shape (see
+/// [`group_sizes()`]) has groups larger than the smallest, and this spreads
+/// those places around instead of leaving the same vertices in the big
+/// group every round. [`large_group_quota()`] gives the fairest limit for a
+/// number of rounds. With an even shape there are no oversized groups and
+/// this is [`make_assignments()`].
+///
+/// ```
+/// # use group_generator::{make_assignments_large_group_fair, ConflictMatrix};
+/// let sols = make_assignments_large_group_fair(&mut ConflictMatrix::empty(5), 2, 1);
+/// // Rounds of 5 are shaped [3, 2], so the first group is the large one
+/// assert!(sols.iter().all(|a| (0..5).all(|v| a.iter().filter(|round| round[0].contains(&v)).count() <= 1)));
+/// ```
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_large_group_fair(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    max_large: usize,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    let n = conflicts.len();
+    if min_group_size == 1 {
+        return vec![vec![singletons(n)]];
+    }
+    let group_sizes = group_sizes(n, min_group_size);
+    let smallest = group_sizes.iter().copied().min().unwrap_or(0);
+    // Number of oversized groups each vertex has been in
+    let large: Vec<Cell<usize>> = (0..n).map(|_| Cell::new(0)).collect();
+    let count = |round: &[RawGroup], add: bool| {
+        for v in round.iter().filter(|g| g.len() > smallest).flatten() {
+            large[*v].set(if add { large[*v].get() + 1 } else { large[*v].get() - 1 });
+        }
+    };
+    let commit = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        commit_round(conflicts, round);
+        count(round, true);
+    };
+    let rollback = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        count(round, false);
+        rollback_round(conflicts, round);
+    };
+    let next_rounds = |conflicts: &mut ConflictMatrix| {
+        rounds_excluding(conflicts, &group_sizes, bitvec![0; n], |slot, _, candidate| {
+            slot.size == smallest || large[candidate].get() < max_large
+        })
+    };
+    maximal_assignments(conflicts, next_rounds, commit, rollback)
+}
+
+/// The fairest limit on oversized groups per vertex over `rounds` rounds
+/// (see [`make_assignments_large_group_fair()`]): the places in oversized
+/// groups across all rounds, shared out over the `n` vertices and rounded
+/// up.
+///
+/// ```
+/// # use group_generator::large_group_quota;
+/// // Rounds of [3, 2, 2] have 3 places in an oversized group each
+/// assert_eq!(large_group_quota(7, 2, 4), 2);
+/// assert_eq!(large_group_quota(6, 2, 4), 0);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `min_group_size` is 0.
+pub fn large_group_quota(n: usize, min_group_size: usize, rounds: usize) -> usize {
+    let group_sizes = group_sizes(n, min_group_size);
+    let smallest = group_sizes.iter().copied().min().unwrap_or(0);
+    let places: usize = group_sizes.iter().filter(|k| **k > smallest).sum();
+    (rounds * places).div_ceil(n.max(1))
+}
+
+/// Generate all assignments of at most `num_rounds` rounds, reaching the most
+/// rounds possible, where a pair which meets becomes eligible again once
+/// `cooldown` further rounds have passed.
+///
+/// A pair grouped in round `r` is treated as conflicting for rounds
+/// `r + 1..=r + cooldown` only, so with `cooldown == 0` pairs may meet in
+/// every round. Conflicts present before the first round stay conflicting
+/// throughout. Since pairs keep becoming available again, schedules may go on
+/// indefinitely and are cut off at `num_rounds`.
+///
+/// With a `cooldown` of at least the [`max_rounds()`], and `num_rounds` at
+/// least as large, this is exactly [`make_assignments()`].
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_with_cooldown(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    cooldown: usize,
+    num_rounds: usize,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    cooldown_assignments(conflicts, min_group_size, cooldown, num_rounds, false)
+}
+
+/// [`make_assignments_with_cooldown()`] where every round after the first
+/// keeps each vertex with someone from its previous group (see
+/// [`has_continuity()`]).
+///
+/// Those partners are still cooling down unless `cooldown == 0`, so
+/// otherwise no round can follow the first.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_with_cooldown_continuing(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    cooldown: usize,
+    num_rounds: usize,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    cooldown_assignments(conflicts, min_group_size, cooldown, num_rounds, true)
+}
+
+/// The search behind [`make_assignments_with_cooldown()`], keeping
+/// continuity between rounds if `continuity` is set.
+fn cooldown_assignments(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    cooldown: usize,
+    num_rounds: usize,
+    continuity: bool,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    let n = conflicts.len();
+    if min_group_size == 1 {
+        return vec![vec![singletons(n)]];
+    }
+    // The rounds committed so far, the last `cooldown` of which hold conflicts
+    let history: RefCell<Vec<Vec<RawGroup>>> = RefCell::new(vec![]);
+    let commit = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        let mut history = history.borrow_mut();
+        commit_round(conflicts, round);
+        history.push(round.to_vec());
+        if let Some(expired) = history.len().checked_sub(cooldown + 1) {
+            rollback_round(conflicts, &history[expired]);
+        }
+    };
+    let rollback = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        let mut history = history.borrow_mut();
+        if let Some(expired) = history.len().checked_sub(cooldown + 1) {
+            commit_round(conflicts, &history[expired]);
+        }
+        history.pop();
+        rollback_round(conflicts, round);
+    };
+    let group_sizes = group_sizes(n, min_group_size);
+    let mut sols = vec![];
+    let mut best = 0;
+    explore_rounds(
+        conflicts,
+        |conflicts, curr| {
+            if curr.len() < num_rounds {
+                continuing_rounds(conflicts, &group_sizes, curr.last().filter(|_| continuity))
+            } else {
+                vec![]
+            }
+        },
+        commit,
+        rollback,
+        |curr| {
+            if curr.len() >= best {
+                if curr.len() > best {
+                    sols.clear();
+                }
+                sols.push(curr.to_vec());
+                best = curr.len();
+            }
+            ControlFlow::Continue(())
+        },
+    );
+    sols
+}
+
+/// Generate all assignments reaching the most rounds possible when vertices
+/// may be absent from some rounds.
+///
+/// `available[v][r]` is whether vertex `v` attends round `r`, and entries
+/// past the end of a row count as absent, so there are as many rounds as the
+/// longest row. Each round partitions only the vertices present in it, into
+/// [`group_sizes()`] of the number present, and absent vertices appear in
+/// none of its groups. A round with fewer than `min_group_size` vertices
+/// present cannot be formed, which ends the assignment there.
+///
+/// ```
+/// # use group_generator::{make_assignments_with_availability, ConflictMatrix};
+/// let (t, f) = (true, false);
+/// let available = vec![vec![t, t, t], vec![t, t, t], vec![f, t, t], vec![f, t, t]];
+/// let sols = make_assignments_with_availability(&mut ConflictMatrix::empty(4), 2, &available).unwrap();
+/// assert!(sols.iter().all(|a| a.len() == 3 && a[0] == vec![vec![0, 1]]));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::AttributeMismatch`] if `available` does not have a row
+///   for each vertex.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`].
+pub fn make_assignments_with_availability(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    available: &[Vec<bool>],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    let n = conflicts.len();
+    if available.len() != n {
+        return Err(GroupError::AttributeMismatch { len: available.len(), n });
+    }
+    let num_rounds = available.iter().map(Vec::len).max().unwrap_or(0);
+    let absent = |round: usize| -> BitVec { (0..n).map(|v| available[v].get(round) != Some(&true)).collect() };
+    if min_group_size == 1 {
+        let present: Vec<RawGroup> = (0..n).filter(|v| available[*v].first() == Some(&true)).map(|v| vec![v]).collect();
+        return Ok(vec![if present.is_empty() { vec![] } else { vec![present] }]);
+    }
+    let mut sols = vec![];
+    let mut best = 0;
+    explore_rounds(
+        conflicts,
+        |conflicts, curr| {
+            let round = curr.len();
+            if round >= num_rounds {
+                return vec![];
+            }
+            let skip = absent(round);
+            let group_sizes = group_sizes(skip.count_zeros(), min_group_size);
+            rounds_excluding(conflicts, &group_sizes, skip, |_, _, _| true)
+        },
+        commit_round,
+        rollback_round,
+        |curr| {
+            if curr.len() >= best {
+                if curr.len() > best {
+                    sols.clear();
+                }
+                sols.push(curr.to_vec());
+                best = curr.len();
+            }
+            ControlFlow::Continue(())
+        },
+    );
+    Ok(sols)
+}
+
+/// Generate the longest assignments which group every pair in `required`
+/// together at least once.
+///
+/// Only branches which end with every required pair covered count, and the
+/// result holds those with the most rounds, so it may be shorter than
+/// [`make_assignments()`] when covering the pairs costs rounds. Branches are
+/// abandoned once the [`max_rounds_upper_bound()`] of the remaining conflicts
+/// leaves too few rounds either to beat the best covering branch so far or to
+/// cover every vertex's remaining required partners, at most one fewer than
+/// the largest group per round.
+///
+/// ```
+/// # use group_generator::{make_assignments_covering, ConflictMatrix};
+/// let sols = make_assignments_covering(&mut ConflictMatrix::empty(4), 2, &[(0, 1), (2, 3)]).unwrap();
+/// assert!(sols.iter().all(|a| a.iter().flatten().any(|g| g == &[0, 1] || g == &[1, 0])));
+/// ```
+///
+/// If a required pair is conflicting from the start, no assignment covers it
+/// and the result is empty.
+///
+/// # Errors
+///
+/// - [`GroupError::VertexOutOfRange`] if a pair refers to a vertex which
+///   does not exist.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`].
+pub fn make_assignments_covering(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    required: &[(usize, usize)],
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    let n = conflicts.len();
+    check_vertices(n, required.iter().flat_map(|(u, v)| [*u, *v]))?;
+    let required: Vec<(usize, usize)> = required.iter().copied().filter(|(u, v)| u != v).collect();
+    if required.iter().any(|(u, v)| conflicts.is_conflicted(*u, *v)) {
+        return Ok(vec![]);
+    }
+    if min_group_size == 1 {
+        return Ok(if required.is_empty() { vec![vec![singletons(n)]] } else { vec![] });
+    }
+
+    let group_sizes = group_sizes(n, min_group_size);
+    let largest = group_sizes.iter().copied().max().unwrap_or(1);
+    let covers_all = |curr: &[Vec<RawGroup>]| {
+        let met = |u: &usize, v: &usize| curr.iter().flatten().any(|g| g.contains(u) && g.contains(v));
+        required.iter().all(|(u, v)| met(u, v))
+    };
+    // Read while choosing rounds and updated at the leaves
+    let best = Cell::new(0);
+    let mut sols = vec![];
+    explore_rounds(
+        conflicts,
+        |conflicts, curr| {
+            let remaining = max_rounds_upper_bound(conflicts, min_group_size);
+            if curr.len() + remaining < best.get() {
+                return vec![];
+            }
+            // Committed rounds only add conflicts, so a required pair is still
+            // uncovered exactly when it is not conflicting
+            let mut uncovered = vec![0; n];
+            for (u, v) in required.iter().filter(|(u, v)| !conflicts.is_conflicted(*u, *v)) {
+                uncovered[*u] += 1;
+                uncovered[*v] += 1;
+            }
+            if uncovered.iter().any(|count| *count > remaining * (largest - 1)) {
+                return vec![];
+            }
+            single_assignment(conflicts, &group_sizes)
+        },
+        commit_round,
+        rollback_round,
+        |curr| {
+            if curr.len() >= best.get() && covers_all(curr) {
+                if curr.len() > best.get() {
+                    sols.clear();
+                }
+                sols.push(curr.to_vec());
+                best.set(curr.len());
+            }
+            ControlFlow::Continue(())
+        },
+    );
+    Ok(sols)
+}
+
+/// Compute the maximum number of rounds and the number of assignments
+/// [`make_assignments()`] would return, in a single pass and without storing
+/// any of them.
+///
+/// Only the current search path is held in memory. The count includes every
+/// ordering of groups and rounds, exactly as [`make_assignments()`] reports
+/// them; see [`count_distinct_maximal()`] to count distinct schedules instead.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn count_assignments(conflicts: &mut ConflictMatrix, min_group_size: usize) -> (usize, u128) {
+    check_problem(conflicts, min_group_size);
+
+    if min_group_size == 1 {
+        return (1, 1);
+    }
+    if !can_form_any_round(conflicts, min_group_size) {
+        return (0, 1);
+    }
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut best = 0;
+    let mut count = 0;
+    explore_rounds(
+        conflicts,
+        |conflicts, _| single_assignment(conflicts, &group_sizes),
+        commit_round,
+        rollback_round,
+        |curr| {
+            if curr.len() > best {
+                best = curr.len();
+                count = 0;
+            }
+            if curr.len() == best {
+                count += 1;
+            }
+            ControlFlow::Continue(())
+        },
+    );
+    (best, count)
+}
+
+/// Count the maximal assignments which are distinct up to the order of
+/// groups within rounds and the order of rounds, without storing them.
+///
+/// [`make_assignments()`] reports the same schedule many times over (once per
+/// ordering of its groups and rounds). Here, each maximal assignment is
+/// streamed through [`canonical_assignment()`] and only a 64-bit fingerprint
+/// of the canonical form is retained, so memory grows with the number of
+/// *distinct* schedules at 8 bytes each rather than with the full output.
+///
+/// Two different schedules sharing a fingerprint would be counted once. With
+/// `m` distinct schedules this happens with probability roughly `m² / 2^65`,
+/// which is negligible for any count this search can reach in practice.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+#[cfg(feature = "std")]
+pub fn count_distinct_maximal(conflicts: &mut ConflictMatrix, min_group_size: usize) -> usize {
+    let mut fingerprints = HashSet::new();
+    make_assignments_with::<()>(conflicts, min_group_size, |assignment| {
+        let mut hasher = DefaultHasher::new();
+        canonical_assignment(assignment).hash(&mut hasher);
+        fingerprints.insert(hasher.finish());
+        ControlFlow::Continue(())
+    });
+    fingerprints.len()
+}
+
+/// Visit every assignment [`make_assignments()`] would return, in the same
+/// order, as it is found, without collecting them.
+///
+/// `visit` may return [`ControlFlow::Break`] to stop the search early (e.g.
+/// once a good enough assignment has been seen), in which case its value is
+/// returned. Otherwise the search runs to completion and `None` is returned.
+/// `conflicts` is restored either way.
+///
+/// Maximality is only known once the whole tree has been searched, so this
+/// takes two passes: the first finds [`max_rounds()`], and the second visits
+/// every assignment reaching that depth. Stopping early only saves time in
+/// the second pass.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_with<B>(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    visit: impl FnMut(&[Vec<RawGroup>]) -> ControlFlow<B>,
+) -> Option<B> {
+    check_problem(conflicts, min_group_size);
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    visit_maximal(conflicts, &group_sizes, visit)
+}
+
+/// [`make_assignments_with()`] for rounds shaped by `group_sizes`, which
+/// must sum to the number of vertices.
+pub(crate) fn visit_maximal<B>(
+    conflicts: &mut ConflictMatrix,
+    group_sizes: &[usize],
+    visit: impl FnMut(&[Vec<RawGroup>]) -> ControlFlow<B>,
+) -> Option<B> {
+    visit_maximal_per_round(conflicts, &[group_sizes.to_vec()], visit)
+}
+
+/// [`visit_maximal()`] with round `i` shaped by `shapes[i]`, or by the last
+/// shape once they run out. There must be at least one shape.
+fn visit_maximal_per_round<B>(
+    conflicts: &mut ConflictMatrix,
+    shapes: &[Vec<usize>],
+    visit: impl FnMut(&[Vec<RawGroup>]) -> ControlFlow<B>,
+) -> Option<B> {
+    visit_maximal_filtered(conflicts, shapes, |_, _, _| true, visit)
+}
+
+/// [`visit_maximal_per_round()`] building groups only as `allows` accepts
+/// (see [`Slot`]).
+///
+/// Restricting groups only removes rounds, so the round bounds still hold.
+pub(crate) fn visit_maximal_filtered<B>(
+    conflicts: &mut ConflictMatrix,
+    shapes: &[Vec<usize>],
+    allows: impl Fn(Slot, &[usize], usize) -> bool,
+    mut visit: impl FnMut(&[Vec<RawGroup>]) -> ControlFlow<B>,
+) -> Option<B> {
+    let n = conflicts.len();
+    if shapes[0].iter().all(|k| *k == 1) {
+        let rounds = if allows_singletons(n, &allows) { vec![singletons(n)] } else { vec![] };
+        return visit(&rounds).break_value();
+    }
+    let best = deepest_branch(conflicts, shapes, &allows, |_| {});
+    let mut stopped = None;
+    explore_rounds(
+        conflicts,
+        |conflicts, curr| {
+            if curr.len() == best {
+                return vec![];
+            }
+            filtered_rounds(conflicts, shape_at(shapes, curr.len()), curr.len(), &allows)
+        },
+        commit_round,
+        rollback_round,
+        |curr| {
+            if curr.len() < best {
+                return ControlFlow::Continue(());
+            }
+            visit(curr).map_break(|value| stopped = Some(value))
+        },
+    );
+    stopped
+}
+
+/// Find a single *maximum-round* assignment, without enumerating the rest.
+///
+/// The search is depth-first, keeping the deepest assignment seen, and:
+///
+/// - abandons any branch whose rounds so far plus
+///   [`max_rounds_upper_bound()`] on the remaining conflicts cannot beat it,
+///   and
+/// - stops outright once it reaches [`max_rounds_upper_bound()`] of the
+///   original problem, since nothing can go deeper.
+///
+/// When the bound is tight this usually finishes after the first few
+/// branches. Otherwise the search must still rule out every deeper branch,
+/// but it never stores more than one assignment. The result is one of those
+/// [`make_assignments()`] would return, though not necessarily the first.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_first_assignment(conflicts: &mut ConflictMatrix, min_group_size: usize) -> Vec<Vec<RawGroup>> {
+    check_problem(conflicts, min_group_size);
+
+    if min_group_size == 1 {
+        return vec![singletons(conflicts.len())];
+    }
+    if !can_form_any_round(conflicts, min_group_size) {
+        return vec![];
+    }
+    let mut best = vec![];
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    deepest_branch(conflicts, &[group_sizes], |_, _, _| true, |curr| best = curr.to_vec());
+    best
+}
+
+/// Branch-and-bound search for the deepest branch of the round-level search,
+/// calling `on_deeper` each time a strictly deeper one is found and returning
+/// its depth.
+///
+/// Round `i` is shaped by `shapes[i]`, or by the last shape once they run
+/// out, and groups are built only as `allows` accepts (as in
+/// [`visit_maximal_filtered()`]). A branch is abandoned once its rounds so
+/// far plus [`shapes_upper_bound()`] on the remaining conflicts and shapes
+/// cannot beat the best depth, and the search stops outright on reaching the
+/// bound for the original problem. Expects the trivial cases to be handled already.
+fn deepest_branch(
+    conflicts: &mut ConflictMatrix,
+    shapes: &[Vec<usize>],
+    allows: impl Fn(Slot, &[usize], usize) -> bool,
+    mut on_deeper: impl FnMut(&[Vec<RawGroup>]),
+) -> usize {
+    let remaining_shapes = |depth: usize| &shapes[depth.min(shapes.len() - 1)..];
+    let bound = shapes_upper_bound(conflicts, shapes);
+    // Read while choosing rounds and updated at the leaves
+    let best = Cell::new(0);
+    explore_rounds(
+        conflicts,
+        |conflicts, curr| {
+            if curr.len() + shapes_upper_bound(conflicts, remaining_shapes(curr.len())) <= best.get() {
+                return vec![];
+            }
+            filtered_rounds(conflicts, shape_at(shapes, curr.len()), curr.len(), &allows)
+        },
+        commit_round,
+        rollback_round,
+        |curr| {
+            if curr.len() > best.get() {
+                on_deeper(curr);
+                best.set(curr.len());
+            }
+            if curr.len() == bound {
+                return ControlFlow::Break(());
+            }
+            ControlFlow::Continue(())
+        },
+    );
+    best.get()
+}
+
+/// Generate the *maximum-round* assignments (as in [`make_assignments()`])
+/// within `max_distance` of `reference`, measured by [`pair_distance()`].
+///
+/// # Distance
+///
+/// The distance is the number of unordered pairs which meet in exactly one of
+/// the two schedules. Every pair a partial schedule brings together which the
+/// reference does not is already part of the final distance, so branches are
+/// abandoned as soon as those extra pairs exceed `max_distance`. Pairs of the
+/// reference which are still missing are only counted at the end.
+///
+/// Like [`make_assignments_to_file()`], this first finds [`max_rounds()`] and
+/// then streams the search, keeping only maximal assignments within range.
+///
+/// # Panics
+///
+/// Panics if `reference` contains a vertex out of range, or under the same
+/// conditions as [`make_assignments()`].
+pub fn make_assignments_near(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    reference: &[Vec<RawGroup>],
+    max_distance: usize,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    let n = conflicts.len();
+    check_problem(conflicts, min_group_size);
+    let v = reference.iter().flatten().flatten().find(|v| **v >= n);
+    assert!(v.is_none(), "Reference vertex {} is out of range for {n} vertices.", v.unwrap_or(&0));
+    let reference_pairs = analysis::met_pairs(reference, n);
+    let extra = |round: &[RawGroup]| {
+        round
+            .iter()
+            .map(|g| {
+                let pairs = g.iter().enumerate().flat_map(|(i, u)| g[..i].iter().map(move |v| (*u, *v)));
+                pairs.filter(|(u, v)| !reference_pairs[*u][*v]).count()
+            })
+            .sum::<usize>()
+    };
+
+    let best = max_rounds(conflicts, min_group_size);
+    if min_group_size == 1 || best == 0 {
+        let trivial = if min_group_size == 1 { vec![singletons(n)] } else { vec![] };
+        if pair_distance(&trivial, reference, n) > max_distance {
+            return vec![];
+        }
+        return vec![trivial];
+    }
+    let group_sizes = group_sizes(n, min_group_size);
+    let mut sols = vec![];
+    explore_rounds(
+        conflicts,
+        |conflicts, curr| {
+            let spent: usize = curr.iter().map(|round| extra(round)).sum();
+            let mut rounds = single_assignment(conflicts, &group_sizes);
+            rounds.retain(|round| spent + extra(round) <= max_distance);
+            rounds
+        },
+        commit_round,
+        rollback_round,
+        |curr| {
+            if curr.len() == best && pair_distance(curr, reference, n) <= max_distance {
+                sols.push(curr.to_vec());
+            }
+            ControlFlow::Continue(())
+        },
+    );
+    sols
+}
+
+/// Compute the single lexicographically smallest maximal assignment, in
+/// canonical form (see [`canonical_assignment()`]).
+///
+/// Assignments are compared by their canonical rounds in order, group by
+/// group, which (since every round has the same shape) is the same as
+/// comparing the flattened sequences of canonical groups. This gives one
+/// reproducible answer out of the many schedules [`make_assignments()`]
+/// returns.
+///
+/// Rather than generating every maximal assignment and sorting, the search
+/// only considers canonical rounds, tries them in ascending order, and
+/// requires each round to be greater than the one before it. The first
+/// assignment reaching [`max_rounds()`] is therefore the smallest one, and the
+/// search stops there. Branches which cannot reach that depth according to
+/// [`max_rounds_upper_bound()`] are pruned.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_lex_min(conflicts: &mut ConflictMatrix, min_group_size: usize) -> Vec<Vec<RawGroup>> {
+    let best = max_rounds(conflicts, min_group_size);
+    if min_group_size == 1 {
+        return vec![singletons(conflicts.len())];
+    }
+    if best == 0 {
+        return vec![];
+    }
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut lex_min = vec![];
+    explore_rounds(
+        conflicts,
+        |conflicts, curr| {
+            if curr.len() + max_rounds_upper_bound(conflicts, min_group_size) < best {
+                return vec![];
+            }
+            let mut rounds = canonical_rounds(conflicts, &group_sizes);
+            if let Some(prev) = curr.last() {
+                rounds.retain(|round| round > prev);
+            }
+            rounds
+        },
+        commit_round,
+        rollback_round,
+        |curr| {
+            if curr.len() < best {
+                return ControlFlow::Continue(());
+            }
+            lex_min = curr.to_vec();
+            ControlFlow::Break(())
+        },
+    );
+    lex_min
+}
+
+/// Generate every conflict-free round with the given group sizes exactly once
+/// per partition, in canonical form (see [`canonical_round()`]) and ascending
+/// order.
+///
+/// Each group is built around the least vertex not yet placed, so groups come
+/// out ordered by least member, and the candidates for each group (across all
+/// sizes still to be placed) are tried in ascending order.
+pub(crate) fn canonical_rounds(conflicts: &ConflictMatrix, group_sizes: &[usize]) -> Vec<Vec<RawGroup>> {
+    let mut res = vec![];
+    let mut placed = bitvec![0; conflicts.len()];
+    let candidates = |placed: &BitVec, curr: &[RawGroup]| {
+        let Some(v) = placed.first_zero() else {
+            return vec![];
+        };
+        let mut sizes = group_sizes.to_vec();
+        for g in curr {
+            if let Some(i) = sizes.iter().position(|k| *k == g.len()) {
+                sizes.swap_remove(i);
+            }
+        }
+        sizes.sort_unstable();
+        sizes.dedup();
+        let mut groups: Vec<RawGroup> = sizes
+            .into_iter()
+            .flat_map(|k| groups_containing(conflicts, &[v], k, placed))
+            .collect();
+        groups.sort_unstable();
+        groups
+    };
+    let mut curr: Vec<RawGroup> = vec![];
+    // Frame `i` holds the candidates for group `i` given the groups in `curr`
+    let mut stack = vec![Frame::new(candidates(&placed, &curr))];
+    while let Some(frame) = stack.last_mut() {
+        let Some(g) = frame.next_option() else {
+            stack.pop();
+            if let Some(g) = curr.pop() {
+                for v in g {
+                    placed.set(v, false);
+                }
+            }
+            continue;
+        };
+        if curr.len() == group_sizes.len() - 1 {
+            curr.push(g);
+            res.push(curr.clone());
+            curr.pop();
+        } else {
+            for v in &g {
+                placed.set(*v, true);
+            }
+            curr.push(g);
+            stack.push(Frame::new(candidates(&placed, &curr)));
+        }
+    }
+    res
+}
+
+/// Generate every assignment with exactly `rounds` rounds, rather than only
+/// the longest ones.
+///
+/// An assignment counts whether or not it could go on for more rounds, so
+/// this lists every way to schedule the first `rounds` rounds. Branches which
+/// cannot get that deep (by [`max_rounds_upper_bound()`] on the remaining
+/// conflicts) are abandoned early.
+///
+/// ```
+/// # use group_generator::{make_assignments_exact_rounds, ConflictMatrix, GroupError};
+/// let sols = make_assignments_exact_rounds(&mut ConflictMatrix::empty(4), 2, 2).unwrap();
+/// assert!(sols.iter().all(|a| a.len() == 2));
+/// let err = make_assignments_exact_rounds(&mut ConflictMatrix::empty(4), 2, 4);
+/// assert_eq!(err, Err(GroupError::UnreachableRounds { rounds: 4, max_rounds: 3 }));
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::UnreachableRounds`] if no assignment has `rounds` rounds,
+///   along with the [`max_rounds()`] there are.
+/// - [`GroupError::NoVertices`] and [`GroupError::InvalidMinGroupSize`] as for
+///   [`try_make_assignments()`].
+pub fn make_assignments_exact_rounds(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    rounds: usize,
+) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    let mut sols = vec![];
+    visit_exact_rounds::<()>(conflicts, min_group_size, rounds, |assignment| {
+        sols.push(assignment.to_vec());
+        ControlFlow::Continue(())
+    });
+    if sols.is_empty() {
+        let max_rounds = max_rounds(conflicts, min_group_size);
+        return Err(GroupError::UnreachableRounds { rounds, max_rounds });
+    }
+    Ok(sols)
+}
+
+/// Whether any assignment has at least `rounds` rounds.
+///
+/// This stops at the first branch reaching `rounds`, abandoning branches
+/// which cannot (by [`max_rounds_upper_bound()`]) along the way, and collects
+/// nothing, so it is far cheaper than [`make_assignments_exact_rounds()`] or
+/// even [`max_rounds()`] when the answer is yes.
+///
+/// ```
+/// # use group_generator::{can_reach_rounds, ConflictMatrix};
+/// assert!(can_reach_rounds(&mut ConflictMatrix::empty(6), 2, 4));
+/// assert!(!can_reach_rounds(&mut ConflictMatrix::empty(6), 3, 2));
+/// ```
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn can_reach_rounds(conflicts: &mut ConflictMatrix, min_group_size: usize, rounds: usize) -> bool {
+    check_problem(conflicts, min_group_size);
+    if rounds > max_rounds_upper_bound(conflicts, min_group_size) {
+        return false;
+    }
+    visit_exact_rounds(conflicts, min_group_size, rounds, |_| ControlFlow::Break(())).is_some()
+}
+
+/// Visit every assignment with exactly `rounds` rounds (see
+/// [`make_assignments_exact_rounds()`]), stopping once `visit` breaks.
+fn visit_exact_rounds<B>(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    rounds: usize,
+    mut visit: impl FnMut(&[Vec<RawGroup>]) -> ControlFlow<B>,
+) -> Option<B> {
+    if min_group_size == 1 && rounds > 0 {
+        // A round of singletons ends the assignment (see `make_assignments()`)
+        return if rounds == 1 { visit(&[singletons(conflicts.len())]).break_value() } else { None };
+    }
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let mut stopped = None;
+    explore_rounds(
+        conflicts,
+        |conflicts, curr| {
+            if curr.len() == rounds || curr.len() + max_rounds_upper_bound(conflicts, min_group_size) < rounds {
+                return vec![];
+            }
+            single_assignment(conflicts, &group_sizes)
+        },
+        commit_round,
+        rollback_round,
+        |curr| {
+            if curr.len() < rounds {
+                return ControlFlow::Continue(());
+            }
+            visit(curr).map_break(|value| stopped = Some(value))
+        },
+    );
+    stopped
+}
+
+/// Compute only the maximum number of rounds achievable, without collecting
+/// any assignments.
+///
+/// Uses the same branch-and-bound search as [`make_first_assignment()`]:
+/// branches which cannot beat the best depth found so far (by
+/// [`max_rounds_upper_bound()`]) are cut off, and the search stops as soon as
+/// the bound for the whole problem is reached. This is usually far cheaper
+/// than [`make_assignments()`], so it can be used to decide whether full
+/// enumeration is worthwhile. It has the same panics.
+pub fn max_rounds(conflicts: &mut ConflictMatrix, min_group_size: usize) -> usize {
+    check_problem(conflicts, min_group_size);
+
+    if min_group_size == 1 {
+        return 1;
+    }
+    if !can_form_any_round(conflicts, min_group_size) {
+        return 0;
+    }
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    deepest_branch(conflicts, &[group_sizes], |_, _, _| true, |_| {})
+}
+
+/// Cheap theoretical upper bound on the number of rounds achievable.
+///
+/// Every round places each vertex in a group of at least the smallest planned
+/// size `s`, giving it at least `s - 1` new partners drawn from the vertices
+/// it does not conflict with. Every round also consumes a fixed number of
+/// pairs determined by the round shape. The bound is the tighter of:
+///
+/// - `min_v floor(free_partners(v) / (s - 1))`, and
+/// - `floor(free_pairs / pairs_per_round)`.
+///
+/// With `min_group_size == 1` the bound is 1 (see [`make_assignments()`]).
+pub fn max_rounds_upper_bound(conflicts: &ConflictMatrix, min_group_size: usize) -> usize {
+    check_problem(conflicts, min_group_size);
+    shapes_upper_bound(conflicts, &[group_sizes(conflicts.len(), min_group_size)])
+}
+
+/// [`max_rounds_upper_bound()`] for rounds of any of the given `shapes`,
+/// each usable any number of times, with `s` the smallest size and
+/// `pairs_per_round` the fewest pairs among them.
+///
+/// A shape of singletons has no pairs and ends the schedule (see
+/// [`make_assignments()`]), so it only ever adds one round.
+fn shapes_upper_bound(conflicts: &ConflictMatrix, shapes: &[Vec<usize>]) -> usize {
+    let pairs = |shape: &[usize]| shape.iter().map(|k| k * (k - 1) / 2).sum::<usize>();
+    let ends = usize::from(shapes.iter().any(|shape| !shape.is_empty() && pairs(shape) == 0));
+    let paired: Vec<&[usize]> = shapes.iter().map(Vec::as_slice).filter(|shape| pairs(shape) > 0).collect();
+    let (Some(smallest), Some(pairs_per_round)) = (
+        paired.iter().flat_map(|shape| shape.iter()).copied().min(),
+        paired.iter().map(|shape| pairs(shape)).min(),
+    ) else {
+        return ends;
+    };
+    let n = conflicts.len();
+    let free_partners: Vec<usize> = (0..n).map(|v| n - 1 - conflicts.degree(v)).collect();
+    let by_vertex = match smallest {
+        1 => usize::MAX,
+        _ => free_partners.iter().map(|free| free / (smallest - 1)).min().unwrap_or(0),
+    };
+    let free_pairs = free_partners.iter().sum::<usize>() / 2;
+    by_vertex.min(free_pairs / pairs_per_round) + ends
+}
+
+/// The shape of round `depth` when round `i` is shaped by `shapes[i]`, or by
+/// the last shape once they run out.
+fn shape_at(shapes: &[Vec<usize>], depth: usize) -> &[usize] {
+    &shapes[depth.min(shapes.len() - 1)]
+}
+
+/// Ratio of the rounds actually achievable ([`max_rounds()`]) to the
+/// theoretical maximum ([`max_rounds_upper_bound()`]).
+///
+/// A value of `0.75` means the constraints allow reaching 75% of the
+/// theoretical maximum mixing. If the bound is 0, nothing was achievable in
+/// the first place, so nothing was lost and the efficiency is `1.0`.
+pub fn schedule_efficiency(conflicts: &mut ConflictMatrix, min_group_size: usize) -> f64 {
+    let bound = max_rounds_upper_bound(conflicts, min_group_size);
+    if bound == 0 {
+        return 1.0;
+    }
+    max_rounds(conflicts, min_group_size) as f64 / bound as f64
+}
+
+/// Compute how many further rounds remain achievable if `candidate` were
+/// committed as the next round.
+///
+/// The candidate's pairs are tentatively marked as conflicting, [`max_rounds()`]
+/// is computed on the resulting state, and the marks are removed again, so
+/// `conflicts` is left exactly as it was. This lets a planner score candidate
+/// rounds by how much future flexibility they preserve.
+///
+/// # Panics
+///
+/// Panics if any group in `candidate` contains a pair which is already in
+/// conflict (it would not be a valid round), or under the same conditions as
+/// [`max_rounds()`].
+pub fn rounds_remaining_after(conflicts: &mut ConflictMatrix, min_group_size: usize, candidate: &[RawGroup]) -> usize {
+    for g in candidate {
+        for (i, u) in g.iter().enumerate() {
+            for v in &g[..i] {
+                assert!(!conflicts.is_conflicted(*u, *v), "Candidate round groups conflicting vertices {v} and {u}.");
+            }
+        }
+    }
+    commit_round(conflicts, candidate);
+    let remaining = max_rounds(conflicts, min_group_size);
+    rollback_round(conflicts, candidate);
+    remaining
+}
+
+/// For each pair currently in conflict, compute how many rounds would be
+/// achievable if that one conflict were lifted.
+///
+/// Each conflict `(i, j)` with `i < j` is cleared (symmetrically), scored with
+/// [`max_rounds()`], and set again before moving on, so `conflicts` is left
+/// exactly as it was. The diagonal is not considered.
+///
+/// Results are sorted best-first by the new round count, with ties in pair
+/// order, so the conflicts limiting the schedule the most come first. Any
+/// entry exceeding the current [`max_rounds()`] is a conflict whose removal
+/// unlocks extra rounds.
+///
+/// # Cost
+///
+/// This runs one [`max_rounds()`] search per set conflict, so it is only
+/// practical where a single search is cheap.
+pub fn conflict_relaxation(conflicts: &mut ConflictMatrix, min_group_size: usize) -> Vec<((usize, usize), usize)> {
+    check_problem(conflicts, min_group_size);
+    let n = conflicts.len();
+    let mut relaxed = vec![];
+    for i in 0..n {
+        for j in i + 1..n {
+            if !conflicts.is_conflicted(i, j) {
+                continue;
+            }
+            remove_conflicts_between(conflicts, &[i, j]);
+            let rounds = max_rounds(conflicts, min_group_size);
+            add_conflicts_between(conflicts, &[i, j]);
+            relaxed.push(((i, j), rounds));
+        }
+    }
+    relaxed.sort_by_key(|(_, rounds)| core::cmp::Reverse(*rounds));
+    relaxed
+}
+
+/// Generate all *maximum-round* assignments (as in [`make_assignments()`])
+/// where one group in every round contains the whole `pivot` set.
+///
+/// This models a fixed table (e.g. an organizing committee) which always sits
+/// together while the rest of its seats, and every other group, rotate as
+/// usual. The pivot always occupies the first (largest) group of each round
+/// and is topped up with non-pivot vertices to the planned group size.
+///
+/// Pivot members are exempt from the no-repeat rule among themselves, since
+/// they are together by construction. Every other pair, including a pivot
+/// member and a vertex joining their table, may still only meet once.
+///
+/// # Panics
+///
+/// In addition to the panics from [`make_assignments()`], panics if `pivot` is
+/// empty, contains duplicate or out-of-range vertices, contains a pair which is
+/// already in conflict, or is larger than the largest planned group.
+pub fn make_assignments_with_pivot(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    pivot: &[usize],
+) -> Vec<Vec<Vec<RawGroup>>> {
+    let n = conflicts.len();
+    check_problem(conflicts, min_group_size);
+    assert!(!pivot.is_empty(), "Pivot set must contain at least one vertex.");
+    let mut in_pivot = bitvec![0; n];
+    for (i, &p) in pivot.iter().enumerate() {
+        assert!(p < n, "Pivot vertex {p} is out of range for {n} vertices.");
+        assert!(!in_pivot[p], "Pivot vertex {p} is repeated.");
+        in_pivot.set(p, true);
+        for &q in &pivot[..i] {
+            assert!(!conflicts.is_conflicted(p, q), "Pivot vertices {q} and {p} are in conflict.");
+        }
+    }
+    let group_sizes = group_sizes(n, min_group_size);
+    assert!(
+        group_sizes.first().is_some_and(|&k| pivot.len() <= k),
+        "Pivot set does not fit in the largest planned group."
+    );
+
+    let next_rounds = |conflicts: &mut ConflictMatrix| {
+        let mut rounds = vec![];
+        for table in groups_containing(conflicts, pivot, group_sizes[0], &bitvec![0; n]) {
+            let mut skip = bitvec![0; n];
+            for v in &table {
+                skip.set(*v, true);
+            }
+            let rest = if group_sizes.len() == 1 {
+                vec![vec![]]
+            } else {
+                rounds_excluding(conflicts, &group_sizes[1..], skip, |_, _, _| true)
+            };
+            for others in rest {
+                let mut round = Vec::with_capacity(group_sizes.len());
+                round.push(table.clone());
+                round.extend(others);
+                rounds.push(round);
+            }
+        }
+        rounds
+    };
+    // Pivot pairs are never in conflict, so clearing them after a commit keeps
+    // them from accumulating history and rollback leaves them untouched.
+    let commit = |conflicts: &mut ConflictMatrix, round: &[RawGroup]| {
+        commit_round(conflicts, round);
+        for &p in pivot {
+            for &q in pivot {
+                conflicts.remove_conflict(p, q);
+            }
+        }
+    };
+    maximal_assignments(conflicts, next_rounds, commit, rollback_round)
+}
+
+/// Enumerate all conflict-free groups of size `k` which contain every vertex
+/// of `seed` and none marked in `skip`, in ascending order. The seed itself is
+/// assumed to be conflict-free.
+fn groups_containing(conflicts: &ConflictMatrix, seed: &[usize], k: usize, skip: &BitVec) -> Vec<RawGroup> {
+    let mut res = vec![];
+    let n = conflicts.len();
+    let mut curr = seed.to_vec();
+    if curr.len() == k {
+        res.push(curr);
+        return res;
+    }
+    let is_candidate = |curr: &[usize], col: usize| {
+        !seed.contains(&col)
+            && !skip[col]
+            && curr.iter().all(|row| !conflicts.is_conflicted(*row, col))
+            && !conflicts.completes_hyperedge(curr, col)
+    };
+    // `next[i]` is the next column to try for the `i`th vertex beyond the seed
+    let mut next = vec![0];
+    while let Some(cursor) = next.last_mut() {
+        let col = *cursor;
+        *cursor += 1;
+        if col >= n {
+            next.pop();
+            if curr.len() > seed.len() {
+                curr.pop();
+            }
+            continue;
+        }
+        if !is_candidate(&curr, col) {
+            continue;
+        }
+        curr.push(col);
+        if curr.len() == k {
+            res.push(curr.clone());
+            curr.pop();
+        } else {
+            next.push(col + 1);
+        }
+    }
+    res
+}
+
+/// Cheap feasibility pre-check for whether *any* round can be formed.
+///
+/// Every vertex must land in some group of at least the smallest planned
+/// size `s`, so every vertex must belong to at least one conflict-free set of
+/// `s` vertices. Each vertex is checked greedily, stopping at the first such
+/// set found, which is far cheaper than enumerating full rounds.
+///
+/// This is a *necessary* condition only:
+///
+/// - `false` means no valid round exists, so the exhaustive search can be
+///   skipped entirely.
+/// - `true` means the search is worth running, but it may still find that no
+///   full partition exists.
+pub fn can_form_any_round(conflicts: &ConflictMatrix, min_group_size: usize) -> bool {
+    fn extends_to_group(conflicts: &ConflictMatrix, curr: &mut Vec<usize>, from: usize, k: usize) -> bool {
+        if curr.len() == k {
+            return true;
+        }
+        for col in from..conflicts.len() {
+            if curr.iter().any(|row| *row == col || conflicts.is_conflicted(*row, col)) {
+                continue;
+            }
+            curr.push(col);
+            let found = extends_to_group(conflicts, curr, col + 1, k);
+            curr.pop();
+            if found {
+                return true;
+            }
+        }
+        false
+    }
+
+    let n = conflicts.len();
+    let Some(&k) = group_sizes(n, min_group_size).last() else {
+        return false;
+    };
+    (0..n).all(|v| extends_to_group(conflicts, &mut vec![v], 0, k))
+}
+
+/// Compute the group sizes for a single round, given
+/// `n` total vertices and a minimum group size `min_group_size`.
+///
+/// Produces a vector of sizes that:
+///
+/// - All sum to exactly `n`.
+/// - Are each at least `min_group_size`.
+/// - Distribute any leftover vertices as evenly as possible, starting from the
+///   front (see [`LeftoverPolicy::Distribute`]).
+///
+/// # Example
+///
+/// ```
+/// # use group_generator::group_sizes;
+/// assert_eq!(group_sizes(7, 2), vec![3, 2, 2]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `min_group_size` is 0.
+pub fn group_sizes(n: usize, min_group_size: usize) -> Vec<usize> {
+    assert!(min_group_size > 0, "Minimum group size must be at least 1.");
+    let mut remaining = n % min_group_size;
+    let mut sizes = vec![min_group_size; n / min_group_size]; 
+    if sizes.is_empty() {
+        return sizes;
+    }
+    // Evenly distribute leftover across vector
+    'outer: loop {
+        for i in 0..sizes.len() {
+            if remaining == 0 {
+                break 'outer sizes;
+            }
+            sizes[i] += 1;
+            remaining -= 1;
+        }
+    }
+}
+
+/// Compute the group sizes for a single round (as in [`group_sizes()`]),
+/// checking that no group exceeds `max_group_size`.
+///
+/// [`group_sizes()`] already uses as many groups as the minimum size allows,
+/// spread as evenly as possible, so its largest group is the smallest any
+/// valid shape can have. If that is still too large, no shape fits.
+///
+/// # Example
+///
+/// ```
+/// # use group_generator::{group_sizes_bounded, GroupError};
+/// assert_eq!(group_sizes_bounded(7, 2, 3), Ok(vec![3, 2, 2]));
+/// assert!(group_sizes_bounded(11, 4, 4).is_err());
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::InvalidMinGroupSize`] if `min_group_size` is 0 or exceeds
+///   `n`.
+/// - [`GroupError::NoFeasibleShape`] if every shape has a group larger than
+///   `max_group_size`.
+pub fn group_sizes_bounded(n: usize, min_group_size: usize, max_group_size: usize) -> Result<Vec<usize>, GroupError> {
+    if min_group_size == 0 || min_group_size > n {
+        return Err(GroupError::InvalidMinGroupSize { min_group_size, n });
+    }
+    let sizes = group_sizes(n, min_group_size);
+    if sizes.iter().any(|k| *k > max_group_size) {
+        return Err(GroupError::NoFeasibleShape { n, min_group_size, max_group_size });
+    }
+    Ok(sizes)
+}
+
+/// Compute the group sizes for a single round with exactly `num_groups`
+/// groups, as even as possible, with the larger groups first.
+///
+/// Unlike [`group_sizes()`], the number of groups is fixed (e.g. by the
+/// tables available) and the sizes follow from it, so groups grow as more
+/// vertices attend.
+///
+/// # Example
+///
+/// ```
+/// # use group_generator::group_sizes_with_count;
+/// assert_eq!(group_sizes_with_count(10, 2, 3), Ok(vec![4, 3, 3]));
+/// assert!(group_sizes_with_count(10, 3, 4).is_err());
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::InvalidMinGroupSize`] if `min_group_size` is 0 or exceeds
+///   `n`.
+/// - [`GroupError::InvalidGroupCount`] if `num_groups` is 0, or so large that
+///   the groups would fall below `min_group_size`.
+pub fn group_sizes_with_count(n: usize, min_group_size: usize, num_groups: usize) -> Result<Vec<usize>, GroupError> {
+    if min_group_size == 0 || min_group_size > n {
+        return Err(GroupError::InvalidMinGroupSize { min_group_size, n });
+    }
+    if num_groups == 0 || n / num_groups < min_group_size {
+        return Err(GroupError::InvalidGroupCount { num_groups, n, min_group_size });
+    }
+    Ok((0..num_groups).map(|i| n / num_groups + usize::from(i < n % num_groups)).collect())
+}
+
+/// Where [`group_sizes_with_policy()`] places the vertices left over when `n`
+/// is not a multiple of the minimum group size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum LeftoverPolicy {
+    /// Spread leftovers one per group from the front, e.g. `[3, 2, 2]` for
+    /// `n = 7, min = 2`. This is what [`group_sizes()`] does.
+    #[default]
+    Distribute,
+    /// Keep every group at the minimum except the last, which absorbs all
+    /// leftovers, e.g. `[2, 2, 3]` for `n = 7, min = 2`.
+    TrailingRemainder,
+}
+
+/// Compute the group sizes for a single round (as in [`group_sizes()`]), with
+/// leftover vertices placed according to `policy`.
+///
+/// The sizes are in group order, so passing them to [`single_assignment()`]
+/// produces rounds whose groups follow the same shape.
+///
+/// # Example
+///
+/// ```
+/// # use group_generator::{group_sizes_with_policy, LeftoverPolicy};
+/// assert_eq!(group_sizes_with_policy(7, 2, LeftoverPolicy::TrailingRemainder), vec![2, 2, 3]);
+/// ```
+///
+/// # Panics
+///
+/// Panics if `min_group_size` is 0.
+pub fn group_sizes_with_policy(n: usize, min_group_size: usize, policy: LeftoverPolicy) -> Vec<usize> {
+    match policy {
+        LeftoverPolicy::Distribute => group_sizes(n, min_group_size),
+        LeftoverPolicy::TrailingRemainder => {
+            assert!(min_group_size > 0, "Minimum group size must be at least 1.");
+            let mut sizes = vec![min_group_size; n / min_group_size];
+            if let Some(last) = sizes.last_mut() {
+                *last += n % min_group_size;
+            }
+            sizes
+        }
+    }
+}
+
+/// Generate all possible **single-round** assignments respecting current
+/// conflict constraints.
+///
+/// A *single assignment* is one full round of grouping using the provided
+/// `group_sizes` (which must sum to the total vertex count).
+///
+/// # Behavior
+///
+/// - Each group in the round is conflict-free according to `conflicts`.
+/// - Once a vertex is placed in a group, it is marked as “skipped” for the
+///   remainder of this round.
+/// - All assignments are returned; no maximality filtering occurs here.
+///
+/// # Returns
+///
+/// A list of all valid ways to construct one round:
+pub fn single_assignment(conflicts: &mut ConflictMatrix, group_sizes: &[usize]) -> Vec<Vec<RawGroup>> {
+    let skip = bitvec![0; conflicts.len()];
+    rounds_excluding(conflicts, group_sizes, skip, |_, _, _| true)
+}
+
+/// [`single_assignment()`] for round `round`, building groups only as
+/// `allows` accepts (see [`Slot`]).
+fn filtered_rounds(
+    conflicts: &mut ConflictMatrix,
+    group_sizes: &[usize],
+    round: usize,
+    allows: impl Fn(Slot, &[usize], usize) -> bool,
+) -> Vec<Vec<RawGroup>> {
+    let skip = bitvec![0; conflicts.len()];
+    rounds_excluding(conflicts, group_sizes, skip, |slot, partial, candidate| {
+        allows(Slot { round, ..slot }, partial, candidate)
+    })
+}
+
+/// Where a group is being built: its round, its index within the round and
+/// the number of members it will have.
+///
+/// Group-level rules are checked incrementally while groups are built, as
+/// `allows(slot, partial, candidate)`: whether `candidate` may join the
+/// members chosen so far, `partial`. Members are added in ascending order,
+/// and rules which need the whole group can check it once the candidate
+/// [completes](Slot::completed_by) it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct Slot {
+    pub(crate) round: usize,
+    pub(crate) index: usize,
+    pub(crate) size: usize,
+}
+
+impl Slot {
+    /// Whether one more member after `partial` fills the group.
+    pub(crate) fn completed_by(self, partial: &[usize]) -> bool {
+        partial.len() + 1 == self.size
+    }
+}
+
+/// `partial` with `candidate` added.
+pub(crate) fn with_member(partial: &[usize], candidate: usize) -> Vec<usize> {
+    let mut group = partial.to_vec();
+    group.push(candidate);
+    group
+}
+
+/// Whether `allows` accepts the round of singletons as the first round.
+fn allows_singletons(n: usize, allows: impl Fn(Slot, &[usize], usize) -> bool) -> bool {
+    (0..n).all(|v| allows(Slot { round: 0, index: v, size: 1 }, &[], v))
+}
+
+/// Generate all ways of partitioning the vertices *not* marked in `skip` into
+/// conflict-free groups of the given sizes, building groups only as `allows`
+/// accepts (see [`Slot`], whose round is always 0 here).
+fn rounds_excluding(
+    conflicts: &mut ConflictMatrix,
+    group_sizes: &[usize],
+    skip: BitVec,
+    allows: impl Fn(Slot, &[usize], usize) -> bool,
+) -> Vec<Vec<RawGroup>> {
+    rounds_excluding_where(conflicts, group_sizes, skip, allows, |_, _| true)
+}
+
+/// [`rounds_excluding()`] which only adds a finished group to the groups
+/// chosen so far if `keep(groups, group)`, abandoning every round it rejects.
+pub(crate) fn rounds_excluding_where<V: VertexIndex>(
+    conflicts: &mut ConflictMatrix,
+    group_sizes: &[usize],
+    mut skip: BitVec,
+    allows: impl Fn(Slot, &[V], usize) -> bool,
+    keep: impl Fn(&[RawGroup<V>], &[V]) -> bool,
+) -> Vec<Vec<RawGroup<V>>> {
+    let mut res: Vec<Vec<RawGroup<V>>> = vec![];
+    if group_sizes.is_empty() {
+        return res;
+    }
+    let potential_groups = |conflicts: &mut ConflictMatrix, index: usize, skip: &BitVec| {
+        let slot = Slot { round: 0, index, size: group_sizes[index] };
+        potential_groups_where(conflicts, slot.size, skip, |partial, candidate| allows(slot, partial, candidate))
+    };
+    let mut curr: Vec<RawGroup<V>> = vec![];
+    // Frame `i` holds the candidates for group `i` given the groups in `curr`
+    let mut stack = vec![Frame::new(potential_groups(conflicts, 0, &skip))];
+    while let Some(frame) = stack.last_mut() {
+        let Some(g) = frame.next_option() else {
+            stack.pop();
+            if let Some(g) = curr.pop() {
+                for e in g {
+                    skip.set(e.index(), false)
+                }
+            }
+            continue;
+        };
+        if !keep(&curr, &g) {
+            continue;
+        }
+        if curr.len() == group_sizes.len() - 1 {
+            curr.push(g);
+            res.push(curr.clone());
+            curr.pop();
+        } else {
+            for e in &g {
+                skip.set(e.index(), true)
+            }
+            curr.push(g);
+            stack.push(Frame::new(potential_groups(conflicts, curr.len(), &skip)));
+        }
+    }
+    res
+}
+
+/// Enumerate all possible **groups of size `k`** that are valid given the
+/// conflict matrix and current "skip" mask.
+///
+/// A group is valid if:
+///
+/// - It contains exactly `k` vertices.
+/// - None of the vertices are marked in `skip` (already chosen).
+/// - No pair inside the group has a conflict (`conflicts.is_conflicted(i, j) == true`).
+/// - It does not contain every member of one of the matrix's
+///   [hyperedges](ConflictMatrix::add_hyperedge).
+///
+/// The function **temporarily** marks conflict edges while exploring deeper
+/// combinations to prune invalid partial groups.
+///
+/// # Arguments
+///
+/// - `conflicts`: mutable matrix tracking current conflict constraints.
+/// - `k`: required group size.
+/// - `skip`: bitmask of vertices that are already taken in this round.
+///
+/// # Returns
+///
+/// Eery valid `k`-set of vertex indices. 
+pub fn potential_groups(conflicts: &mut ConflictMatrix, k: usize, skip: &BitVec) -> Vec<RawGroup> {
+    potential_groups_where(conflicts, k, skip, |_, _| true)
+}
+
+/// [`potential_groups()`] which only adds a vertex to the members chosen so
+/// far if `allows(members, vertex)`, abandoning every group it rejects.
+pub(crate) fn potential_groups_where<V: VertexIndex>(
+    conflicts: &mut ConflictMatrix,
+    k: usize,
+    skip: &BitVec,
+    allows: impl Fn(&[V], usize) -> bool,
+) -> Vec<RawGroup<V>> {
+    let mut res = vec![];
+    let n = conflicts.len();
+    for row in 0..n {
+        if skip[row] || !allows(&[], row) {
+            continue;
+        }
+        let mut curr = vec![V::from_index(row)];
+        if k == 1 {
+            res.push(curr);
+            continue;
+        }
+        // `next[i]` is the next column to try after `curr[i]`
+        let mut next = vec![row + 1];
+        while let Some(cursor) = next.last_mut() {
+            let col = *cursor;
+            *cursor += 1;
+            if col >= n {
+                next.pop();
+                if next.is_empty() {
+                    break;
+                }
+                if let Some(&last) = curr.last() {
+                    remove_conflicts(conflicts, last.index(), curr.iter());
+                }
+                curr.pop();
+                continue;
+            }
+            if skip[col]
+                || curr.iter().any(|row| conflicts.is_conflicted(row.index(), col))
+                || conflicts.completes_hyperedge(&curr, col)
+                || !allows(&curr, col)
+            {
+                continue;
+            }
+            curr.push(V::from_index(col));
+            if curr.len() == k {
+                res.push(curr.clone());
+                curr.pop();
+            } else {
+                add_conflicts(conflicts, col, curr.iter());
+                next.push(col + 1);
+            }
+        }
+    }
+    res
+}
+
+/// Mark all pairs inside `between` as mutually conflicting.
+///
+/// The diagonal is left untouched so that [`remove_conflicts_between()`]
+/// restores the matrix exactly.
+#[inline]
+pub(crate) fn add_conflicts_between<V: VertexIndex>(conflicts: &mut ConflictMatrix, between: &[V]) {
+    for (a, i) in between.iter().enumerate() {
+        for j in between[a + 1..].iter().filter(|j| *j != i) {
+            conflicts.add_conflict(i.index(), j.index());
+        }
+    }
+}
+
+/// Remove all conflicts previously added by `add_conflicts_between`.
+#[inline]
+pub(crate) fn remove_conflicts_between<V: VertexIndex>(conflicts: &mut ConflictMatrix, between: &[V]) {
+    for (a, i) in between.iter().enumerate() {
+        for j in &between[a + 1..] {
+            conflicts.remove_conflict(i.index(), j.index());
+        }
+    }
+}
+
+/// Add conflicts between one vertex `col` and all vertices from an iterator.
+#[inline]
+fn add_conflicts<'a, V: VertexIndex + 'a>(conflicts: &mut ConflictMatrix, col: usize, rows: impl Iterator<Item = &'a V>) {
+    for row in rows.map(|row| row.index()).filter(|row| *row != col) {
+        conflicts.add_conflict(row, col);
+    }
+}
+
+/// Remove conflicts previously added by `add_conflicts`.
+#[inline]
+fn remove_conflicts<'a, V: VertexIndex + 'a>(conflicts: &mut ConflictMatrix, col: usize, rows: impl Iterator<Item = &'a V>) {
+    for row in rows.map(|row| row.index()).filter(|row| *row != col) {
+        conflicts.remove_conflict(row, col);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+    use std::sync::OnceLock;
+
+    pub(crate) fn diagonal(n: usize) -> ConflictMatrix {
+        ConflictMatrix::empty(n)
+    }
+
+    /// The original natively recursive search, kept as a reference for the
+    /// explicit-stack implementation.
+    mod recursive {
+        use super::super::{add_conflicts, add_conflicts_between, can_form_any_round, group_sizes, remove_conflicts, remove_conflicts_between, ConflictMatrix, RawGroup};
+        use bitvec::prelude::*;
+
+        pub fn make_assignments(conflicts: &mut ConflictMatrix, min_group_size: usize) -> Vec<Vec<Vec<RawGroup>>> {
+            assert!(!conflicts.is_empty(), "Cannot make groups from 0 vertices.");
+            assert!(min_group_size <= conflicts.len(), "Cannot require groups larger than the number of potential vertices.");
+
+            fn backtrack(
+                conflicts: &mut ConflictMatrix,
+                sols: &mut Vec<Vec<Vec<RawGroup>>>,
+                curr: &mut Vec<Vec<RawGroup>>,
+                best: &mut usize,
+                group_sizes: &[usize],
+            ) {
+                let options = single_assignment(conflicts, group_sizes);
+                if options.is_empty() && curr.len() >= *best {
+                    if curr.len() > *best {
+                        sols.clear();
+                    }
+                    sols.push(curr.clone());
+                    *best = curr.len();
+                } else {
+                    for opt in options {
+                        for g in &opt {
+                            add_conflicts_between(conflicts, g);
+                        }
+                        curr.push(opt);
+                        backtrack(conflicts, sols, curr, best, group_sizes);
+                        if let Some(opt) = curr.pop() {
+                            for g in &opt {
+                                remove_conflicts_between(conflicts, g);
+                            }
+                        }
+                    }
+                }
+            }
+            if !can_form_any_round(conflicts, min_group_size) {
+                return vec![vec![]];
+            }
+            let mut sols = vec![];
+            let mut curr = vec![];
+            let mut best = 0;
+            let group_sizes = group_sizes(conflicts.len(), min_group_size);
+            backtrack(conflicts, &mut sols, &mut curr, &mut best, &group_sizes);
+            sols
+        }
+
+        pub fn single_assignment(conflicts: &mut ConflictMatrix, group_sizes: &[usize]) -> Vec<Vec<RawGroup>> {
+            fn backtrack(
+                conflicts: &mut ConflictMatrix,
+                sols: &mut Vec<Vec<RawGroup>>,
+                curr: &mut Vec<RawGroup>,
+                group_sizes: &[usize],
+                skip: &mut BitVec,
+            ) {
+                let k = group_sizes[curr.len()];
+                for g in potential_groups(conflicts, k, skip) {
+                    if curr.len() == group_sizes.len() - 1 {
+                        curr.push(g);
+                        sols.push(curr.clone());
+                        curr.pop();
+                    } else {
+                        for e in &g {
+                            skip.set(*e, true)
+                        }
+                        curr.push(g);
+                        backtrack(conflicts, sols, curr, group_sizes, skip);
+                        if let Some(g) = curr.pop() {
+                            for e in g {
+                                skip.set(e, false)
+                            }
+                        }
+                    }
+                }
+            }
+
+            let n = conflicts.len();
+            let mut res: Vec<Vec<RawGroup>> = vec![];
+            let mut skip = bitvec![0; n];
+            let mut curr = vec![];
+            backtrack(conflicts, &mut res, &mut curr, group_sizes, &mut skip);
+            res
+        }
+
+        pub fn potential_groups(conflicts: &mut ConflictMatrix, k: usize, skip: &BitVec) -> Vec<RawGroup> {
+            fn backtrack(
+                conflicts: &mut ConflictMatrix,
+                sols: &mut Vec<Vec<usize>>,
+                curr: &mut Vec<usize>,
+                row: usize,
+                n: usize,
+                k: usize,
+                skip: &BitVec,
+            ) {
+                for col in (row + 1)..n {
+                    if skip[col] {
+                        continue;
+                    }
+                    let is_valid = curr.iter().all(|row| !conflicts.is_conflicted(*row, col));
+                    if is_valid {
+                        curr.push(col);
+                        if curr.len() == k {
+                            sols.push(curr.clone());
+                        } else {
+                            add_conflicts(conflicts, col, curr.iter());
+                            backtrack(conflicts, sols, curr, col, n, k, skip);
+                            remove_conflicts(conflicts, col, curr.iter());
+                        }
+                        curr.pop();
+                    }
+                }
+            }
+
+            let mut res = vec![];
+            let n = conflicts.len();
+            for row in 0..n {
+                if skip[row] {
+                    continue;
+                }
+                let mut curr = vec![row];
+                backtrack(conflicts, &mut res, &mut curr, row, n, k, skip);
+            }
+            res
+        }
+    }
+
+    /// All maximal assignments for the complete graph on 7 vertices with
+    /// minimum group size 2, computed once and shared between tests.
+    pub(crate) fn complete_seven_two() -> &'static Vec<Vec<Vec<RawGroup>>> {
+        static SOLS: OnceLock<Vec<Vec<Vec<RawGroup>>>> = OnceLock::new();
+        SOLS.get_or_init(|| make_assignments(&mut diagonal(7), 2))
+    }
+
+    #[test]
+    fn no_conflicts() {
+        let n = 5;
+        let k = 3;
+        let mut conflicts = diagonal(n);
+        let skip = bitvec![0; n];
+        let res = potential_groups(&mut conflicts, k, &skip);
+        assert_eq!(res.len(), 10);
+    }
+
+    fn test_single_assignment(n: usize, k: usize) {
+        let mut conflicts = diagonal(n);
+        let group_sizes = group_sizes(n, k);
+        let res = single_assignment(&mut conflicts, &group_sizes);
+        for groups in res {
+            let mut seen: HashSet<usize> = HashSet::new();
+            let mut count = 0;
+            for g in groups {
+                count += g.len();
+                seen.extend(&g);
+            }
+            assert_eq!(count, n);
+            assert_eq!(seen.len(), count);
+        }
+    }
+
+    #[test]
+    fn odd_complete_graph_single_assignment() {
+        let tests = [
+            (3, 2),
+            (5, 2),
+        ];
+        for (n, k) in tests {
+            test_single_assignment(n, k);
+        }
+    }
+
+    #[test]
+    fn even_complete_graph_single_assignment() {
+        let tests = [
+            (2, 2),
             (4, 2),
             (6, 2),
             (6, 3),
@@ -410,14 +3966,938 @@ mod tests {
         }
     }
 
+    #[test]
+    fn isolated_vertex_cannot_form_round() {
+        let n = 4;
+        let mut conflicts = conflicting_with_all(diagonal(n), 0);
+        assert!(!can_form_any_round(&conflicts, 2));
+        let res = make_assignments(&mut conflicts, 2);
+        assert_eq!(res, vec![Vec::<Vec<RawGroup>>::new()]);
+        assert!(can_form_any_round(&diagonal(n), 2));
+    }
+
+    #[test]
+    fn best_partner_spread_only() {
+        let best = make_assignments_by_partner_spread(&mut diagonal(4), 2, true);
+        assert_eq!(best.len(), 1);
+        assert_eq!(distinct_partners(&best[0], 4), vec![3; 4]);
+    }
+
+    #[test]
+    fn iterative_matches_recursive() {
+        let tests = [(3, 2), (4, 2), (5, 2), (5, 3), (6, 3), (6, 4)];
+        for (n, k) in tests {
+            let iterative = make_assignments(&mut diagonal(n), k);
+            let reference = recursive::make_assignments(&mut diagonal(n), k);
+            assert_eq!(iterative, reference, "Mismatch for n = {n}, k = {k}");
+        }
+        let mut conflicts = diagonal(5);
+        conflicts.add_conflict(0, 3);
+        let iterative = make_assignments(&mut conflicts.clone(), 2);
+        assert_eq!(iterative, recursive::make_assignments(&mut conflicts, 2));
+    }
+
+    #[test]
+    fn deep_search_on_small_stack() {
+        // K6 minus a perfect matching: four rounds deep with a large tree
+        let handle = std::thread::Builder::new()
+            .stack_size(64 * 1024)
+            .spawn(|| {
+                let mut conflicts = diagonal(6);
+                for i in (0..6).step_by(2) {
+                    conflicts.add_conflict(i, i + 1);
+                }
+                make_assignments(&mut conflicts, 2)
+            })
+            .unwrap();
+        let res = handle.join().expect("Search should not overflow a small stack");
+        assert!(res.iter().all(|a| a.len() == 4));
+    }
+
+    fn assert_pivot_together(res: &[Vec<Vec<RawGroup>>], pivot: &[usize]) {
+        for assignment in res {
+            for round in assignment {
+                assert!(round.iter().any(|g| pivot.iter().all(|p| g.contains(p))));
+            }
+        }
+    }
+
+    #[test]
+    fn pivot_sits_together_every_round() {
+        let n = 6;
+        let pivot = [0, 1];
+        let mut conflicts = diagonal(n);
+        let res = make_assignments_with_pivot(&mut conflicts, 2, &pivot);
+        assert_eq!(res[0].len(), 3);
+        assert_pivot_together(&res, &pivot);
+        for assignment in &res {
+            let partners = distinct_partners(assignment, n);
+            assert_eq!(partners[0], 1);
+            assert!(partners[2..].iter().all(|&p| p == 3));
+        }
+    }
+
+    #[test]
+    fn pivot_table_rotates_extra_seats() {
+        let pivot = [2, 4];
+        let res = make_assignments_with_pivot(&mut diagonal(7), 2, &pivot);
+        assert_eq!(res[0].len(), 5);
+        assert_pivot_together(&res, &pivot);
+        for assignment in &res {
+            let mut guests: Vec<usize> = assignment.iter().map(|round| round[0][2]).collect();
+            guests.sort();
+            assert_eq!(guests, vec![0, 1, 3, 5, 6]);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn conflicting_pivot_panics() {
+        let mut conflicts = diagonal(4);
+        conflicts.add_conflict(0, 1);
+        make_assignments_with_pivot(&mut conflicts, 2, &[0, 1]);
+    }
+
+    #[test]
+    fn bounded_max_rounds_matches_enumeration() {
+        let mut conflicted = diagonal(5);
+        conflicted.add_conflict(0, 3);
+        for (conflicts, k) in [(diagonal(4), 2), (diagonal(5), 2), (diagonal(6), 3), (conflicted, 2)] {
+            let mut conflicts = conflicts;
+            let expected = make_assignments(&mut conflicts.clone(), k)[0].len();
+            assert_eq!(max_rounds(&mut conflicts, k), expected);
+        }
+        // Reaches the bound (a 1-factorization) without exhausting K6
+        assert_eq!(max_rounds(&mut diagonal(6), 2), 5);
+    }
+
+    #[test]
+    fn exact_rounds_lists_every_prefix() {
+        let mut prefixes: Vec<_> = make_assignments(&mut diagonal(4), 2).into_iter().map(|a| a[..2].to_vec()).collect();
+        prefixes.dedup();
+        assert_eq!(make_assignments_exact_rounds(&mut diagonal(4), 2, 2), Ok(prefixes));
+        assert_eq!(make_assignments_exact_rounds(&mut diagonal(4), 2, 3), Ok(make_assignments(&mut diagonal(4), 2)));
+        assert_eq!(make_assignments_exact_rounds(&mut diagonal(5), 2, 0), Ok(vec![vec![]]));
+        assert_eq!(make_assignments_exact_rounds(&mut diagonal(3), 1, 1), Ok(vec![vec![singletons(3)]]));
+        let err = make_assignments_exact_rounds(&mut diagonal(6), 3, 2);
+        assert_eq!(err, Err(GroupError::UnreachableRounds { rounds: 2, max_rounds: 1 }));
+        let err = make_assignments_exact_rounds(&mut diagonal(3), 1, 2);
+        assert_eq!(err, Err(GroupError::UnreachableRounds { rounds: 2, max_rounds: 1 }));
+    }
+
+    #[test]
+    fn reachable_rounds_match_max_rounds() {
+        let mut conflicted = diagonal(5);
+        conflicted.add_conflict(0, 3);
+        for (conflicts, k) in [(diagonal(4), 2), (diagonal(6), 2), (diagonal(6), 3), (conflicted, 2), (diagonal(3), 1)] {
+            let mut conflicts = conflicts;
+            let original = conflicts.clone();
+            let best = max_rounds(&mut conflicts, k);
+            for rounds in 0..=best + 1 {
+                assert_eq!(can_reach_rounds(&mut conflicts, k, rounds), rounds <= best);
+            }
+            assert_eq!(conflicts, original);
+        }
+    }
+
+    #[test]
+    fn rounds_remaining_depends_on_candidate() {
+        let mut conflicts = diagonal(6);
+        for (u, v) in [(0, 1), (2, 3)] {
+            conflicts.add_conflict(u, v);
+        }
+        let original = conflicts.clone();
+        assert_eq!(max_rounds(&mut conflicts, 2), 4);
+        let restrictive = vec![vec![0, 2], vec![1, 3], vec![4, 5]];
+        let flexible = vec![vec![0, 2], vec![1, 4], vec![3, 5]];
+        assert_eq!(rounds_remaining_after(&mut conflicts, 2, &restrictive), 2);
+        assert_eq!(rounds_remaining_after(&mut conflicts, 2, &flexible), 3);
+        assert_eq!(conflicts, original);
+    }
+
+    #[test]
+    fn search_restores_conflicts() {
+        let mut conflicts = diagonal(5);
+        conflicts.add_conflict(1, 4);
+        let original = conflicts.clone();
+        make_assignments(&mut conflicts, 2);
+        assert_eq!(conflicts, original);
+    }
+
+    #[test]
+    fn singleton_groups_form_one_round() {
+        let n = 4;
+        let expected = vec![vec![vec![0], vec![1], vec![2], vec![3]]];
+        assert_eq!(make_assignments(&mut diagonal(n), 1), vec![expected]);
+        assert_eq!(max_rounds(&mut diagonal(n), 1), 1);
+        let skip = bitvec![0, 1, 0, 0];
+        assert_eq!(potential_groups(&mut diagonal(n), 1, &skip), vec![vec![0], vec![2], vec![3]]);
+        let res = make_assignments_with_pivot(&mut diagonal(n), 1, &[2]);
+        assert!(res.iter().all(|a| a.len() == 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "Minimum group size must be at least 1.")]
+    fn zero_min_group_size_panics() {
+        make_assignments(&mut diagonal(3), 0);
+    }
+
+    #[test]
+    fn invalid_input_is_reported() {
+        assert_eq!(try_make_assignments(&mut diagonal(0), 2), Err(GroupError::NoVertices));
+        let err = try_make_assignments(&mut diagonal(3), 4);
+        assert_eq!(err, Err(GroupError::InvalidMinGroupSize { min_group_size: 4, n: 3 }));
+        assert_eq!(try_make_assignments(&mut diagonal(4), 2), Ok(make_assignments(&mut diagonal(4), 2)));
+    }
+
+    #[test]
+    fn upper_bound_and_efficiency() {
+        let tests = [(4, 2, 3, 1.0), (5, 2, 2, 0.5), (6, 3, 2, 0.5)];
+        for (n, k, bound, efficiency) in tests {
+            assert_eq!(max_rounds_upper_bound(&diagonal(n), k), bound);
+            assert_eq!(schedule_efficiency(&mut diagonal(n), k), efficiency);
+        }
+        let mut isolated = conflicting_with_all(diagonal(4), 0);
+        assert_eq!(max_rounds_upper_bound(&isolated, 2), 0);
+        assert_eq!(schedule_efficiency(&mut isolated, 2), 1.0);
+    }
+
+    #[test]
+    fn continuing_from_history() {
+        let history = vec![vec![vec![0, 1, 2], vec![3, 4, 5]]];
+        let mut conflicts = diagonal(6);
+        let res = make_assignments_continuing(&mut conflicts, 3, &history).unwrap();
+        assert_eq!(conflicts, diagonal(6));
+        let expected: Vec<_> = make_assignments(&mut diagonal(6), 3)
+            .into_iter()
+            .filter(|a| a[0] == history[0])
+            .collect();
+        assert!(!res.is_empty());
+        assert_eq!(res, expected);
+
+        let complete = make_first_assignment(&mut diagonal(4), 2);
+        assert_eq!(make_assignments_continuing(&mut diagonal(4), 2, &complete).unwrap(), vec![complete]);
+        assert_eq!(make_assignments_continuing(&mut diagonal(3), 1, &[singletons(3)]).unwrap(), vec![vec![singletons(3)]]);
+    }
+
+    #[test]
+    fn continuing_rejects_invalid_history() {
+        let mut conflicts = diagonal(4);
+        let repeated = vec![vec![vec![0, 1], vec![2, 3]], vec![vec![1, 0], vec![2, 3]]];
+        assert_eq!(make_assignments_continuing(&mut conflicts, 2, &repeated), Err(GroupError::InvalidRound));
+        assert_eq!(conflicts, diagonal(4));
+        let partial = vec![vec![vec![0, 1]]];
+        assert_eq!(make_assignments_continuing(&mut conflicts, 2, &partial), Err(GroupError::InvalidRound));
+    }
+
+    #[test]
+    fn explicit_shapes() {
+        let shaped = make_assignments_shaped(&mut diagonal(6), &[3, 3]).unwrap();
+        assert_eq!(shaped, make_assignments(&mut diagonal(6), 3));
+        let trailing = make_assignments_shaped(&mut diagonal(5), &[2, 3]).unwrap();
+        assert!(trailing.iter().flatten().all(|round| round.iter().map(Vec::len).eq([2, 3])));
+        assert_eq!(make_assignments_shaped(&mut diagonal(3), &[1, 1, 1]).unwrap(), vec![vec![singletons(3)]]);
+        for bad in [&[][..], &[3, 0, 2], &[2, 2]] {
+            assert_eq!(make_assignments_shaped(&mut diagonal(5), bad), Err(GroupError::InvalidGroupShape { n: 5 }));
+        }
+    }
+
+    #[test]
+    fn per_round_minimums() {
+        assert_eq!(make_assignments_per_round(&mut diagonal(6), &[3]).unwrap(), make_assignments(&mut diagonal(6), 3));
+        // After a round of pairs, only one round of triples avoids them all
+        let sols = make_assignments_per_round(&mut diagonal(6), &[2, 3]).unwrap();
+        assert_eq!(sols[0], vec![vec![vec![0, 1], vec![2, 3], vec![4, 5]], vec![vec![0, 2, 4], vec![1, 3, 5]]]);
+        assert!(sols.iter().all(|a| a.len() == 2 && a[0].len() == 3 && a[1].len() == 2));
+        assert!(sols.iter().all(|a| validate_assignment(a, 6, 2)));
+        assert_eq!(make_assignments_per_round(&mut diagonal(3), &[1, 2]).unwrap(), vec![vec![singletons(3)]]);
+        let err = GroupError::InvalidMinGroupSize { min_group_size: 0, n: 4 };
+        assert_eq!(make_assignments_per_round(&mut diagonal(4), &[]), Err(err));
+        let err = GroupError::InvalidMinGroupSize { min_group_size: 5, n: 4 };
+        assert_eq!(make_assignments_per_round(&mut diagonal(4), &[2, 5]), Err(err));
+    }
+
+    #[test]
+    fn trajectory_orders_mixed_shapes() {
+        // Two groups is a round of triples, three a round of pairs
+        let group_counts = |a: &Vec<Vec<RawGroup>>| a.iter().map(|round| round.len()).collect::<Vec<_>>();
+        let decreasing = make_assignments_with_trajectory(&mut diagonal(6), &[2, 3], 2, RoundTrajectory::Decreasing).unwrap();
+        let increasing = make_assignments_with_trajectory(&mut diagonal(6), &[3, 2], 2, RoundTrajectory::Increasing).unwrap();
+        assert_eq!(group_counts(&decreasing[0]), vec![2, 3]);
+        assert_eq!(group_counts(&increasing[0]), vec![3, 2]);
+        assert_eq!(decreasing.len(), increasing.len());
+        assert!(decreasing.iter().all(|a| validate_assignment(a, 6, 2)));
+        // Pairs then triples, as fixed by `make_assignments_per_round()`, is one of the ties
+        let per_round = make_assignments_per_round(&mut diagonal(6), &[2, 3]).unwrap();
+        assert!(per_round.iter().all(|a| decreasing.contains(a)));
+        let err = GroupError::UnreachableRounds { rounds: 4, max_rounds: 3 };
+        assert_eq!(make_assignments_with_trajectory(&mut diagonal(4), &[2, 4], 4, RoundTrajectory::Flat), Err(err));
+    }
+
+    #[test]
+    fn shared_solves_run_concurrently() {
+        let conflicts = conflicts_from_groups(6, &[0, 0, 1, 1, 2, 2]);
+        let expected = make_assignments(&mut conflicts.clone(), 3);
+        let results: Vec<_> = std::thread::scope(|s| {
+            let handles: Vec<_> = (0..2).map(|_| s.spawn(|| make_assignments_shared(&conflicts, 3))).collect();
+            handles.into_iter().map(|h| h.join().unwrap()).collect()
+        });
+        assert!(results.iter().all(|r| *r == expected));
+        assert_eq!(conflicts, conflicts_from_groups(6, &[0, 0, 1, 1, 2, 2]));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn memoized_matches_plain_search() {
+        let structured = conflicts_from_groups(6, &[0, 0, 1, 1, 2, 2]);
+        for (conflicts, k) in [(diagonal(4), 2), (diagonal(5), 3), (structured, 3)] {
+            let plain = make_assignments(&mut conflicts.clone(), k);
+            let (memoized, stats) = make_assignments_memoized(&mut conflicts.clone(), k);
+            assert_eq!(plain, memoized);
+            assert!(stats.misses > 0);
+        }
+        let (_, stats) = make_assignments_memoized(&mut diagonal(4), 2);
+        // Both orders of each perfect matching lead to the same state
+        assert!(stats.hits > 0);
+        assert!(stats.hit_rate() > 0.5);
+    }
+
+    #[test]
+    fn highest_affinity_first() {
+        let n = 5;
+        let mut affinity = vec![vec![0; n]; n];
+        affinity[0][1] = 5;
+        affinity[3][4] = 1;
+        let res = make_assignments_affinity(&mut diagonal(n), 2, &affinity);
+        assert_eq!(res.len(), make_assignments(&mut diagonal(n), 2).len());
+        let scores: Vec<u64> = res.iter().map(|a| affinity_score(a, &affinity)).collect();
+        assert!(scores.windows(2).all(|w| w[0] >= w[1]));
+        assert_eq!(scores[0], 6);
+        assert!(scores.last() < scores.first());
+        let groups = &res[0][0];
+        assert!(groups.iter().any(|g| g.contains(&0) && g.contains(&1)));
+        assert!(groups.iter().any(|g| g.contains(&3) && g.contains(&4)));
+    }
+
+    #[test]
+    fn new_partners_every_round() {
+        // In pairs, a new partner means an unmet pair, so no repeats at all
+        assert_eq!(make_assignments_with_new_partners(&mut diagonal(4), 2, 3, 1), make_assignments(&mut diagonal(4), 2));
+        assert_eq!(make_assignments_with_new_partners(&mut diagonal(4), 2, 2, 0), make_assignments_with_repeats(&mut diagonal(4), 2, 2));
+        let none = make_assignments_with_new_partners(&mut diagonal(4), 2, 2, 2);
+        assert!(none.len() == 1 && none[0].is_empty());
+        let sols = make_assignments_with_new_partners(&mut diagonal(5), 2, 2, 1);
+        for a in &sols {
+            for (i, round) in a.iter().enumerate() {
+                let before = meeting_counts(&a[..i], 5);
+                assert!(round.iter().all(|g| g.iter().all(|u| g.iter().any(|v| v != u && before[*u][*v] == 0))));
+            }
+        }
+    }
+
+    #[test]
+    fn large_groups_are_shared_out() {
+        let mut conflicts = diagonal(7);
+        for (u, v) in [(0, 1), (2, 3), (4, 5)] {
+            conflicts.add_conflict(u, v);
+        }
+        let all = make_assignments(&mut conflicts.clone(), 2);
+        let within = |a: &&Vec<Vec<RawGroup>>| (0..7).all(|v| a.iter().filter(|round| round[0].contains(&v)).count() <= 2);
+        let expected: Vec<_> = all.iter().filter(within).cloned().collect();
+        assert!(!expected.is_empty() && expected.len() < all.len());
+        assert_eq!(make_assignments_large_group_fair(&mut conflicts, 2, 2), expected);
+        let zero = make_assignments_large_group_fair(&mut diagonal(5), 2, 0);
+        assert!(zero.len() == 1 && zero[0].is_empty());
+        assert_eq!(make_assignments_large_group_fair(&mut diagonal(4), 2, 0), make_assignments(&mut diagonal(4), 2));
+        assert_eq!(large_group_quota(5, 2, 2), 2);
+    }
+
+    #[test]
+    fn relaxed_mode_minimizes_repeats() {
+        let repeats = |a: &[Vec<RawGroup>]| -> usize {
+            let counts = meeting_counts(a, 4);
+            (0..4).flat_map(|u| (u + 1..4).map(move |v| (u, v))).map(|(u, v)| counts[u][v].saturating_sub(1)).sum()
+        };
+        let rounds = single_assignment(&mut diagonal(4), &[2, 2]);
+        let mut all: Vec<Vec<Vec<RawGroup>>> = vec![vec![]];
+        for _ in 0..4 {
+            all = all.into_iter().flat_map(|a| rounds.iter().map(move |r| [a.clone(), vec![r.clone()]].concat())).collect();
+        }
+        let fewest = all.iter().map(|a| repeats(a)).min().unwrap();
+        let expected: Vec<_> = all.into_iter().filter(|a| repeats(a) == fewest).collect();
+        assert_eq!(fewest, 2);
+        assert_eq!(make_assignments_relaxed(&mut diagonal(4), 2, 4), expected);
+        assert_eq!(make_assignments_relaxed(&mut diagonal(4), 2, 2), make_assignments_exact_rounds(&mut diagonal(4), 2, 2).unwrap());
+        assert_eq!(make_assignments_relaxed(&mut diagonal(2), 1, 2), vec![vec![singletons(2); 2]]);
+        let mut conflicts = diagonal(3);
+        conflicts.add_conflict(0, 1);
+        assert!(make_assignments_relaxed(&mut conflicts, 3, 2).is_empty());
+    }
+
+    #[test]
+    fn max_affinity_keeps_top_ties() {
+        let n = 5;
+        let mut affinity = vec![vec![0; n]; n];
+        affinity[0][1] = 5;
+        affinity[3][4] = 1;
+        let ranked = make_assignments_affinity(&mut diagonal(n), 2, &affinity);
+        let top = affinity_score(&ranked[0], &affinity);
+        let expected: Vec<_> = ranked.iter().take_while(|a| affinity_score(a, &affinity) == top).cloned().collect();
+        assert_eq!(make_assignments_max_affinity(&mut diagonal(n), 2, &affinity), expected);
+        let flat = vec![vec![0; n]; n];
+        assert_eq!(make_assignments_max_affinity(&mut diagonal(n), 2, &flat), make_assignments(&mut diagonal(n), 2));
+    }
+
+    #[test]
+    fn soft_conflicts_minimize_penalty() {
+        let n = 5;
+        let mut penalties = vec![vec![0; n]; n];
+        penalties[0][1] = 2;
+        penalties[2][3] = 1;
+        penalties[3][2] = 1;
+        let mut conflicts = diagonal(n);
+        conflicts.add_conflict(1, 4);
+        let all = make_assignments(&mut conflicts.clone(), 2);
+        let lowest = all.iter().map(|a| soft_conflict_penalty(a, &penalties)).min().unwrap();
+        let expected: Vec<_> = all.iter().filter(|a| soft_conflict_penalty(a, &penalties) == lowest).cloned().collect();
+        assert!(expected.len() < all.len());
+        let original = conflicts.clone();
+        assert_eq!(make_assignments_soft_conflicts(&mut conflicts, 2, &penalties), expected);
+        assert_eq!(conflicts, original);
+    }
+
+    #[test]
+    fn robust_mode_avoids_minimal_groups() {
+        let plain = make_assignments(&mut diagonal(5), 2);
+        assert!(plain.iter().all(|a| dropout_robustness(a, 2).iter().any(|(_, rounds)| !rounds.is_empty())));
+        let robust = make_assignments_robust(&mut diagonal(5), 2, 1);
+        assert!(!robust.is_empty());
+        assert_eq!(make_assignments_robust(&mut diagonal(5), 2, 0), plain);
+        for assignment in &robust {
+            assert!(assignment.iter().flatten().all(|g| g.len() > 2));
+            assert!(dropout_robustness(assignment, 2).iter().all(|(_, rounds)| rounds.is_empty()));
+        }
+    }
+
+    #[test]
+    fn weighted_groups_fill_their_seats() {
+        let weights = [2, 1, 1, 1, 1, 1, 1, 0];
+        let mut conflicts = diagonal(8).with_pairs([(0, 1)]);
+        let res = make_assignments_weighted(&mut conflicts, 4, &weights).unwrap();
+        assert_eq!(conflicts, diagonal(8).with_pairs([(0, 1)]));
+        assert!(!res.is_empty());
+        for assignment in &res {
+            assert_eq!(assignment.len(), 1);
+            assert!(validate_assignment(assignment, 8, 1));
+            for g in assignment.iter().flatten() {
+                assert_eq!(g.iter().map(|v| weights[*v]).sum::<usize>(), 4);
+                assert!(!(g.contains(&0) && g.contains(&1)));
+            }
+        }
+        assert_eq!(make_assignments_weighted(&mut diagonal(3), 2, &[1, 1]), Err(GroupError::AttributeMismatch { len: 2, n: 3 }));
+        assert_eq!(make_assignments_weighted(&mut diagonal(2), 5, &[2, 2]), Err(GroupError::InvalidMinGroupSize { min_group_size: 5, n: 4 }));
+    }
+
+    #[test]
+    fn together_pairs_share_every_round() {
+        let res = make_assignments_together(&mut diagonal(6), 2, &[(0, 1)]).unwrap();
+        assert!(!res.is_empty());
+        for assignment in &res {
+            assert_eq!(assignment.len(), 3);
+            assert!(assignment.iter().all(|round| round.contains(&vec![0, 1])));
+            let counts = meeting_counts(assignment, 6);
+            assert!((2..6).all(|u| (0..6).all(|v| counts[u][v] <= 1)));
+        }
+        let mut conflicts = diagonal(4).with_pairs([(0, 1)]);
+        let res = make_assignments_together(&mut conflicts, 2, &[(0, 1)]).unwrap();
+        assert!(res.len() == 1 && res[0].is_empty());
+        assert_eq!(conflicts, diagonal(4).with_pairs([(0, 1)]));
+    }
+
+    #[test]
+    fn covering_requires_every_pair() {
+        // Some, but not all, maximal assignments bring 0 and 1 together
+        let res = make_assignments_covering(&mut diagonal(5), 2, &[(0, 1)]).unwrap();
+        let all = make_assignments(&mut diagonal(5), 2);
+        let expected: Vec<_> = all.iter().filter(|a| meeting_counts(a, 5)[0][1] == 1).cloned().collect();
+        assert!(!expected.is_empty() && expected.len() < all.len());
+        assert_eq!(res, expected);
+        assert_eq!(make_assignments_covering(&mut diagonal(5), 2, &[]).unwrap(), all);
+
+        let mut conflicts = diagonal(4).with_pairs([(0, 1)]);
+        assert!(make_assignments_covering(&mut conflicts, 2, &[(0, 1)]).unwrap().is_empty());
+        assert_eq!(make_assignments_covering(&mut conflicts, 2, &[(0, 4)]), Err(GroupError::VertexOutOfRange { vertex: 4, n: 4 }));
+    }
+
+    #[test]
+    fn pins_fix_group_slots() {
+        let pins = [Pin { vertex: 0, round: 0, group: 1 }, Pin { vertex: 1, round: 0, group: 1 }];
+        let res = make_assignments_pinned(&mut diagonal(6), 3, &pins).unwrap();
+        let all = make_assignments(&mut diagonal(6), 3);
+        let expected: Vec<_> = all.iter().filter(|a| a[0][1].contains(&0) && a[0][1].contains(&1)).cloned().collect();
+        assert!(!expected.is_empty());
+        assert_eq!(res, expected);
+
+        let split = [Pin { vertex: 0, round: 0, group: 0 }, Pin { vertex: 0, round: 0, group: 1 }];
+        let res = make_assignments_pinned(&mut diagonal(6), 3, &split).unwrap();
+        assert!(res.len() == 1 && res[0].is_empty());
+        let far = Pin { vertex: 0, round: 0, group: 2 };
+        assert_eq!(
+            make_assignments_pinned(&mut diagonal(6), 3, &[far]),
+            Err(GroupError::GroupIndexOutOfRange { group: 2, num_groups: 2 })
+        );
+    }
+
+    #[test]
+    fn forbidden_sets_never_share_a_group() {
+        let forbidden = [vec![0, 1, 2], vec![3, 4]];
+        let res = make_assignments_forbidding(&mut diagonal(6), 3, &forbidden).unwrap();
+        let expected: Vec<_> = make_assignments(&mut diagonal(6).with_pairs([(3, 4)]), 3)
+            .into_iter()
+            .filter(|a| a.iter().flatten().all(|g| g != &[0, 1, 2]))
+            .collect();
+        assert_eq!(res, expected);
+        assert!(!res.is_empty());
+        let res = make_assignments_forbidding(&mut diagonal(4), 2, &[vec![1]]).unwrap();
+        assert!(res.len() == 1 && res[0].is_empty());
+        assert_eq!(make_assignments_forbidding(&mut diagonal(4), 2, &[vec![0, 4]]), Err(GroupError::VertexOutOfRange { vertex: 4, n: 4 }));
+    }
+
+    #[test]
+    fn candidates_cover_every_round() {
+        let trios: Vec<Vec<usize>> = vec![vec![0, 1, 2], vec![3, 4, 5], vec![0, 3, 4], vec![1, 2, 5], vec![0, 1, 5], vec![2, 3, 4]];
+        let res = make_assignments_from_candidates(&mut diagonal(6), &trios).unwrap();
+        assert_eq!(res, vec![vec![vec![vec![0, 1, 2], vec![3, 4, 5]]], vec![vec![vec![0, 1, 5], vec![2, 3, 4]]], vec![vec![vec![0, 3, 4], vec![1, 2, 5]]]]);
+
+        let mut conflicts = diagonal(4).with_pairs([(0, 1)]);
+        let pairs = [vec![0, 1], vec![2, 3], vec![0, 2], vec![1, 3], vec![0, 3], vec![1, 2]];
+        let res = make_assignments_from_candidates(&mut conflicts, &pairs).unwrap();
+        assert_eq!(conflicts, diagonal(4).with_pairs([(0, 1)]));
+        assert_eq!(res.len(), 2);
+        assert!(res.iter().all(|a| a.len() == 2 && validate_assignment(a, 4, 2)));
+        let res = make_assignments_from_candidates(&mut diagonal(3), &[vec![0, 1]]).unwrap();
+        assert!(res.len() == 1 && res[0].is_empty());
+        assert_eq!(make_assignments_from_candidates(&mut diagonal(3), &[vec![3]]), Err(GroupError::VertexOutOfRange { vertex: 3, n: 3 }));
+    }
+
+    #[test]
+    fn repeats_allow_pairs_to_meet_again() {
+        assert_eq!(make_assignments_with_repeats(&mut diagonal(4), 2, 1), make_assignments(&mut diagonal(4), 2));
+        let mut conflicts = diagonal(4);
+        conflicts.add_conflict(0, 1);
+        let res = make_assignments_with_repeats(&mut conflicts, 2, 2);
+        assert_eq!(conflicts, diagonal(4).with_pairs([(0, 1)]));
+        assert!(!res.is_empty());
+        for assignment in &res {
+            assert_eq!(assignment.len(), 4);
+            let counts = meeting_counts(assignment, 4);
+            assert_eq!(counts[0][1], 0);
+            assert!(counts.iter().flatten().all(|c| *c <= 2));
+        }
+    }
+
+    #[test]
+    fn hosts_keep_their_slots() {
+        let mut conflicts = diagonal(6);
+        let res = make_assignments_with_hosts(&mut conflicts, 3, &[3, 1]).unwrap();
+        assert_eq!(conflicts, diagonal(6));
+        assert!(!res.is_empty());
+        for assignment in &res {
+            assert!(validate_assignment(assignment, 6, 3));
+            assert!(assignment.iter().all(|round| round[0].contains(&3) && round[1].contains(&1)));
+        }
+        let unhosted = make_assignments(&mut diagonal(6), 3);
+        let expected: Vec<_> = unhosted.into_iter().filter(|a| a[0][0].contains(&3) && a[0][1].contains(&1)).collect();
+        assert_eq!(res, expected);
+        let double = make_assignments_with_hosts(&mut diagonal(6), 3, &[0, 0]).unwrap();
+        assert!(double.len() == 1 && double[0].is_empty());
+        assert_eq!(
+            make_assignments_with_hosts(&mut diagonal(6), 3, &[0, 1, 2]),
+            Err(GroupError::GroupIndexOutOfRange { group: 2, num_groups: 2 })
+        );
+    }
+
+    #[test]
+    fn hyperedges_forbid_whole_sets() {
+        let mut conflicts = diagonal(6);
+        conflicts.add_hyperedge(&[0, 1, 2]);
+        let res = make_assignments(&mut conflicts, 3);
+        assert_eq!(conflicts.hyperedges(), &[vec![0, 1, 2]]);
+        assert_eq!(res, make_assignments_forbidding(&mut diagonal(6), 3, &[vec![0, 1, 2]]).unwrap());
+        assert!(!res.is_empty());
+        assert_eq!(check_round(&conflicts, 3, &[vec![0, 1, 2], vec![3, 4, 5]]), Err(GroupError::InvalidRound));
+    }
+
+    #[test]
+    fn absent_vertices_sit_out_their_rounds() {
+        let available = vec![vec![true, true, false], vec![true, false, true], vec![true, true, true], vec![true, true, true], vec![false, true, true]];
+        let mut conflicts = diagonal(5);
+        let res = make_assignments_with_availability(&mut conflicts, 2, &available).unwrap();
+        assert_eq!(conflicts, diagonal(5));
+        assert!(!res.is_empty());
+        for assignment in &res {
+            for (r, round) in assignment.iter().enumerate() {
+                let mut members: Vec<usize> = round.iter().flatten().copied().collect();
+                members.sort_unstable();
+                let present: Vec<usize> = (0..5).filter(|v| available[*v][r]).collect();
+                assert_eq!(members, present);
+                assert!(round.iter().all(|g| g.len() >= 2));
+            }
+            let counts = meeting_counts(assignment, 5);
+            assert!((0..5).all(|u| (0..5).all(|v| u == v || counts[u][v] <= 1)));
+        }
+        assert_eq!(make_assignments_with_availability(&mut diagonal(3), 2, &[vec![true]]), Err(GroupError::AttributeMismatch { len: 1, n: 3 }));
+    }
+
+    #[test]
+    fn sitting_out_unlocks_rounds() {
+        let mut conflicts = diagonal(5);
+        let res = make_assignments_with_sit_outs(&mut conflicts, 2, 1, 1);
+        assert_eq!(conflicts, diagonal(5));
+        assert!(!res.is_empty());
+        for assignment in &res {
+            assert_eq!(assignment.len(), 5);
+            let mut sat_out = [0; 5];
+            for round in assignment {
+                assert!(round.iter().all(|g| g.len() >= 2));
+                let members: Vec<usize> = round.iter().flatten().copied().collect();
+                assert!(members.len() >= 4);
+                (0..5).filter(|v| !members.contains(v)).for_each(|v| sat_out[v] += 1);
+            }
+            assert!(sat_out.iter().all(|s| *s <= 1));
+            let counts = meeting_counts(assignment, 5);
+            assert!((0..5).all(|u| (0..5).all(|v| u == v || counts[u][v] == 1)));
+        }
+        assert_eq!(make_assignments_with_sit_outs(&mut diagonal(5), 2, 1, 0), make_assignments(&mut diagonal(5), 2));
+    }
+
+    #[test]
+    fn stations_are_visited_once() {
+        let mut conflicts = diagonal(6);
+        let res = make_assignments_with_station_rotation(&mut conflicts, 2);
+        assert_eq!(conflicts, diagonal(6));
+        assert!(!res.is_empty());
+        for assignment in &res {
+            assert_eq!(assignment.len(), 3);
+            assert!(validate_assignment(assignment, 6, 2));
+            for v in 0..6 {
+                let mut stations: Vec<usize> = assignment.iter().map(|round| round.iter().position(|g| g.contains(&v)).unwrap()).collect();
+                stations.sort_unstable();
+                assert_eq!(stations, vec![0, 1, 2]);
+            }
+        }
+    }
+
+    #[test]
+    fn overlap_limit_mixes_consecutive_rounds() {
+        let mut conflicts = diagonal(4);
+        let res = make_assignments_with_overlap_limit(&mut conflicts, 2, 2, 1);
+        assert_eq!(conflicts, diagonal(4));
+        assert!(!res.is_empty());
+        for assignment in &res {
+            assert_eq!(assignment.len(), 6);
+            for pair in assignment.windows(2) {
+                for g in &pair[1] {
+                    assert!(pair[0].iter().all(|prev| g.iter().filter(|v| prev.contains(v)).count() <= 1));
+                }
+            }
+            let counts = meeting_counts(assignment, 4);
+            assert!((0..4).all(|u| (0..4).all(|v| u == v || counts[u][v] <= 2)));
+        }
+        let unlimited = make_assignments_with_overlap_limit(&mut diagonal(5), 2, 1, 1);
+        assert_eq!(unlimited, make_assignments_with_repeats(&mut diagonal(5), 2, 1));
+        assert!(make_assignments_with_overlap_limit(&mut diagonal(4), 2, 2, 0).iter().all(|a| a.len() == 1));
+    }
+
+    #[test]
+    fn priority_vertices_rest_last() {
+        let priorities = [0, 0, 0, 0, 9];
+        let mut conflicts = diagonal(5);
+        let res = make_assignments_with_priorities(&mut conflicts, 2, &priorities, 1, 1).unwrap();
+        assert_eq!(conflicts, diagonal(5));
+        assert!(!res.is_empty());
+        assert!(res[0][0][0].contains(&4));
+        for assignment in &res {
+            assert_eq!(assignment.len(), 5);
+            assert!(assignment[..4].iter().all(|round| round.iter().flatten().any(|v| *v == 4)));
+            assert!(assignment.iter().flatten().all(|g| g.is_sorted()));
+        }
+        let plain = make_assignments_with_priorities(&mut diagonal(4), 2, &[0; 4], 1, 1).unwrap();
+        assert_eq!(plain, make_assignments_with_sit_outs(&mut diagonal(4), 2, 1, 1));
+        assert_eq!(make_assignments_with_priorities(&mut diagonal(3), 2, &[1], 1, 1), Err(GroupError::AttributeMismatch { len: 1, n: 3 }));
+    }
+
+    #[test]
+    fn continuity_rejects_reshuffled_rounds() {
+        let plain = make_assignments_with_repeats(&mut diagonal(4), 2, 2);
+        assert!(plain.iter().any(|a| !satisfies_continuity(a)));
+        let continuing = make_assignments_with_repeats_continuing(&mut diagonal(4), 2, 2);
+        assert!(!continuing.is_empty());
+        assert!(continuing.iter().all(|a| satisfies_continuity(a)));
+        // In pairs, continuity means keeping the same partner
+        for a in &continuing {
+            assert_eq!(a.len(), 2);
+            assert_eq!(canonical_round(&a[0]), canonical_round(&a[1]));
+        }
+        let plain = make_assignments_with_cooldown(&mut diagonal(4), 2, 0, 3);
+        let continuing = make_assignments_with_cooldown_continuing(&mut diagonal(4), 2, 0, 3);
+        assert!(!continuing.is_empty() && continuing.len() < plain.len());
+        assert!(continuing.iter().all(|a| a.len() == 3 && satisfies_continuity(a)));
+        let cooling = make_assignments_with_cooldown_continuing(&mut diagonal(4), 2, 1, 3);
+        assert!(cooling.iter().all(|a| a.len() == 1));
+    }
+
+    #[test]
+    fn cooldown_lets_pairs_meet_again() {
+        assert_eq!(make_assignments_with_cooldown(&mut diagonal(4), 2, 3, 5), make_assignments(&mut diagonal(4), 2));
+        let mut conflicts = diagonal(4);
+        let res = make_assignments_with_cooldown(&mut conflicts, 2, 1, 5);
+        assert_eq!(conflicts, diagonal(4));
+        assert!(!res.is_empty());
+        for assignment in &res {
+            assert_eq!(assignment.len(), 5);
+            assert!(validate_assignment(&assignment[..1], 4, 2));
+            for pair in assignment.windows(2) {
+                assert!(validate_assignment(pair, 4, 2));
+            }
+        }
+        let cycling = make_assignments_with_cooldown(&mut diagonal(4), 2, 2, 6);
+        for assignment in &cycling {
+            let assignment: Vec<_> = assignment.iter().map(|round| canonical_round(round)).collect();
+            assert_eq!(assignment.len(), 6);
+            assert_eq!(assignment[..3], assignment[3..]);
+        }
+    }
+
+    #[test]
+    fn partner_cap_sits_out_exhausted_vertices() {
+        let n = 7;
+        let res = make_assignments_partner_cap(&mut diagonal(n), 2, 2);
+        assert!(!res.is_empty());
+        for assignment in &res {
+            assert_eq!(assignment.len(), 2);
+            // The first round's trio exhausts its budget and sits out
+            let trio = &assignment[0][0];
+            assert_eq!(trio.len(), 3);
+            let second: Vec<usize> = assignment[1].iter().flatten().copied().collect();
+            assert_eq!(second.len(), 4);
+            assert!(trio.iter().all(|v| !second.contains(v)));
+            assert!(distinct_partners(assignment, n).iter().all(|p| *p <= 2));
+        }
+        let uncapped = make_assignments_partner_cap(&mut diagonal(4), 2, 3);
+        assert_eq!(uncapped, make_assignments(&mut diagonal(4), 2));
+        assert!(make_assignments_partner_cap(&mut diagonal(4), 2, 1).iter().all(|a| a.len() == 1));
+    }
+
+    #[test]
+    fn partner_budgets_differ_per_vertex() {
+        let budgets = [1, 1, 4, 4, 4];
+        let mut conflicts = diagonal(5);
+        let res = make_assignments_partner_budgets(&mut conflicts, 2, &budgets).unwrap();
+        assert_eq!(conflicts, diagonal(5));
+        assert!(!res.is_empty());
+        for assignment in &res {
+            let partners = distinct_partners(assignment, 5);
+            assert!((0..5).all(|v| partners[v] <= budgets[v]));
+        }
+        assert_eq!(make_assignments_partner_budgets(&mut diagonal(4), 2, &[2; 4]).unwrap(), make_assignments_partner_cap(&mut diagonal(4), 2, 2));
+        assert_eq!(make_assignments_partner_budgets(&mut diagonal(4), 2, &[2; 3]), Err(GroupError::AttributeMismatch { len: 3, n: 4 }));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn distinct_count_matches_deduplicated_output() {
+        let mut conflicted = diagonal(6);
+        conflicted.add_conflict(1, 2);
+        let tests = [(diagonal(4), 2), (diagonal(5), 2), (diagonal(6), 3), (diagonal(6), 4), (conflicted, 3)];
+        for (conflicts, k) in tests {
+            let distinct: HashSet<Vec<Vec<RawGroup>>> = make_assignments(&mut conflicts.clone(), k)
+                .iter()
+                .map(|a| canonical_assignment(a))
+                .collect();
+            assert_eq!(count_distinct_maximal(&mut conflicts.clone(), k), distinct.len());
+        }
+        assert_eq!(count_distinct_maximal(&mut diagonal(4), 2), 1);
+        assert_eq!(count_distinct_maximal(&mut diagonal(5), 2), 10);
+        assert_eq!(count_distinct_maximal(&mut diagonal(3), 1), 1);
+    }
+
+    #[test]
+    fn near_reference_schedules() {
+        let sols = make_assignments(&mut diagonal(6), 3);
+        let reference = &sols[3];
+        let identical = make_assignments_near(&mut diagonal(6), 3, reference, 0);
+        assert_eq!(identical.len(), 2);
+        for assignment in &identical {
+            assert_eq!(pair_distance(assignment, reference, 6), 0);
+            assert_eq!(canonical_assignment(assignment), canonical_assignment(reference));
+        }
+        // Any other split into trios swaps one member of each, changing eight pairs
+        assert_eq!(make_assignments_near(&mut diagonal(6), 3, reference, 8), sols);
+
+        // With a trio and a pair, splits sharing two pairs with the reference are at distance 4
+        let sols = make_assignments(&mut diagonal(5), 2);
+        let reference = vec![vec![vec![0, 1, 2], vec![3, 4]]];
+        let near = make_assignments_near(&mut diagonal(5), 2, &reference, 4);
+        let expected: Vec<_> = sols.iter().filter(|a| pair_distance(a, &reference, 5) <= 4).cloned().collect();
+        assert_eq!(near, expected);
+        assert!(near.len() > 1 && near.len() < sols.len());
+    }
+
+    #[test]
+    fn counts_match_enumeration() {
+        let mut conflicted = diagonal(5);
+        conflicted.add_conflict(0, 3);
+        let mut isolated = diagonal(3);
+        isolated.add_conflict(0, 1);
+        isolated.add_conflict(0, 2);
+        for (conflicts, k) in [(diagonal(4), 2), (diagonal(5), 2), (diagonal(6), 3), (conflicted, 2), (isolated, 2), (diagonal(3), 1)] {
+            let mut conflicts = conflicts;
+            let eager = make_assignments(&mut conflicts.clone(), k);
+            assert_eq!(count_assignments(&mut conflicts, k), (eager[0].len(), eager.len() as u128));
+        }
+    }
+
+    #[test]
+    fn visitor_stops_early() {
+        let eager = make_assignments(&mut diagonal(5), 2);
+        let mut seen = vec![];
+        let stopped = make_assignments_with(&mut diagonal(5), 2, |assignment| {
+            seen.push(assignment.to_vec());
+            if seen.len() == 3 {
+                return ControlFlow::Break(seen.len());
+            }
+            ControlFlow::Continue(())
+        });
+        assert_eq!(stopped, Some(3));
+        assert_eq!(seen, eager[..3]);
+
+        let mut conflicts = diagonal(4);
+        let mut count = 0;
+        let finished = make_assignments_with::<()>(&mut conflicts, 2, |_| {
+            count += 1;
+            ControlFlow::Continue(())
+        });
+        assert_eq!((finished, count), (None, make_assignments(&mut diagonal(4), 2).len()));
+        assert_eq!(conflicts, diagonal(4));
+    }
+
+    #[test]
+    fn first_assignment_is_maximal() {
+        let mut conflicted = diagonal(5);
+        conflicted.add_conflict(0, 3);
+        for (conflicts, k) in [(diagonal(4), 2), (diagonal(5), 2), (diagonal(6), 3), (conflicted, 2)] {
+            let mut conflicts = conflicts;
+            let original = conflicts.clone();
+            let first = make_first_assignment(&mut conflicts, k);
+            assert_eq!(conflicts, original);
+            assert!(make_assignments(&mut conflicts, k).contains(&first));
+        }
+        // Stops at the bound rather than enumerating the whole K6 tree
+        let first = make_first_assignment(&mut diagonal(6), 2);
+        assert_eq!(first.len(), 5);
+        assert!(validate_assignment(&first, 6, 2));
+        assert_eq!(make_first_assignment(&mut diagonal(3), 1), vec![singletons(3)]);
+    }
+
+    #[test]
+    fn lex_min_matches_sorted_output() {
+        let structured = conflicts_from_groups(6, &[0, 0, 1, 1, 2, 2]);
+        let tests = [(diagonal(4), 2), (diagonal(5), 2), (diagonal(5), 3), (diagonal(6), 3), (structured, 3)];
+        for (conflicts, k) in tests {
+            let mut sorted: Vec<Vec<Vec<RawGroup>>> = make_assignments(&mut conflicts.clone(), k)
+                .iter()
+                .map(|a| canonical_assignment(a))
+                .collect();
+            sorted.sort_by_key(|a| a.concat());
+            let mut searched = conflicts.clone();
+            assert_eq!(make_assignments_lex_min(&mut searched, k), sorted[0]);
+            assert_eq!(searched, conflicts);
+        }
+        let expected = vec![
+            vec![vec![0, 1], vec![2, 3]],
+            vec![vec![0, 2], vec![1, 3]],
+            vec![vec![0, 3], vec![1, 2]],
+        ];
+        assert_eq!(make_assignments_lex_min(&mut diagonal(4), 2), expected);
+        assert!(make_assignments_lex_min(&mut conflicting_with_all(diagonal(4), 0), 2).is_empty());
+    }
+
+    #[test]
+    fn leftover_policies() {
+        let tests = [
+            (7, 2, vec![3, 2, 2], vec![2, 2, 3]),
+            (11, 3, vec![4, 4, 3], vec![3, 3, 5]),
+            (6, 3, vec![3, 3], vec![3, 3]),
+            (2, 3, vec![], vec![]),
+        ];
+        for (n, k, distributed, trailing) in tests {
+            assert_eq!(group_sizes_with_policy(n, k, LeftoverPolicy::default()), distributed);
+            assert_eq!(group_sizes_with_policy(n, k, LeftoverPolicy::TrailingRemainder), trailing);
+        }
+        let rounds = single_assignment(&mut diagonal(5), &[2, 3]);
+        assert!(!rounds.is_empty());
+        assert!(rounds.iter().all(|round| round[0].len() == 2 && round[1].len() == 3));
+    }
+
+    #[test]
+    fn pruning_stats() {
+        let structured = conflicts_from_groups(6, &[0, 0, 1, 1, 2, 2]);
+        let mut conflicted = diagonal(5);
+        conflicted.add_conflict(0, 3);
+        for (conflicts, k) in [(diagonal(4), 2), (diagonal(5), 2), (conflicted, 2), (structured, 3), (diagonal(6), 3)] {
+            let plain = make_assignments(&mut conflicts.clone(), k);
+            let (res, stats) = make_assignments_with_stats(&mut conflicts.clone(), k, SearchOptions::default());
+            assert_eq!(res, plain);
+            assert_eq!(stats.solutions_found, plain.len());
+            assert_eq!(stats.branches_pruned_by_bound + stats.branches_pruned_by_symmetry, 0);
+
+            let bounded = SearchOptions { bound_pruning: true, ..Default::default() };
+            let (res, bound_stats) = make_assignments_with_stats(&mut conflicts.clone(), k, bounded);
+            assert_eq!(res, plain);
+            assert!(bound_stats.nodes_visited <= stats.nodes_visited);
+
+            let symmetric = SearchOptions { symmetry_breaking: true, ..Default::default() };
+            let (res, sym_stats) = make_assignments_with_stats(&mut conflicts.clone(), k, symmetric);
+            let distinct: HashSet<Vec<Vec<RawGroup>>> = plain.iter().map(|a| canonical_assignment(a)).collect();
+            assert_eq!(res.len(), distinct.len());
+            assert!(res.iter().all(|a| distinct.contains(a)));
+            assert!(sym_stats.nodes_visited <= stats.nodes_visited);
+        }
+        let symmetric = SearchOptions { symmetry_breaking: true, ..Default::default() };
+        let (_, stats) = make_assignments_with_stats(&mut diagonal(4), 2, symmetric);
+        assert!(stats.branches_pruned_by_symmetry > 0);
+    }
+
+    #[test]
+    fn relaxing_one_conflict_unlocks_round() {
+        let mut conflicts = diagonal(4);
+        for (u, v) in [(0, 1), (2, 3), (0, 2)] {
+            conflicts.add_conflict(u, v);
+        }
+        let original = conflicts.clone();
+        assert_eq!(max_rounds(&mut conflicts, 2), 1);
+        let relaxed = conflict_relaxation(&mut conflicts, 2);
+        assert_eq!(relaxed, vec![((0, 2), 2), ((0, 1), 1), ((2, 3), 1)]);
+        assert_eq!(conflicts, original);
+    }
+
+    fn conflicting_with_all(mut conflicts: ConflictMatrix, v: usize) -> ConflictMatrix {
+        for u in 0..conflicts.len() {
+            conflicts.add_conflict(u, v);
+        }
+        conflicts
+    }
+
     fn test_all_assignment(n: usize, k: usize, exp_rounds: usize, exp_sizes: &[usize]) {
         let mut conflicts = diagonal(n);
         let res = make_assignments(&mut conflicts, k);
         let nrounds = res[0].len();
         assert_eq!(nrounds, exp_rounds, "Expected {exp_rounds} rounds but found {nrounds}");
         for possibility in res {
-            for i in 0..exp_rounds {
-                let group_sizes = possibility[i].iter().map(|v| v.len());
+            assert_eq!(possibility.len(), exp_rounds);
+            for round in &possibility {
+                let group_sizes = round.iter().map(|v| v.len());
                 assert!(group_sizes.eq(exp_sizes.iter().copied()));
             }
         }
@@ -0,0 +1,164 @@
+//! # Objectives
+//!
+//! Ranking the maximal assignments by a caller-defined score, so the best
+//! ones come first instead of every caller re-scanning the whole solution
+//! set for them.
+use alloc::collections::BinaryHeap;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+use core::ops::ControlFlow;
+
+use crate::{make_assignments, make_assignments_with, Assignment, ConflictMatrix};
+
+/// A measure of how good an assignment is, where higher scores are better.
+///
+/// Any `Fn(&Assignment) -> f64` closure is an objective:
+///
+/// ```
+/// # use group_generator::{make_assignments_ranked, Assignment, ConflictMatrix};
+/// // Prefer schedules where 0 and 1 meet in the first round
+/// let early = |a: &Assignment| if a[0].group_of(0).unwrap().contains(1) { 1.0 } else { 0.0 };
+/// let ranked = make_assignments_ranked(&mut ConflictMatrix::empty(4), 2, &early);
+/// assert!(ranked[0][0].group_of(0).unwrap().contains(1));
+/// ```
+pub trait Score {
+    /// The score of `assignment`.
+    fn score(&self, assignment: &Assignment) -> f64;
+}
+
+impl<F: Fn(&Assignment) -> f64> Score for F {
+    fn score(&self, assignment: &Assignment) -> f64 {
+        self(assignment)
+    }
+}
+
+/// Generate all *maximum-round* assignments (as in [`make_assignments()`]),
+/// sorted best-first by `objective`.
+///
+/// Each assignment is scored once. The sort is stable, so equally scored
+/// assignments keep their search order, and scores are compared with
+/// [`f64::total_cmp()`], so `NaN` ranks above everything.
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_ranked(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    objective: &(impl Score + ?Sized),
+) -> Vec<Assignment> {
+    let mut scored: Vec<(f64, Assignment)> = make_assignments(conflicts, min_group_size)
+        .into_iter()
+        .map(|a| {
+            let a = Assignment::from(a);
+            (objective.score(&a), a)
+        })
+        .collect();
+    scored.sort_by(|(x, _), (y, _)| y.total_cmp(x));
+    scored.into_iter().map(|(_, a)| a).collect()
+}
+
+/// Generate only the `k` best *maximum-round* assignments by `objective`,
+/// best-first: the first `k` of [`make_assignments_ranked()`].
+///
+/// Assignments are scored as the search finds them and only the best `k` so
+/// far are kept, in a heap, so the full solution set is never collected.
+///
+/// ```
+/// # use group_generator::{make_assignments_top_k, Assignment, ConflictMatrix};
+/// let spread = |a: &Assignment| (0..5).map(|v| a.partner_count(v)).min().unwrap_or(0) as f64;
+/// let best = make_assignments_top_k(&mut ConflictMatrix::empty(5), 2, 3, &spread);
+/// assert_eq!(best.len(), 3);
+/// ```
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_top_k(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    k: usize,
+    objective: &(impl Score + ?Sized),
+) -> Vec<Assignment> {
+    let mut heap: BinaryHeap<Ranked> = BinaryHeap::new();
+    let mut found = 0;
+    make_assignments_with::<()>(conflicts, min_group_size, |raw| {
+        let assignment = Assignment::from(raw.to_vec());
+        let ranked = Ranked { score: objective.score(&assignment), index: found, assignment };
+        found += 1;
+        if heap.len() < k {
+            heap.push(ranked);
+        } else if heap.peek().is_some_and(|worst| ranked < *worst) {
+            heap.pop();
+            heap.push(ranked);
+        }
+        ControlFlow::Continue(())
+    });
+    heap.into_sorted_vec().into_iter().map(|ranked| ranked.assignment).collect()
+}
+
+/// An assignment kept by [`make_assignments_top_k()`], with `index` its
+/// position in search order.
+///
+/// Better assignments order first: higher scores, then earlier ones, as in
+/// the stable sort of [`make_assignments_ranked()`]. The top of the heap is
+/// therefore the worst assignment kept.
+struct Ranked {
+    score: f64,
+    index: usize,
+    assignment: Assignment,
+}
+
+impl Ord for Ranked {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.score.total_cmp(&self.score).then(self.index.cmp(&other.index))
+    }
+}
+
+impl PartialOrd for Ranked {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Ranked {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Ranked {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::diagonal;
+    use core::cmp::Reverse;
+
+    /// Rounds in which `0` and `1` share a group.
+    struct Together;
+
+    impl Score for Together {
+        fn score(&self, assignment: &Assignment) -> f64 {
+            assignment.rounds().iter().filter(|r| r.group_of(0).is_some_and(|g| g.contains(1))).count() as f64
+        }
+    }
+
+    #[test]
+    fn ranks_best_first_and_stable() {
+        let mut expected: Vec<Assignment> = make_assignments(&mut diagonal(5), 2).into_iter().map(Assignment::from).collect();
+        expected.sort_by_key(|a| Reverse(Together.score(a) as usize));
+        let ranked = make_assignments_ranked(&mut diagonal(5), 2, &Together);
+        assert_eq!(ranked, expected);
+        assert!(Together.score(&ranked[0]) > Together.score(ranked.last().unwrap()));
+    }
+
+    #[test]
+    fn top_k_is_prefix_of_ranking() {
+        let ranked = make_assignments_ranked(&mut diagonal(5), 2, &Together);
+        for k in [0, 1, 3, ranked.len(), ranked.len() + 2, usize::MAX] {
+            let top = make_assignments_top_k(&mut diagonal(5), 2, k, &Together);
+            assert_eq!(top, ranked[..k.min(ranked.len())]);
+        }
+    }
+}
@@ -0,0 +1,152 @@
+//! # Parallel Search
+//!
+//! Multi-threaded version of the exhaustive search, splitting the tree by its
+//! first round.
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+
+use crate::{
+    can_form_any_round, check_problem, commit_round, explore_rounds, group_sizes, rollback_round, single_assignment,
+    singletons, ConflictMatrix, RawGroup,
+};
+
+/// The index of a first round, and the assignments found in its subtree.
+type Subtree = (usize, Vec<Vec<Vec<RawGroup>>>);
+
+/// Generate all *maximum-round* assignments (as in
+/// [`make_assignments()`](crate::make_assignments)) using up to `threads`
+/// worker threads.
+///
+/// # Work Splitting
+///
+/// Each possible first round roots an independent subtree. Workers claim
+/// subtrees one at a time from a shared counter, so a worker finishing a small
+/// subtree immediately moves on to the next, and explore them on their own
+/// copy of the conflict matrix.
+///
+/// # Shared Best
+///
+/// The best depth found by any worker is kept in an [`AtomicUsize`] and only
+/// ever raised (with an atomic maximum, so concurrent improvements cannot
+/// overwrite each other with a smaller value). Workers read it to avoid
+/// keeping leaves which are already known to be too shallow, but never block
+/// on each other: every worker collects its results in its own shard, and the
+/// shards are only combined once all workers have finished.
+///
+/// A shard may still hold assignments from before a deeper one was found
+/// elsewhere, so the merge keeps only those reaching the final best depth.
+/// Shards are merged in first-round order, so the result is identical to
+/// [`make_assignments()`](crate::make_assignments), including its order.
+///
+/// # Panics
+///
+/// Panics if `threads` is 0, or under the same conditions as
+/// [`make_assignments()`](crate::make_assignments).
+pub fn make_assignments_parallel(conflicts: &ConflictMatrix, min_group_size: usize, threads: usize) -> Vec<Vec<Vec<RawGroup>>> {
+    check_problem(conflicts, min_group_size);
+    assert!(threads > 0, "Need at least one thread to search.");
+
+    if min_group_size == 1 {
+        return vec![vec![singletons(conflicts.len())]];
+    }
+    if !can_form_any_round(conflicts, min_group_size) {
+        return vec![vec![]];
+    }
+    let group_sizes = group_sizes(conflicts.len(), min_group_size);
+    let first_rounds = single_assignment(&mut conflicts.clone(), &group_sizes);
+    if first_rounds.is_empty() {
+        return vec![vec![]];
+    }
+
+    let best = AtomicUsize::new(0);
+    let next = AtomicUsize::new(0);
+    // One shard per worker, each holding the subtrees it explored
+    let shards: Mutex<Vec<Vec<Subtree>>> = Mutex::new(vec![]);
+    thread::scope(|scope| {
+        for _ in 0..threads.min(first_rounds.len()) {
+            scope.spawn(|| {
+                let mut shard = vec![];
+                let mut conflicts = conflicts.clone();
+                loop {
+                    let i = next.fetch_add(1, Ordering::Relaxed);
+                    let Some(first) = first_rounds.get(i) else {
+                        break;
+                    };
+                    let mut sols = vec![];
+                    commit_round(&mut conflicts, first);
+                    explore_rounds(
+                        &mut conflicts,
+                        |conflicts, _| single_assignment(conflicts, &group_sizes),
+                        commit_round,
+                        rollback_round,
+                        |rest| {
+                            let depth = rest.len() + 1;
+                            if depth >= best.fetch_max(depth, Ordering::Relaxed) {
+                                let mut assignment = Vec::with_capacity(depth);
+                                assignment.push(first.clone());
+                                assignment.extend_from_slice(rest);
+                                sols.push(assignment);
+                            }
+                            ControlFlow::Continue(())
+                        },
+                    );
+                    rollback_round(&mut conflicts, first);
+                    shard.push((i, sols));
+                }
+                shards.lock().expect("A worker panicked").push(shard);
+            });
+        }
+    });
+
+    let best = best.into_inner();
+    let mut subtrees: Vec<Subtree> = shards.into_inner().expect("A worker panicked").into_iter().flatten().collect();
+    subtrees.sort_unstable_by_key(|(i, _)| *i);
+    subtrees
+        .into_iter()
+        .flat_map(|(_, sols)| sols)
+        .filter(|assignment| assignment.len() == best)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::make_assignments;
+    use crate::tests::diagonal;
+
+    #[test]
+    fn matches_sequential_search() {
+        let mut conflicted = diagonal(5);
+        conflicted.add_conflict(0, 3);
+        for (conflicts, k) in [(diagonal(4), 2), (diagonal(5), 2), (diagonal(6), 3), (conflicted, 2)] {
+            let expected = make_assignments(&mut conflicts.clone(), k);
+            for threads in [1, 2, 8] {
+                assert_eq!(make_assignments_parallel(&conflicts, k, threads), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn stale_shallow_solutions_discarded() {
+        // Some first rounds only allow three rounds in total, others four
+        let mut conflicts = diagonal(6);
+        for (u, v) in [(0, 1), (2, 3)] {
+            conflicts.add_conflict(u, v);
+        }
+        let res = make_assignments_parallel(&conflicts, 2, 4);
+        assert!(res.iter().all(|a| a.len() == 4));
+        assert_eq!(res, make_assignments(&mut conflicts, 2));
+    }
+
+    #[test]
+    fn trivial_inputs() {
+        assert_eq!(make_assignments_parallel(&diagonal(3), 1, 2), vec![vec![vec![vec![0], vec![1], vec![2]]]]);
+        let mut isolated = diagonal(4);
+        for v in 1..4 {
+            isolated.add_conflict(0, v);
+        }
+        assert_eq!(make_assignments_parallel(&isolated, 2, 2), vec![Vec::<Vec<RawGroup>>::new()]);
+    }
+}
@@ -0,0 +1,128 @@
+//! # Online Planning
+//!
+//! An interactive planner for live events, where rounds are decided one at a
+//! time and new conflicts may be discovered between rounds.
+use alloc::vec;
+use alloc::vec::Vec;
+use alloc::collections::BTreeSet;
+
+use crate::analysis::canonical_round;
+use crate::{check_problem, commit_round, group_sizes, rounds_remaining_after, single_assignment, ConflictMatrix, RawGroup};
+
+/// Plans a schedule one round at a time, adapting to conflicts discovered
+/// along the way.
+///
+/// Each call to [`next_round()`](OnlinePlanner::next_round) commits the
+/// available round which preserves the most future rounds (as scored by
+/// [`rounds_remaining_after()`]), so the schedule stays as flexible as
+/// possible in case further conflicts are added.
+#[derive(Debug, Clone)]
+pub struct OnlinePlanner {
+    conflicts: ConflictMatrix,
+    min_group_size: usize,
+    rounds: Vec<Vec<RawGroup>>,
+}
+
+impl OnlinePlanner {
+    /// Create a planner with no committed rounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as
+    /// [`make_assignments()`](crate::make_assignments).
+    pub fn new(conflicts: ConflictMatrix, min_group_size: usize) -> Self {
+        check_problem(&conflicts, min_group_size);
+        Self {
+            conflicts,
+            min_group_size,
+            rounds: vec![],
+        }
+    }
+
+    /// Compute, commit, and return the next round, or `None` if no further
+    /// round can be formed.
+    ///
+    /// Ties between equally flexible rounds go to the first found. With a
+    /// minimum group size of 1 the schedule is the single round of
+    /// singletons, as in [`make_assignments()`](crate::make_assignments).
+    pub fn next_round(&mut self) -> Option<Vec<RawGroup>> {
+        if self.min_group_size == 1 && !self.rounds.is_empty() {
+            return None;
+        }
+        let group_sizes = group_sizes(self.conflicts.len(), self.min_group_size);
+        let mut seen = BTreeSet::new();
+        let mut best: Option<(usize, Vec<RawGroup>)> = None;
+        for candidate in single_assignment(&mut self.conflicts, &group_sizes) {
+            if !seen.insert(canonical_round(&candidate)) {
+                continue;
+            }
+            let remaining = rounds_remaining_after(&mut self.conflicts, self.min_group_size, &candidate);
+            if best.as_ref().is_none_or(|(most, _)| remaining > *most) {
+                best = Some((remaining, candidate));
+            }
+        }
+        let (_, round) = best?;
+        commit_round(&mut self.conflicts, &round);
+        self.rounds.push(round.clone());
+        Some(round)
+    }
+
+    /// Record a new conflict between vertices `i` and `j`, which will be
+    /// respected by all future rounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics if either vertex is out of range.
+    pub fn add_conflict(&mut self, i: usize, j: usize) {
+        self.conflicts.add_conflict(i, j);
+    }
+
+    /// The rounds committed so far.
+    pub fn rounds(&self) -> &[Vec<RawGroup>] {
+        &self.rounds
+    }
+
+    /// The current conflict state, including committed rounds.
+    pub fn conflicts(&self) -> &ConflictMatrix {
+        &self.conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::diagonal;
+
+    #[test]
+    fn plans_around_new_conflicts() {
+        let mut planner = OnlinePlanner::new(diagonal(4), 2);
+        assert_eq!(planner.next_round(), Some(vec![vec![0, 1], vec![2, 3]]));
+        planner.add_conflict(0, 2);
+        assert_eq!(planner.next_round(), Some(vec![vec![0, 3], vec![1, 2]]));
+        assert_eq!(planner.next_round(), None);
+        assert_eq!(planner.rounds().len(), 2);
+        assert!(planner.conflicts().is_conflicted(1, 2));
+    }
+
+    #[test]
+    fn prefers_flexible_rounds() {
+        let mut conflicts = diagonal(6);
+        for (u, v) in [(0, 1), (2, 3)] {
+            conflicts.add_conflict(u, v);
+        }
+        let mut planner = OnlinePlanner::new(conflicts, 2);
+        let mut count = 0;
+        while planner.next_round().is_some() {
+            count += 1;
+        }
+        assert_eq!(count, 4);
+    }
+
+    #[test]
+    fn singletons_are_one_round() {
+        let mut planner = OnlinePlanner::new(diagonal(3), 1);
+        assert_eq!(planner.next_round(), Some(vec![vec![0], vec![1], vec![2]]));
+        assert_eq!(planner.next_round(), None);
+        assert_eq!(planner.rounds().len(), 1);
+    }
+}
@@ -0,0 +1,168 @@
+//! # Problem Builder
+//!
+//! A validated way to set up a scheduling problem without constructing the
+//! conflict matrix (and its diagonal convention) by hand.
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{
+    can_reach_rounds, make_assignments, make_assignments_exact_rounds, make_assignments_ranked, make_assignments_top_k, validate_problem,
+    Assignment, ConflictMatrix, GroupError, RawGroup, Score,
+};
+
+/// Builder for a scheduling problem, producing an [`AssignmentSolver`].
+///
+/// ```
+/// # use group_generator::AssignmentProblem;
+/// let solver = AssignmentProblem::new(6).min_group_size(3).conflict(2, 5).build().unwrap();
+/// for assignment in solver.solve() {
+///     assert!(assignment.iter().flatten().all(|g| !(g.contains(&2) && g.contains(&5))));
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssignmentProblem {
+    n: usize,
+    min_group_size: usize,
+    conflicts: Vec<(usize, usize)>,
+}
+
+impl AssignmentProblem {
+    /// Start describing a problem on `n` vertices with no conflicts and a
+    /// minimum group size of 2.
+    pub fn new(n: usize) -> Self {
+        Self {
+            n,
+            min_group_size: 2,
+            conflicts: vec![],
+        }
+    }
+
+    /// Set the minimum group size.
+    pub fn min_group_size(mut self, min_group_size: usize) -> Self {
+        self.min_group_size = min_group_size;
+        self
+    }
+
+    /// Forbid vertices `i` and `j` from ever sharing a group.
+    pub fn conflict(mut self, i: usize, j: usize) -> Self {
+        self.conflicts.push((i, j));
+        self
+    }
+
+    /// Forbid every pair in `pairs` from ever sharing a group.
+    pub fn conflicts(mut self, pairs: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        self.conflicts.extend(pairs);
+        self
+    }
+
+    /// Validate the problem and build its conflict matrix.
+    ///
+    /// # Errors
+    ///
+    /// - [`GroupError::NoVertices`] if there are no vertices.
+    /// - [`GroupError::InvalidMinGroupSize`] if the minimum group size is 0 or
+    ///   larger than the number of vertices.
+    /// - [`GroupError::VertexOutOfRange`] if a conflict refers to a vertex
+    ///   which does not exist.
+    pub fn build(self) -> Result<AssignmentSolver, GroupError> {
+        let conflicts = ConflictMatrix::from_edges(self.n, self.conflicts)?;
+        validate_problem(&conflicts, self.min_group_size)?;
+        Ok(AssignmentSolver {
+            conflicts,
+            min_group_size: self.min_group_size,
+        })
+    }
+}
+
+/// A validated scheduling problem, ready to solve. Built by
+/// [`AssignmentProblem::build()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AssignmentSolver {
+    conflicts: ConflictMatrix,
+    min_group_size: usize,
+}
+
+impl AssignmentSolver {
+    /// Number of vertices.
+    pub fn n(&self) -> usize {
+        self.conflicts.len()
+    }
+
+    /// Minimum group size.
+    pub fn min_group_size(&self) -> usize {
+        self.min_group_size
+    }
+
+    /// The conflict matrix, with the diagonal set.
+    pub fn conflicts(&self) -> &ConflictMatrix {
+        &self.conflicts
+    }
+
+    /// Generate all *maximum-round* assignments (see [`make_assignments()`]).
+    pub fn solve(&self) -> Vec<Vec<Vec<RawGroup>>> {
+        make_assignments(&mut self.conflicts.clone(), self.min_group_size)
+    }
+
+    /// Generate all *maximum-round* assignments, sorted best-first by
+    /// `objective` (see [`make_assignments_ranked()`]).
+    pub fn solve_ranked(&self, objective: &(impl Score + ?Sized)) -> Vec<Assignment> {
+        make_assignments_ranked(&mut self.conflicts.clone(), self.min_group_size, objective)
+    }
+
+    /// Generate only the `k` best *maximum-round* assignments by `objective`
+    /// (see [`make_assignments_top_k()`]).
+    pub fn solve_top_k(&self, k: usize, objective: &(impl Score + ?Sized)) -> Vec<Assignment> {
+        make_assignments_top_k(&mut self.conflicts.clone(), self.min_group_size, k, objective)
+    }
+
+    /// Generate all assignments with exactly `rounds` rounds (see
+    /// [`make_assignments_exact_rounds()`]).
+    ///
+    /// # Errors
+    ///
+    /// [`GroupError::UnreachableRounds`] if no assignment has that many.
+    pub fn solve_exact_rounds(&self, rounds: usize) -> Result<Vec<Vec<Vec<RawGroup>>>, GroupError> {
+        make_assignments_exact_rounds(&mut self.conflicts.clone(), self.min_group_size, rounds)
+    }
+
+    /// Whether any assignment has at least `rounds` rounds (see
+    /// [`can_reach_rounds()`]).
+    pub fn can_reach_rounds(&self, rounds: usize) -> bool {
+        can_reach_rounds(&mut self.conflicts.clone(), self.min_group_size, rounds)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::diagonal;
+
+    #[test]
+    fn builder_matches_manual_matrix() {
+        let solver = AssignmentProblem::new(5).conflicts([(0, 3), (1, 2)]).build().unwrap();
+        let mut manual = diagonal(5);
+        for (i, j) in [(0, 3), (1, 2)] {
+            manual.add_conflict(i, j);
+        }
+        assert_eq!(solver.conflicts(), &manual);
+        assert_eq!((solver.n(), solver.min_group_size()), (5, 2));
+        assert_eq!(solver.solve(), make_assignments(&mut manual, 2));
+        assert_eq!(solver.solve_exact_rounds(1), make_assignments_exact_rounds(&mut manual, 2, 1));
+        let first = |a: &Assignment| a[0][0][0] as f64;
+        assert_eq!(solver.solve_ranked(&first), make_assignments_ranked(&mut manual.clone(), 2, &first));
+        assert_eq!(solver.solve_top_k(2, &first), solver.solve_ranked(&first)[..2]);
+        let best = solver.solve()[0].len();
+        assert!(solver.can_reach_rounds(best) && !solver.can_reach_rounds(best + 1));
+    }
+
+    #[test]
+    fn invalid_problems_are_rejected() {
+        assert_eq!(AssignmentProblem::new(0).build(), Err(GroupError::NoVertices));
+        let err = AssignmentProblem::new(3).min_group_size(4).build();
+        assert_eq!(err, Err(GroupError::InvalidMinGroupSize { min_group_size: 4, n: 3 }));
+        let err = AssignmentProblem::new(3).min_group_size(0).build();
+        assert_eq!(err, Err(GroupError::InvalidMinGroupSize { min_group_size: 0, n: 3 }));
+        let err = AssignmentProblem::new(3).conflict(1, 3).build();
+        assert_eq!(err, Err(GroupError::VertexOutOfRange { vertex: 3, n: 3 }));
+    }
+}
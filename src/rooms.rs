@@ -0,0 +1,117 @@
+//! # Rooms
+//!
+//! Schedules where every group needs a room of its own, so the round shape
+//! follows from the rooms available and each group is told where to meet.
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{make_assignments_shaped, singletons, validate_problem, ConflictMatrix, GroupError, RawGroup};
+
+/// A group along with the index of the room it meets in.
+type RoomedGroup = (usize, RawGroup);
+
+/// Generate the *maximum-round* assignments (as in
+/// [`make_assignments()`](crate::make_assignments)) where each group in a
+/// round meets in a distinct room it fits, pairing every group with the index
+/// of its room in `capacities`.
+///
+/// The shape uses as many groups as the rooms allow, each between
+/// `min_group_size` and its room's capacity, with the largest rooms taken
+/// first and vertices spread over them as evenly as the capacities allow.
+/// Every round has the same shape, so each slot keeps its room.
+///
+/// ```
+/// # use group_generator::{make_assignments_with_rooms, ConflictMatrix};
+/// let sols = make_assignments_with_rooms(&mut ConflictMatrix::empty(6), 2, &[2, 4]).unwrap();
+/// for (room, group) in &sols[0][0] {
+///     assert!(group.len() <= [2, 4][*room]);
+/// }
+/// ```
+///
+/// # Errors
+///
+/// - [`GroupError::NoVertices`] if `conflicts` is empty.
+/// - [`GroupError::InvalidMinGroupSize`] if `min_group_size` is 0 or exceeds
+///   the number of vertices.
+/// - [`GroupError::InsufficientRooms`] if no choice of rooms fits everyone.
+pub fn make_assignments_with_rooms(
+    conflicts: &mut ConflictMatrix,
+    min_group_size: usize,
+    capacities: &[usize],
+) -> Result<Vec<Vec<Vec<RoomedGroup>>>, GroupError> {
+    validate_problem(conflicts, min_group_size)?;
+    let n = conflicts.len();
+    let (group_sizes, rooms) = fit_rooms(n, min_group_size, capacities)?;
+    let sols = if group_sizes.iter().all(|k| *k == 1) {
+        vec![vec![singletons(n)]]
+    } else {
+        make_assignments_shaped(conflicts, &group_sizes)?
+    };
+    let with_rooms = |round: Vec<RawGroup>| rooms.iter().copied().zip(round).collect();
+    Ok(sols.into_iter().map(|a| a.into_iter().map(with_rooms).collect()).collect())
+}
+
+/// The round shape with the most groups fitting the rooms in `capacities`,
+/// along with the room holding each group.
+fn fit_rooms(n: usize, min_group_size: usize, capacities: &[usize]) -> Result<(Vec<usize>, Vec<usize>), GroupError> {
+    let mut order: Vec<usize> = (0..capacities.len()).collect();
+    order.sort_by_key(|r| core::cmp::Reverse(capacities[*r]));
+    for num_groups in (1..=(n / min_group_size).min(order.len())).rev() {
+        let rooms = &order[..num_groups];
+        let caps: Vec<usize> = rooms.iter().map(|r| capacities[*r]).collect();
+        if caps[num_groups - 1] < min_group_size || caps.iter().sum::<usize>() < n {
+            continue;
+        }
+        // Raise every group a member at a time, so sizes stay as even as the
+        // capacities allow and the larger groups come first
+        let mut sizes = vec![min_group_size; num_groups];
+        let mut left = n - num_groups * min_group_size;
+        while left > 0 {
+            for (size, cap) in sizes.iter_mut().zip(&caps) {
+                if left > 0 && *size < *cap {
+                    *size += 1;
+                    left -= 1;
+                }
+            }
+        }
+        return Ok((sizes, rooms.to_vec()));
+    }
+    Err(GroupError::InsufficientRooms { n, min_group_size })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::diagonal;
+
+    #[test]
+    fn groups_fit_distinct_rooms() {
+        let capacities = [2, 4, 1];
+        let sols = make_assignments_with_rooms(&mut diagonal(6), 2, &capacities).unwrap();
+        let expected = make_assignments_shaped(&mut diagonal(6), &[4, 2]).unwrap();
+        assert_eq!(sols.len(), expected.len());
+        for (a, b) in sols.iter().zip(&expected) {
+            for (round, groups) in a.iter().zip(b) {
+                let rooms: Vec<usize> = round.iter().map(|(room, _)| *room).collect();
+                assert_eq!(rooms, vec![1, 0]);
+                assert!(round.iter().map(|(_, g)| g).eq(groups));
+            }
+        }
+    }
+
+    #[test]
+    fn shape_spreads_over_rooms() {
+        assert_eq!(fit_rooms(7, 2, &[5, 5, 5]), Ok((vec![3, 2, 2], vec![0, 1, 2])));
+        assert_eq!(fit_rooms(7, 2, &[2, 5]), Ok((vec![5, 2], vec![1, 0])));
+        assert_eq!(fit_rooms(4, 1, &[1, 1, 1, 1]), Ok((vec![1; 4], vec![0, 1, 2, 3])));
+        let sols = make_assignments_with_rooms(&mut diagonal(3), 1, &[1, 1, 1]).unwrap();
+        assert_eq!(sols, vec![vec![vec![(0, vec![0]), (1, vec![1]), (2, vec![2])]]]);
+    }
+
+    #[test]
+    fn rejects_too_few_rooms() {
+        let err = GroupError::InsufficientRooms { n: 6, min_group_size: 2 };
+        assert_eq!(make_assignments_with_rooms(&mut diagonal(6), 2, &[2, 3]), Err(err.clone()));
+        assert_eq!(make_assignments_with_rooms(&mut diagonal(6), 2, &[]), Err(err));
+    }
+}
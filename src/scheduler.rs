@@ -0,0 +1,192 @@
+//! # Interactive Scheduling
+//!
+//! A session for building a schedule by hand, one round at a time, where the
+//! caller (e.g. an organizer in a UI) chooses each round from the options the
+//! conflicts still allow.
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::{
+    canonical_rounds, check_problem, check_round, commit_round, group_sizes, max_rounds_upper_bound, rollback_round, ConflictMatrix,
+    GroupError, RawGroup,
+};
+
+/// Scheduling session which lists the valid next rounds and lets the caller
+/// commit the one they want.
+///
+/// ```
+/// # use group_generator::{ConflictMatrix, Scheduler};
+/// let mut scheduler = Scheduler::new(ConflictMatrix::empty(4), 2);
+/// let options = scheduler.round_options();
+/// assert_eq!(options.len(), 3);
+/// scheduler.commit(options[2].clone()).unwrap();
+/// assert_eq!(scheduler.round_options().len(), 2);
+/// assert_eq!(scheduler.remaining_bound(), 2);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Scheduler {
+    conflicts: ConflictMatrix,
+    min_group_size: usize,
+    rounds: Vec<Vec<RawGroup>>,
+}
+
+impl Scheduler {
+    /// Start a session with no committed rounds.
+    ///
+    /// # Panics
+    ///
+    /// Panics under the same conditions as
+    /// [`make_assignments()`](crate::make_assignments).
+    pub fn new(conflicts: ConflictMatrix, min_group_size: usize) -> Self {
+        check_problem(&conflicts, min_group_size);
+        Self {
+            conflicts,
+            min_group_size,
+            rounds: vec![],
+        }
+    }
+
+    /// Every round which may be committed next, with groups shaped by
+    /// [`group_sizes()`](crate::group_sizes). Each partition is listed once,
+    /// in canonical form (see [`canonical_round()`](crate::canonical_round)),
+    /// in ascending order.
+    ///
+    /// With a minimum group size of 1 the only option is the round of
+    /// singletons, and only before any round is committed (see
+    /// [`make_assignments()`](crate::make_assignments)).
+    pub fn round_options(&self) -> Vec<Vec<RawGroup>> {
+        if self.min_group_size == 1 && !self.rounds.is_empty() {
+            return vec![];
+        }
+        let group_sizes = group_sizes(self.conflicts.len(), self.min_group_size);
+        canonical_rounds(&self.conflicts, &group_sizes)
+    }
+
+    /// Commit `round` as the next round, so none of the pairs it groups
+    /// together may meet again.
+    ///
+    /// Any round of groups of at least the minimum size is accepted, not only
+    /// those listed by [`round_options()`](Scheduler::round_options).
+    ///
+    /// # Errors
+    ///
+    /// [`GroupError::InvalidRound`] if `round` does not place every vertex
+    /// exactly once, has a group below the minimum size, or groups together a
+    /// conflicting pair, or if the minimum group size is 1 and the round of
+    /// singletons (the whole schedule) is already committed. The session is
+    /// left unchanged.
+    pub fn commit(&mut self, round: Vec<RawGroup>) -> Result<(), GroupError> {
+        if self.min_group_size == 1 && !self.rounds.is_empty() {
+            return Err(GroupError::InvalidRound);
+        }
+        check_round(&self.conflicts, self.min_group_size, &round)?;
+        commit_round(&mut self.conflicts, &round);
+        self.rounds.push(round);
+        Ok(())
+    }
+
+    /// Revert the most recently committed round, restoring the conflicts to
+    /// their state before it, and return it. Returns `None` if no round has
+    /// been committed.
+    ///
+    /// Since [`commit()`](Scheduler::commit) only accepts conflict-free
+    /// rounds, every pair the round grouped together was free beforehand, so
+    /// the restore is exact.
+    pub fn undo(&mut self) -> Option<Vec<RawGroup>> {
+        let round = self.rounds.pop()?;
+        rollback_round(&mut self.conflicts, &round);
+        Some(round)
+    }
+
+    /// Revert every committed round, returning the session to its starting
+    /// state.
+    pub fn reset(&mut self) {
+        while self.undo().is_some() {}
+    }
+
+    /// Upper bound on how many more rounds can be committed, from
+    /// [`max_rounds_upper_bound()`](crate::max_rounds_upper_bound) on the
+    /// current conflicts. Cheap, but not necessarily achievable.
+    pub fn remaining_bound(&self) -> usize {
+        if self.min_group_size == 1 {
+            return usize::from(self.rounds.is_empty());
+        }
+        max_rounds_upper_bound(&self.conflicts, self.min_group_size)
+    }
+
+    /// The rounds committed so far.
+    pub fn rounds(&self) -> &[Vec<RawGroup>] {
+        &self.rounds
+    }
+
+    /// The current conflict state, including committed rounds.
+    pub fn conflicts(&self) -> &ConflictMatrix {
+        &self.conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::diagonal;
+
+    #[test]
+    fn options_shrink_as_rounds_are_committed() {
+        let mut scheduler = Scheduler::new(diagonal(4), 2);
+        assert_eq!(
+            scheduler.round_options(),
+            vec![vec![vec![0, 1], vec![2, 3]], vec![vec![0, 2], vec![1, 3]], vec![vec![0, 3], vec![1, 2]]]
+        );
+        assert_eq!(scheduler.remaining_bound(), 3);
+        scheduler.commit(vec![vec![2, 0], vec![3, 1]]).unwrap();
+        assert_eq!(scheduler.round_options(), vec![vec![vec![0, 1], vec![2, 3]], vec![vec![0, 3], vec![1, 2]]]);
+        assert!(scheduler.conflicts().is_conflicted(0, 2));
+        assert_eq!(scheduler.rounds(), &[vec![vec![2, 0], vec![3, 1]]]);
+    }
+
+    #[test]
+    fn rejects_invalid_rounds() {
+        let mut scheduler = Scheduler::new(diagonal(4), 2);
+        scheduler.commit(vec![vec![0, 1], vec![2, 3]]).unwrap();
+        for round in [
+            vec![vec![0, 1], vec![2, 3]],
+            vec![vec![0, 2], vec![1]],
+            vec![vec![0, 2], vec![1, 3, 4]],
+            vec![vec![0, 2, 3, 1]],
+        ] {
+            assert_eq!(scheduler.commit(round), Err(GroupError::InvalidRound));
+        }
+        assert_eq!(scheduler.rounds().len(), 1);
+    }
+
+    #[test]
+    fn undo_restores_conflicts() {
+        let mut conflicts = diagonal(6);
+        conflicts.add_conflict(0, 1);
+        let mut scheduler = Scheduler::new(conflicts.clone(), 2);
+        assert_eq!(scheduler.undo(), None);
+        let first = scheduler.round_options()[0].clone();
+        scheduler.commit(first.clone()).unwrap();
+        let options = scheduler.round_options();
+        scheduler.commit(options[0].clone()).unwrap();
+        assert_eq!(scheduler.undo(), Some(options[0].clone()));
+        assert_eq!(scheduler.round_options(), options);
+        assert_eq!(scheduler.rounds(), &[first]);
+
+        scheduler.commit(options[0].clone()).unwrap();
+        scheduler.reset();
+        assert!(scheduler.rounds().is_empty());
+        assert_eq!(scheduler.conflicts(), &conflicts);
+    }
+
+    #[test]
+    fn singleton_sessions_end_after_one_round() {
+        let mut scheduler = Scheduler::new(diagonal(3), 1);
+        assert_eq!(scheduler.remaining_bound(), 1);
+        scheduler.commit(scheduler.round_options()[0].clone()).unwrap();
+        assert!(scheduler.round_options().is_empty());
+        assert_eq!(scheduler.remaining_bound(), 0);
+        assert_eq!(scheduler.commit(vec![vec![0], vec![1], vec![2]]), Err(GroupError::InvalidRound));
+        assert_eq!(scheduler.rounds().len(), 1);
+    }
+}
@@ -0,0 +1,109 @@
+//! # Partially Known Conflicts
+//!
+//! Solving when the conflict status of some pairs is not known, by resolving
+//! every unknown entry one way or the other before running the usual search.
+use alloc::vec::Vec;
+
+use crate::{make_assignments, ConflictMatrix, RawGroup};
+
+/// Conflict status of one pair of vertices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConflictStatus {
+    /// The pair may never share a group.
+    Conflict,
+    /// The pair may share a group.
+    NoConflict,
+    /// Not known whether the pair may share a group.
+    Unknown,
+}
+
+/// How [`make_assignments_ternary()`] interprets [`ConflictStatus::Unknown`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnknownMode {
+    /// Best case: unknowns are treated as no-conflict. This gives the most
+    /// rounds the data could possibly allow, but a schedule may group a pair
+    /// which later turns out to conflict.
+    Optimistic,
+    /// Worst case: unknowns are treated as conflicts. Every schedule stays
+    /// valid however the unknowns resolve, at the cost of possibly fewer
+    /// rounds.
+    Pessimistic,
+}
+
+/// Resolve a ternary conflict matrix into the boolean form used by the
+/// solver, interpreting unknown entries according to `mode`.
+///
+/// The diagonal is always set, as usual. A pair conflicts if its entry
+/// resolves to a conflict in either direction.
+///
+/// # Panics
+///
+/// Panics if a row is longer than the number of rows.
+pub fn resolve_conflicts(matrix: &[Vec<ConflictStatus>], mode: UnknownMode) -> ConflictMatrix {
+    let conflicting = |status: &ConflictStatus| match status {
+        ConflictStatus::Conflict => true,
+        ConflictStatus::NoConflict => false,
+        ConflictStatus::Unknown => mode == UnknownMode::Pessimistic,
+    };
+    let pairs = matrix.iter().enumerate().flat_map(|(i, row)| {
+        row.iter()
+            .enumerate()
+            .filter(move |(j, status)| i != *j && conflicting(status))
+            .map(move |(j, _)| (i, j))
+    });
+    ConflictMatrix::empty(matrix.len()).with_pairs(pairs)
+}
+
+/// Generate all *maximum-round* assignments (as in
+/// [`make_assignments()`]) for a matrix with unknown entries, resolved
+/// according to `mode` (see [`resolve_conflicts()`]).
+///
+/// # Panics
+///
+/// Panics under the same conditions as [`make_assignments()`].
+pub fn make_assignments_ternary(
+    matrix: &[Vec<ConflictStatus>],
+    min_group_size: usize,
+    mode: UnknownMode,
+) -> Vec<Vec<Vec<RawGroup>>> {
+    make_assignments(&mut resolve_conflicts(matrix, mode), min_group_size)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::validate_assignment;
+    use ConflictStatus::*;
+
+    /// Four vertices where it is unknown whether 0 and 1 get along.
+    fn unknown_pair() -> Vec<Vec<ConflictStatus>> {
+        let mut matrix = vec![vec![NoConflict; 4]; 4];
+        matrix[0][1] = Unknown;
+        matrix[1][0] = Unknown;
+        matrix
+    }
+
+    #[test]
+    fn optimistic_allows_unknown_pairs() {
+        let res = make_assignments_ternary(&unknown_pair(), 2, UnknownMode::Optimistic);
+        assert!(res.iter().all(|a| a.len() == 3));
+        assert!(res.iter().all(|a| a.iter().flatten().any(|g| g.contains(&0) && g.contains(&1))));
+    }
+
+    #[test]
+    fn pessimistic_keeps_unknown_pairs_apart() {
+        let res = make_assignments_ternary(&unknown_pair(), 2, UnknownMode::Pessimistic);
+        for assignment in &res {
+            assert_eq!(assignment.len(), 2);
+            assert!(validate_assignment(assignment, 4, 2));
+            assert!(!assignment.iter().flatten().any(|g| g.contains(&0) && g.contains(&1)));
+        }
+    }
+
+    #[test]
+    fn diagonal_always_set() {
+        let matrix = vec![vec![NoConflict; 2]; 2];
+        let resolved = resolve_conflicts(&matrix, UnknownMode::Optimistic);
+        assert_eq!(resolved, ConflictMatrix::empty(2));
+    }
+}